@@ -0,0 +1,50 @@
+use chrono::offset::TimeZone;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+// a long input with dates, times, typos and plain text in between
+fn long_input(sentences: usize) -> String {
+    "let's meet nxet friday at 5pm or tomorrow evening, but not on 03/05/2019. ".repeat(sentences)
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let tz_aware = chrono_tz::Europe::Moscow.ymd(2019, 1, 1).and_hms(12, 0, 0);
+    let input = long_input(50);
+
+    let config = when::ParseConfig::default();
+    c.bench_function("parse long input", |b| {
+        b.iter(|| when::en(tz_aware, black_box(&input), &config))
+    });
+
+    let config = when::ParseConfig {
+        exact_match: true,
+        ..Default::default()
+    };
+    c.bench_function("parse long input exactly", |b| {
+        b.iter(|| when::en(tz_aware, black_box(&input), &config))
+    });
+}
+
+// parsing time must grow linearly with the input length
+fn bench_input_length(c: &mut Criterion) {
+    let tz_aware = chrono_tz::Europe::Moscow.ymd(2019, 1, 1).and_hms(12, 0, 0);
+    let config = when::ParseConfig::default();
+
+    let mut group = c.benchmark_group("input length");
+    for sentences in &[1, 10, 100] {
+        let input = long_input(*sentences);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(sentences),
+            &input,
+            |b, input| b.iter(|| when::en(tz_aware, black_box(input), &config)),
+        );
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    // a single parse of the longest input takes about a second
+    config = Criterion::default().sample_size(10);
+    targets = bench_parse, bench_input_length
+}
+criterion_main!(benches);
@@ -0,0 +1,112 @@
+use crate::rules::config::ParseConfig;
+use crate::rules::errors::DateTimeError;
+use crate::rules::rules::MatchResult;
+use crate::rules::{de, en, es, fr, ru};
+use chrono::{offset::TimeZone, DateTime};
+
+/// Set of rules for one natural language, allows to choose a language at runtime.
+pub trait Language<Tz: TimeZone> {
+    fn parse(
+        &self,
+        tz_aware: DateTime<Tz>,
+        input: &str,
+        config: &ParseConfig,
+    ) -> Vec<Result<MatchResult, DateTimeError>>;
+}
+
+pub struct English;
+pub struct German;
+pub struct French;
+pub struct Spanish;
+pub struct Russian;
+
+impl<Tz: TimeZone> Language<Tz> for English {
+    fn parse(
+        &self,
+        tz_aware: DateTime<Tz>,
+        input: &str,
+        config: &ParseConfig,
+    ) -> Vec<Result<MatchResult, DateTimeError>> {
+        en::parse(tz_aware, input, config)
+    }
+}
+
+impl<Tz: TimeZone> Language<Tz> for German {
+    fn parse(
+        &self,
+        tz_aware: DateTime<Tz>,
+        input: &str,
+        config: &ParseConfig,
+    ) -> Vec<Result<MatchResult, DateTimeError>> {
+        de::parse(tz_aware, input, config)
+    }
+}
+
+impl<Tz: TimeZone> Language<Tz> for French {
+    fn parse(
+        &self,
+        tz_aware: DateTime<Tz>,
+        input: &str,
+        config: &ParseConfig,
+    ) -> Vec<Result<MatchResult, DateTimeError>> {
+        fr::parse(tz_aware, input, config)
+    }
+}
+
+impl<Tz: TimeZone> Language<Tz> for Spanish {
+    fn parse(
+        &self,
+        tz_aware: DateTime<Tz>,
+        input: &str,
+        config: &ParseConfig,
+    ) -> Vec<Result<MatchResult, DateTimeError>> {
+        es::parse(tz_aware, input, config)
+    }
+}
+
+impl<Tz: TimeZone> Language<Tz> for Russian {
+    fn parse(
+        &self,
+        tz_aware: DateTime<Tz>,
+        input: &str,
+        config: &ParseConfig,
+    ) -> Vec<Result<MatchResult, DateTimeError>> {
+        ru::parse(tz_aware, input, config)
+    }
+}
+
+/// Chooses the language of the input, there is no detection yet, so it is always English.
+pub fn detect_language<Tz: TimeZone>(_input: &str) -> Box<dyn Language<Tz>> {
+    Box::new(English)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{detect_language, English, Language};
+    use crate::rules::config::ParseConfig;
+    use chrono::prelude::*;
+
+    fn fixed_time() -> DateTime<Local> {
+        // 2019 1st January, Tuesday
+        Local.ymd(2019, 1, 1).and_hms(0, 0, 0)
+    }
+
+    #[test]
+    fn test_english() {
+        let res = English.parse(fixed_time(), "friday", &ParseConfig::default());
+        assert_eq!(res.len(), 1);
+        assert_eq!(
+            res[0]
+                .as_ref()
+                .unwrap()
+                .get_timeshift()
+                .duration
+                .num_seconds(),
+            259200
+        );
+
+        let language = detect_language::<Local>("friday");
+        let res = language.parse(fixed_time(), "friday", &ParseConfig::default());
+        assert_eq!(res.len(), 1);
+    }
+}
@@ -2,9 +2,46 @@
 //!
 //! This library is inspired by https://github.com/olebedev/when
 
+pub mod language;
 pub mod parser;
 pub(crate) mod rules;
 
+pub use language::{detect_language, English, French, German, Language, Russian, Spanish};
 pub use parser::Parser;
+pub use rules::config::{
+    DateOrder, Hemisphere, ParseConfig, RuleKind, RulePriorities, RuleSet, SeasonConvention,
+};
+pub use rules::de;
+pub use rules::en;
 pub use rules::en::parse as en;
+pub use rules::errors;
 pub use rules::errors::DateTimeError;
+pub use rules::es;
+pub use rules::fr;
+pub use rules::registry::RuleRegistry;
+pub use rules::ru;
+pub use rules::rules::{Context, CustomRule, MatchResult, Recurrence, Segment};
+pub use rules::stats::ParseStats;
+pub use rules::tokens;
+
+/// Word recognizers the rules are built of, they allow custom rules to recognize words with the
+/// same typos tolerance. This API is unstable and may change between versions.
+///
+/// ```
+/// use nom::types::CompleteStr;
+/// use when::matchers::{recognize_word, Dist};
+/// use when::tokens::{PToken, Priority, Token, When};
+///
+/// let token = PToken::PToken(Token::When(When::Next), Priority(0));
+/// let (tail, desc) =
+///     recognize_word(CompleteStr("nxet week"), CompleteStr("next"), Dist(1), token).unwrap();
+/// assert_eq!(desc.dist, Dist(1));
+/// assert_eq!(tail, CompleteStr(" week"));
+/// ```
+#[cfg(feature = "unstable")]
+pub mod matchers {
+    pub use crate::rules::combinators::{
+        all_fits, best_fit, recognize_int, recognize_word, tokenize_word, Dist, Interpretation,
+    };
+    pub use crate::rules::rules::{MyResult, TokenDesc};
+}
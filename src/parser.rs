@@ -1,15 +1,21 @@
+use crate::rules::config::{
+    DateOrder, Hemisphere, ParseConfig, RulePriorities, RuleSet, SeasonConvention,
+};
 use crate::rules::errors::DateTimeError;
 use crate::rules::rules::{Context, MatchResult};
-use chrono::offset::{TimeZone, Utc};
-use chrono::{DateTime, Datelike, NaiveDateTime, Timelike};
+use chrono::offset::TimeZone;
+#[cfg(feature = "std-time")]
+use chrono::offset::Utc;
+use chrono::{DateTime, NaiveDateTime, Weekday};
 
 type ParserType<'a, Tz> =
-    Fn(DateTime<Tz>, &'a str, bool) -> Vec<Result<MatchResult, DateTimeError>>;
+    Fn(DateTime<Tz>, &'a str, &ParseConfig) -> Vec<Result<MatchResult, DateTimeError>>;
 
 pub struct Parser<'a, Tz: TimeZone + 'a> {
-    lang_parser:
-        Box<Fn(DateTime<Tz>, &'a str, bool) -> Vec<Result<MatchResult, DateTimeError>> + 'a>,
-    exact_match: bool,
+    lang_parser: Box<
+        Fn(DateTime<Tz>, &'a str, &ParseConfig) -> Vec<Result<MatchResult, DateTimeError>> + 'a,
+    >,
+    config: ParseConfig,
     max_dist: usize,
     tz: Tz,
 }
@@ -18,7 +24,7 @@ impl<'a, Tz: TimeZone + 'a> Parser<'a, Tz> {
     pub fn new(tz: Tz) -> Self {
         Parser {
             lang_parser: Box::new(super::en),
-            exact_match: false,
+            config: ParseConfig::default(),
             max_dist: 5,
             tz,
         }
@@ -34,8 +40,106 @@ impl<'a, Tz: TimeZone + 'a> Parser<'a, Tz> {
         self
     }
 
+    /// Replaces all the parse settings at once, e.g. with a config shared by several parsers.
+    pub fn config(mut self, config: ParseConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Replaces the timezone given to `new`, dates and times are resolved in it.
+    pub fn timezone(mut self, tz: Tz) -> Self {
+        self.tz = tz;
+        self
+    }
+
     pub fn fuzzy_parse(mut self, fuzzy_parse: bool) -> Self {
-        self.exact_match = !fuzzy_parse;
+        self.config.exact_match = !fuzzy_parse;
+        self
+    }
+
+    /// Same as `fuzzy_parse(!exact)`, words must match their patterns exactly.
+    pub fn exact(self, exact: bool) -> Self {
+        self.fuzzy_parse(!exact)
+    }
+
+    pub fn max_edit_distance(mut self, max_edit_distance: usize) -> Self {
+        self.config.max_edit_distance = Some(max_edit_distance);
+        self
+    }
+
+    /// Same as `max_edit_distance`, unlike `max_dist` it bounds typos in words rather than gaps
+    /// between merged matches.
+    pub fn max_distance(self, max_distance: usize) -> Self {
+        self.max_edit_distance(max_distance)
+    }
+
+    pub fn date_order(mut self, date_order: DateOrder) -> Self {
+        self.config.date_order = date_order;
+        self
+    }
+
+    pub fn preserve_case(mut self, preserve_case: bool) -> Self {
+        self.config.preserve_case = preserve_case;
+        self
+    }
+
+    pub fn max_matches(mut self, max_matches: usize) -> Self {
+        self.config.max_matches = Some(max_matches);
+        self
+    }
+
+    pub fn week_start(mut self, week_start: Weekday) -> Self {
+        self.config.week_start = week_start;
+        self
+    }
+
+    pub fn weekend_range(mut self, weekend_range: bool) -> Self {
+        self.config.weekend_range = weekend_range;
+        self
+    }
+
+    pub fn business_hours(mut self, business_hours: (u32, u32)) -> Self {
+        self.config.business_hours = business_hours;
+        self
+    }
+
+    pub fn season_convention(mut self, season_convention: SeasonConvention) -> Self {
+        self.config.season_convention = season_convention;
+        self
+    }
+
+    pub fn hemisphere(mut self, hemisphere: Hemisphere) -> Self {
+        self.config.hemisphere = hemisphere;
+        self
+    }
+
+    pub fn prefer_future(mut self, prefer_future: bool) -> Self {
+        self.config.prefer_future = prefer_future;
+        self
+    }
+
+    pub fn strict_meridiem(mut self, strict_meridiem: bool) -> Self {
+        self.config.strict_meridiem = strict_meridiem;
+        self
+    }
+
+    pub fn few_count(mut self, few_count: u32) -> Self {
+        self.config.few_count = few_count;
+        self
+    }
+
+    pub fn zero_time_of_day(mut self, zero_time_of_day: bool) -> Self {
+        self.config.zero_time_of_day = zero_time_of_day;
+        self
+    }
+
+    pub fn rules(mut self, rules: RuleSet) -> Self {
+        self.config.rules = rules;
+        self
+    }
+
+    pub fn rule_priorities(mut self, rule_priorities: RulePriorities) -> Self {
+        self.config.rule_priorities = rule_priorities;
         self
     }
 
@@ -43,6 +147,10 @@ impl<'a, Tz: TimeZone + 'a> Parser<'a, Tz> {
         &self.tz
     }
 
+    pub fn get_config(&self) -> &ParseConfig {
+        &self.config
+    }
+
     fn parser_helper(
         &self,
         now: NaiveDateTime,
@@ -50,13 +158,14 @@ impl<'a, Tz: TimeZone + 'a> Parser<'a, Tz> {
     ) -> (DateTime<Tz>, Vec<Result<Context, DateTimeError>>) {
         let tz_aware = self.tz.from_utc_datetime(&now);
 
-        let res = (self.lang_parser)(tz_aware.clone(), input, self.exact_match);
+        let res = (self.lang_parser)(tz_aware.clone(), input, &self.config);
         let merged = self.merge(res);
 
         (tz_aware, merged)
     }
 
     // convert date/time to chrono
+    #[cfg(feature = "std-time")]
     pub fn parse(&self, input: &'a str) -> Vec<Result<DateTime<Tz>, DateTimeError>> {
         let (tz_aware, merged) = self.parser_helper(Utc::now().naive_utc(), input);
         self.to_chrono(tz_aware, merged)
@@ -126,40 +235,7 @@ impl<'a, Tz: TimeZone + 'a> Parser<'a, Tz> {
         let mut ready: Vec<Result<DateTime<Tz>, DateTimeError>> = Vec::new();
 
         for ctx in merged {
-            if ctx.is_err() {
-                ready.push(Err(ctx.unwrap_err()));
-                continue;
-            }
-
-            let ctx = ctx.unwrap();
-
-            let mut tz_aware = date_time.clone();
-
-            tz_aware = tz_aware + ctx.duration;
-
-            if ctx.duration.num_seconds() % 60 == 0 {
-                tz_aware = tz_aware.with_second(0).unwrap();
-            }
-
-            if ctx.year.is_some() {
-                tz_aware = tz_aware.with_year(ctx.year.unwrap()).unwrap();
-            }
-            if ctx.month.is_some() {
-                tz_aware = tz_aware.with_month(ctx.month.unwrap() as u32).unwrap();
-            }
-            if ctx.day.is_some() {
-                tz_aware = tz_aware.with_day(ctx.day.unwrap() as u32).unwrap();
-            }
-            if ctx.hour.is_some() {
-                tz_aware = tz_aware.with_hour(ctx.hour.unwrap() as u32).unwrap();
-            }
-            if ctx.minute.is_some() {
-                tz_aware = tz_aware.with_minute(ctx.minute.unwrap() as u32).unwrap();
-            }
-
-            tz_aware = tz_aware.with_nanosecond(0).unwrap();
-
-            ready.push(Ok(tz_aware));
+            ready.push(ctx.and_then(|ctx| ctx.apply(date_time.clone())));
         }
         ready
     }
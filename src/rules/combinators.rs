@@ -1,25 +1,30 @@
-use super::rules::{FnRule, MatchBounds, MatchResult, MyResult, RuleResult, TokenDesc};
+use super::config::{ParseConfig, RuleKind};
+use super::rules::{
+    CharCounter, Context, CustomRule, FnRule, MatchBounds, MatchResult, MyResult, RuleResult,
+    TokenDesc,
+};
+use super::stats;
 
-use core::borrow::BorrowMut;
 use nom::{
-    self, alt, char, map, map_res, named, named_args, opt, pair, preceded, recognize, tag,
-    take_while, tuple, types::CompleteStr, ErrorKind,
+    self, alt, char, map, map_res, named, named_args, opt, pair, preceded, recognize, tag, take,
+    take_while, tuple, types::CompleteStr, verify, ErrorKind,
 };
+use std::cmp::Reverse;
 
 use chrono::{DateTime, TimeZone};
 use strsim::damerau_levenshtein;
 
-pub use super::errors::{intersection_error, DateTimeError, SemanticError};
+pub(crate) use super::errors::{intersection_error, DateTimeError, SemanticError};
 
-#[derive(Debug, Clone, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 pub struct Dist(pub usize);
 
 macro_rules! set {
-    ( max_dist = $max_dist: expr, $exact_match: expr ) => {
-        if !$exact_match {
+    ( max_dist = $max_dist: expr, $dist_limit: expr ) => {
+        if $max_dist < $dist_limit {
             $max_dist
         } else {
-            crate::rules::combinators::Dist(0)
+            $dist_limit
         }
     };
 }
@@ -34,11 +39,11 @@ macro_rules! set {
 ///
 /// instead of clumsy:
 ///
-/// named_args!(saturday<'a>(exact_match: bool)<CompleteStr<'a>, MatchResult<'a>>,
+/// named_args!(saturday<'a>(dist_limit: Dist)<CompleteStr<'a>, MatchResult<'a>>,
 ///    alt!(
-///        call!(recognize_word, CompleteStr("saturday"), set!(max_dist=2, exact_match),
+///        call!(recognize_word, CompleteStr("saturday"), set!(max_dist=2, dist_limit),
 ///            &Weekday::Saturday) |
-///        call!(recognize_word, CompleteStr("sat"), set!(max_dist=0, exact_match),
+///        call!(recognize_word, CompleteStr("sat"), set!(max_dist=0, dist_limit),
 ///            &Weekday::Saturday)
 ///    )
 /// );
@@ -61,16 +66,16 @@ macro_rules! set {
 /// Use "combine!" combinator if you want to choose the best fit word among the list.
 macro_rules! define {
     ( $func_name: ident: ($token: expr, $p: expr), $repr: expr, $max_dist: expr ) => (
-        named_args!(pub $func_name<'a>(exact_match: bool)<CompleteStr<'a>, TokenDesc>,
+        named_args!(pub $func_name<'a>(dist_limit: Dist)<CompleteStr<'a>, TokenDesc>,
             call!(crate::rules::combinators::recognize_word, CompleteStr($repr), set!(max_dist=$max_dist,
-                  exact_match), crate::rules::tokens::PToken::PToken($token, $p))
+                  dist_limit), crate::rules::tokens::PToken::PToken($token, $p))
         );
     );
     ( $func_name: ident: $([($token: expr, $p: expr), $repr: expr, $max_dist: expr])|* ) => (
-        named_args!(pub $func_name<'a>(exact_match: bool)<CompleteStr<'a>, TokenDesc>,
+        named_args!(pub $func_name<'a>(dist_limit: Dist)<CompleteStr<'a>, TokenDesc>,
             alt!(
                 $(call!(crate::rules::combinators::recognize_word, CompleteStr($repr), set!(max_dist=$max_dist,
-                        exact_match), crate::rules::tokens::PToken::PToken($token, $p))) |*
+                        dist_limit), crate::rules::tokens::PToken::PToken($token, $p))) |*
             )
         );
     );
@@ -99,7 +104,27 @@ macro_rules! define_char {
 ///
 /// define_num!(hour, (Token::Hour, 0));
 ///
+/// Numbers out of the inclusive range are not recognized if the range is given:
+///
+/// define_num!(year: (Token::Year, Priority(0)), 1900, 2200);
+///
 macro_rules! define_num {
+    ( $func_name: ident: ($ctor: expr, $p: expr), $min: expr, $max: expr ) => {
+        fn $func_name(input: CompleteStr) -> crate::rules::rules::MyResult {
+            if let Ok((tail, n)) = crate::rules::combinators::recognize_int(input) {
+                if n >= $min && n <= $max {
+                    return Ok((
+                        tail,
+                        TokenDesc::new(
+                            crate::rules::tokens::PToken::PToken($ctor(n as _), $p),
+                            crate::rules::combinators::Dist(0),
+                        ),
+                    ));
+                }
+            }
+            crate::rules::combinators::wrap_error(input, crate::rules::errors::UNKNOWN)
+        }
+    };
     ( $func_name: ident: ($ctor: expr, $p: expr) ) => {
         fn $func_name(input: CompleteStr) -> crate::rules::rules::MyResult {
             if let Ok((tail, n)) = crate::rules::combinators::recognize_int(input) {
@@ -125,8 +150,8 @@ macro_rules! define_num {
 /// Match considered as the best one if its Levenshtein distance is minimal compared to others.
 macro_rules! combine {
     ( $func_name: ident => $($f: ident) |* ) => (
-        named_args!(pub $func_name<'a>(exact_match: bool)<CompleteStr<'a>, TokenDesc>,
-            call!(crate::rules::combinators::best_fit, exact_match, vec![$(&$f),*])
+        named_args!(pub $func_name<'a>(dist_limit: Dist)<CompleteStr<'a>, TokenDesc>,
+            call!(crate::rules::combinators::best_fit, dist_limit, &[$(&$f),*])
         );
     );
 }
@@ -136,22 +161,41 @@ macro_rules! make_interpreter {
     ( positions = $n: expr ) => {
         use tuple::TupleElements;
 
-        pub(crate) fn interpret<Tz: TimeZone>(
-            input: &str,
-            exact_match: bool,
+        pub(crate) fn interpret<'a, Tz: TimeZone>(
+            input: &'a str,
+            config: &crate::rules::config::ParseConfig,
             tz: DateTime<Tz>,
-        ) -> Result<RuleResult, crate::rules::errors::SemanticError> {
+        ) -> Result<RuleResult<'a>, crate::rules::errors::SemanticError<'a>> {
             let mut res = RuleResult::new();
-            match parse(CompleteStr(input), exact_match) {
+            match parse(CompleteStr(input), config.dist_limit()) {
                 Ok((tail, (skipped, tt))) => {
                     let bounds =
                         crate::rules::combinators::match_bounds(skipped.iter().sum(), input, tail);
                     res.set_bounds(Some(bounds));
                     for idx in 0..$n {
-                        res.set_token(tt.get(idx).unwrap());
+                        // a pattern with fewer tokens than positions is no match, a panic would
+                        // abort parsing of the whole input
+                        let token = match tt.get(idx) {
+                            Some(token) => token,
+                            None => {
+                                let mut res = RuleResult::new();
+                                res.set_tail(input);
+                                return Ok(res);
+                            }
+                        };
+                        if let crate::rules::tokens::PToken::Ambiguous(candidates) = &token.token {
+                            let mut err = crate::rules::errors::ambiguous_token_error(
+                                &input[bounds.start_idx..bounds.end_idx],
+                                candidates.clone(),
+                            );
+                            err.set_bounds(bounds);
+                            err.set_tail(tail);
+                            return Err(err);
+                        }
+                        res.set_token(token);
                     }
                     res.set_tail(*tail);
-                    match make_time(&res, tz, &input[bounds.start_idx..bounds.end_idx]) {
+                    match make_time(&res, tz, &input[bounds.start_idx..bounds.end_idx], config) {
                         Ok(ctx) => res.set_context(ctx),
                         Err(mut err) => {
                             err.set_bounds(bounds);
@@ -180,24 +224,63 @@ named!(trim<CompleteStr, CompleteStr>,
     take_while!(is_ignorable)
 );
 
-fn is_word_symbol(c: char) -> bool {
-    c == '.' || c == ':' || c.is_alphanumeric()
+fn is_apostrophe(c: char) -> bool {
+    c == '\'' || c == '\u{2019}'
+}
+
+// apostrophe is a part of a word only between two letters, e.g. "aujourd'hui", so that quotes
+// like in "on 'friday'" are not, non-alphanumeric symbols including unicode spaces and dashes are
+// not a part of a word either, see is_ignorable
+fn take_word(input: CompleteStr) -> nom::IResult<CompleteStr, CompleteStr> {
+    let mut end = input.len();
+    let mut prev = None;
+    let mut chars = input.char_indices().peekable();
+    while let Some((idx, c)) = chars.next() {
+        let is_word_symbol = if is_apostrophe(c) {
+            prev.map_or(false, char::is_alphabetic)
+                && chars
+                    .peek()
+                    .map_or(false, |&(_, next)| next.is_alphabetic())
+        } else {
+            c == '.' || c == ':' || c.is_alphanumeric()
+        };
+        if !is_word_symbol {
+            end = idx;
+            break;
+        }
+        prev = Some(c);
+    }
+    Ok((CompleteStr(&input[end..]), CompleteStr(&input[..end])))
+}
+
+// possessive "'s" is dropped, so "monday's" is the same as "monday"
+fn strip_possessive(word: &str) -> &str {
+    let mut chars = word.chars().rev();
+    match (chars.next(), chars.next()) {
+        (Some(s), Some(apostrophe)) if (s == 's' || s == 'S') && is_apostrophe(apostrophe) => {
+            &word[..word.len() - 1 - apostrophe.len_utf8()]
+        }
+        _ => word,
+    }
 }
 
 /// Ignores whitespaces using "trim" and then consumes alphabetical characters in a string until
 /// any non alpha-numeric character appears or the string has been exhausted:
 ///
 /// "  , abracadabra  " -> "abracadabra"
-named!(tokenize_word<CompleteStr, CompleteStr>,
-    preceded!(trim, take_while!(is_word_symbol))
+named!(pub tokenize_word<CompleteStr, CompleteStr>,
+    preceded!(trim, take_word)
 );
 
 /// Consumes all spaces before a word, the word itself and all spaces after the word and returns
-/// total number of consumed characters:
+/// total number of consumed characters, a symbol which is not a part of any word is consumed
+/// instead of the word, so that the rules get past it:
 ///
 /// "  , abracadabra  " -> 17
+/// " / 2019" -> 3
 named!(pub(crate) tokenize_count_symbols<CompleteStr, usize>,
-    map!(tuple!(trim, take_while!(|c: char| c == '.' || c == ':' || c.is_alphanumeric()), trim),
+    map!(tuple!(trim, alt!(verify!(take_word, |word: CompleteStr| !word.is_empty()) | take!(1)),
+                trim),
     |(prefix, word, suffix)| {
         prefix.len() + word.len() + suffix.len()
     })
@@ -208,7 +291,7 @@ named!(pub(crate) tokenize_count_symbols<CompleteStr, usize>,
 /// converts the number from the string representation into i32:
 ///
 /// "  , -321  " -> -321
-named!(pub(crate) recognize_int<CompleteStr, i32>,
+named!(pub recognize_int<CompleteStr, i32>,
     map!(
         preceded!(trim, pair!(
             opt!(alt!(tag!("+") | tag!("-"))),
@@ -219,6 +302,13 @@ named!(pub(crate) recognize_int<CompleteStr, i32>,
      })
 );
 
+// same as recognize_int, but takes no sign, so that a dash before the number is left for others:
+//
+// "  , 2019-01" -> 2019
+named!(pub(crate) recognize_uint<CompleteStr, u32>,
+    preceded!(trim, map_res!(recognize!(nom::digit), |s: CompleteStr| s.parse::<u32>()))
+);
+
 named_args!(pub recognize_symbol<'a>(c: char)<CompleteStr<'a>, char>,
     preceded!(trim, char!(c))
 );
@@ -226,15 +316,15 @@ named_args!(pub recognize_symbol<'a>(c: char)<CompleteStr<'a>, char>,
 /// Stub combinator should be used in situations when there are several alternatives
 /// are considered but they are differ in length, example:
 ///
-/// named_args!(parse<'a>(exact_match: bool)<CompleteStr<'a>, (Vec<CompleteStr<'a>>,
+/// named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<CompleteStr<'a>>,
 ///                             ( TokenDesc, TokenDesc, TokenDesc ) )>,
 ///     many_till!(take!(1),
 ///        alt!(
 ///            // day of week, when, "week", e.g. "tuesday next week"
-///            tuple!(apply!(day_of_week, exact_match), apply!(when, exact_match),
-///                   apply!(week_noun, exact_match)) |
+///            tuple!(apply!(day_of_week, dist_limit), apply!(when, dist_limit),
+///                   apply!(week_noun, dist_limit)) |
 ///            // when and then any day of week, e.g. "last friday"
-///            tuple!(apply!(when, exact_match), apply!(day_of_week, exact_match), call!(stub))
+///            tuple!(apply!(when, dist_limit), apply!(day_of_week, dist_limit), call!(stub))
 ///        )
 ///    )
 ///
@@ -263,15 +353,32 @@ pub(crate) fn wrap_error(input: CompleteStr, error_code: u32) -> MyResult {
 
 /// Tries to recognize a word using Domerau-Levenshtein algorithm, it is both simple enough and
 /// efficient.
-pub(crate) fn recognize_word<'a>(
+///
+/// The word matches if its Damerau-Levenshtein distance to the pattern is at most max_dist, the
+/// distance is returned along with the token in TokenDesc, so that the closest of several
+/// patterns may be chosen, see best_fit. Patterns must be lowercase.
+///
+/// Zero max_dist, i.e. exact match mode (see en::parse_exact), skips distance computation and
+/// uses plain string equality instead. Patterns of one or two letters like "am" always match
+/// exactly, any typo makes them a different word, e.g. "an".
+pub fn recognize_word<'a>(
     input: CompleteStr<'a>,
     pattern: CompleteStr<'a>,
     max_dist: crate::rules::combinators::Dist,
     token: super::tokens::PToken,
 ) -> MyResult<'a> {
-    if let Ok((tail, mut word)) = tokenize_word(input) {
-        // TODO: add comment why we replace "." with "" (examples a.m., p.m.)
-        let normalized_word = word.borrow_mut().replace(".", "");
+    let max_dist = if pattern.chars().count() <= 2 {
+        crate::rules::combinators::Dist(0)
+    } else {
+        max_dist
+    };
+    if let Ok((tail, word)) = tokenize_word(input) {
+        // dots and apostrophes are dropped, so "a.m." is the same as "am" and "aujourd'hui" is
+        // the same as "aujourdhui", patterns are lowercase, so the word is lowercased too in case
+        // the input is not (see ParseConfig::preserve_case)
+        let normalized_word = strip_possessive(word.0)
+            .replace(|c| c == '.' || is_apostrophe(c), "")
+            .to_lowercase();
         if max_dist == crate::rules::combinators::Dist(0) {
             // when max_dist is 0 perform just plain string comparison
             if normalized_word == *pattern {
@@ -281,6 +388,7 @@ pub(crate) fn recognize_word<'a>(
                 ));
             }
         } else {
+            stats::record(|stats| stats.distance_computations += 1);
             let dist = Dist(damerau_levenshtein(&normalized_word, *pattern));
             if dist <= max_dist {
                 return Ok((tail, TokenDesc::new(token, dist)));
@@ -292,36 +400,92 @@ pub(crate) fn recognize_word<'a>(
 }
 
 /// Finds a minimal distance between an input word by applying all combinators from funcs.
-/// Each function accepts an input string and a flag which denotes whether exact match is required.
-pub(crate) fn best_fit<'a>(
+/// Each function accepts an input string and a maximum allowed distance. Combinators are passed
+/// as a slice, so that combine! doesn't allocate them on every call.
+///
+/// If several different tokens have the same minimal distance, i.e. all_fits finds
+/// Interpretation::Multiple, the one with the smallest priority number wins. Tokens which tie on
+/// both distance and priority are returned as PToken::Ambiguous, rule interpreter reports it as an
+/// error. Patterns of the same token don't make it ambiguous, e.g. "twenty" and "twentieth" both
+/// stand for the twentieth day of a month.
+pub fn best_fit<'a>(
     input: CompleteStr<'a>,
-    exact_match: bool,
-    combinators: Vec<&Fn(CompleteStr<'a>, bool) -> MyResult<'a>>,
+    dist_limit: Dist,
+    combinators: &[&dyn Fn(CompleteStr<'a>, Dist) -> MyResult<'a>],
 ) -> MyResult<'a> {
-    let mut min_dist = Dist(std::usize::MAX);
+    let (tail, fits) = all_fits(input, dist_limit, combinators)?;
+    let fits = match fits {
+        Interpretation::Single(fit) => return Ok((tail, fit)),
+        Interpretation::Multiple(fits) => fits,
+    };
 
-    let mut selected_token = crate::rules::tokens::PToken::Stub;
-    let mut selected_count = 0;
+    let priority = |fit: &TokenDesc| match &fit.token {
+        crate::rules::tokens::PToken::PToken(_, p) => p.0,
+        _ => std::isize::MAX,
+    };
+    let min_priority = fits.iter().map(priority).min().unwrap_or(std::isize::MAX);
+    let mut candidates: Vec<TokenDesc> = fits
+        .into_iter()
+        .filter(|fit| priority(fit) == min_priority)
+        .collect();
+
+    if candidates.len() == 1 {
+        return Ok((tail, candidates.remove(0)));
+    }
+    let dist = candidates[0].dist;
+    let tokens = candidates
+        .into_iter()
+        .filter_map(|fit| match fit.token {
+            crate::rules::tokens::PToken::PToken(token, _) => Some(token),
+            _ => None,
+        })
+        .collect();
+    Ok((
+        tail,
+        TokenDesc::new(crate::rules::tokens::PToken::Ambiguous(tokens), dist),
+    ))
+}
+
+/// Tokens recognized by all_fits.
+#[derive(Debug, Clone)]
+pub enum Interpretation {
+    Single(TokenDesc),
+    /// several different tokens are equally close to the word
+    Multiple(Vec<TokenDesc>),
+}
+
+/// Same as best_fit, but instead of choosing between tokens with the same minimal distance it
+/// returns all of them regardless of their priorities, so that the caller is able to choose.
+pub fn all_fits<'a>(
+    input: CompleteStr<'a>,
+    dist_limit: Dist,
+    combinators: &[&dyn Fn(CompleteStr<'a>, Dist) -> MyResult<'a>],
+) -> nom::IResult<CompleteStr<'a>, Interpretation> {
+    let mut min_dist = Dist(std::usize::MAX);
+    let mut fits: Vec<TokenDesc> = Vec::new();
     let mut selected_tail = CompleteStr("");
 
     for comb in combinators {
-        if let Ok((tail, TokenDesc { token, dist })) = comb(input, exact_match) {
-            if min_dist > dist {
-                selected_token = token;
+        if let Ok((tail, desc)) = comb(input, dist_limit) {
+            if desc.dist < min_dist {
+                fits.clear();
+                min_dist = desc.dist;
                 selected_tail = tail;
-                selected_count = 1;
-                min_dist = dist;
-            } else if min_dist == dist {
-                selected_count += 1;
+            }
+            if desc.dist == min_dist && fits.iter().all(|fit| fit.token != desc.token) {
+                fits.push(desc);
             }
         }
     }
 
-    if selected_count == 1 {
-        return Ok((selected_tail, TokenDesc::new(selected_token, min_dist)));
+    match fits.len() {
+        0 => Err(nom::Err::Error(nom::simple_errors::Context::Code(
+            input,
+            ErrorKind::Custom(crate::rules::errors::UNKNOWN),
+        ))),
+        1 => Ok((selected_tail, Interpretation::Single(fits.remove(0)))),
+        _ => Ok((selected_tail, Interpretation::Multiple(fits))),
     }
-
-    wrap_error(input, crate::rules::errors::UNKNOWN)
 }
 
 pub(crate) fn remove_overlapped<'a>(
@@ -383,9 +547,225 @@ pub(crate) fn remove_overlapped<'a>(
     result
 }
 
+/// Words which modify a match when they go around it, they are specific to a language, e.g.
+/// en::MODIFIERS. Markers are lowercase and are compared to whole words just like patterns of the
+/// rules, see recognize_word.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct Modifiers {
+    /// words which make a date/time approximate when they go right before it, e.g. "around 5pm"
+    pub(crate) approximation: &'static [&'static str],
+    /// words which negate a date/time when they go right before it, e.g. "not tomorrow"
+    pub(crate) negation: &'static [&'static str],
+    /// recognizes a relative adjustment right after a match, e.g. "plus 2 days", it gets the text
+    /// after the match and returns the length of the adjustment and its value in seconds
+    pub(crate) adjustment: Option<fn(&str) -> Option<(usize, i64)>>,
+}
+
+/// Returns the last word of the prefix skipped by a rule before its match along with the offset
+/// of the word in the prefix, the words are the same ones the rules skip, see
+/// tokenize_count_symbols. There is no word if anything else separates it from the match.
+/// apply_generic scans the words itself, MatchIter looks for them in the prefix.
+fn last_skipped_word(prefix: &str) -> Option<(usize, &str)> {
+    let mut rest = CompleteStr(prefix);
+    let mut last = None;
+    while let Ok((tail, word)) = tokenize_word(rest) {
+        if word.is_empty() {
+            break;
+        }
+        last = Some((prefix.len() - tail.len() - word.len(), *word));
+        rest = tail;
+    }
+    if rest.trim_start_matches(is_ignorable).is_empty() {
+        last
+    } else {
+        None
+    }
+}
+
+// markers are exact, a typo in a short word like "no" makes it a different word
+fn is_marker(word: &str, markers: &[&str]) -> bool {
+    markers.iter().any(|marker| {
+        recognize_word(
+            CompleteStr(word),
+            CompleteStr(marker),
+            Dist(0),
+            crate::rules::tokens::PToken::Stub,
+        )
+        .is_ok()
+    })
+}
+
+/// Text skipped by a rule before its match: where the rule has started to look for the match
+/// and the last skipped word right before the match along with its start, if there is any.
+#[derive(Clone, Copy)]
+struct Skipped<'s> {
+    start: usize,
+    last_word: Option<(usize, &'s str)>,
+}
+
+/// Looks for an approximation marker right before the match start among the words skipped by
+/// the rule, returns the start of the marker if there is any, so that the marker becomes a part
+/// of the match. A tilde is a marker in every language, e.g. "~5pm".
+fn approximation_start(
+    source_str: &str,
+    modifiers: &Modifiers,
+    skipped: Skipped,
+    start_idx: usize,
+) -> Option<usize> {
+    let gap_start = skipped
+        .last_word
+        .map_or(skipped.start, |(word_start, word)| word_start + word.len());
+    let gap = source_str[gap_start..start_idx].trim_end();
+    if gap.ends_with('~') {
+        return Some(gap_start + gap.len() - 1);
+    }
+    let (word_start, word) = skipped.last_word?;
+    if is_marker(word, modifiers.approximation) {
+        Some(word_start)
+    } else {
+        None
+    }
+}
+
+/// Tells whether the last word skipped by the rule before its match is a negation marker, the
+/// marker is not a part of the match.
+fn is_negated(modifiers: &Modifiers, skipped: Skipped) -> bool {
+    skipped
+        .last_word
+        .map_or(false, |(_, word)| is_marker(word, modifiers.negation))
+}
+
+/// Creates a match result, an approximation marker before the match and an adjustment after it
+/// are included into it. Approximate is set for matches which are vague on their own, e.g.
+/// "in a few days". The found item is a context with its distance and approximate flag, the way
+/// rules return it.
+fn make_match_result(
+    source_str: &str,
+    modifiers: &Modifiers,
+    skipped: Skipped,
+    bounds: MatchBounds,
+    (mut context, distance, approximate): (Context, usize, bool),
+) -> MatchResult {
+    let MatchBounds {
+        start_idx,
+        mut end_idx,
+    } = bounds;
+    let adjustment = modifiers
+        .adjustment
+        .and_then(|adjust| adjust(&source_str[end_idx..]));
+    if let Some((adjustment_len, seconds)) = adjustment {
+        context.adjust(seconds);
+        end_idx += adjustment_len;
+    }
+
+    let marker_start = approximation_start(source_str, modifiers, skipped, start_idx);
+    let negated = is_negated(modifiers, skipped);
+    let start_idx = marker_start.unwrap_or(start_idx);
+    let mut match_result = MatchResult::new(context, start_idx, end_idx);
+    match_result.set_matched_text(&source_str[start_idx..end_idx]);
+    match_result.set_approximate(approximate || marker_start.is_some());
+    match_result.set_negated(negated);
+    match_result.set_distance(distance);
+    match_result
+}
+
+// words which may stand between a date and a time of one match, e.g. "friday at 5pm"
+const DATE_TIME_CONNECTORS: [&str; 2] = ["at", "@"];
+
+/// Merges a date and a time right after it into a single match, e.g. "next friday at 5pm",
+/// returns None if the matches don't make up a date with a time.
+fn join_date_time(source_str: &str, date: &MatchResult, time: &MatchResult) -> Option<MatchResult> {
+    let gap = &source_str[date.get_end_idx()..time.get_start_idx()];
+    let is_connected = gap
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|word| !word.is_empty())
+        .all(|word| DATE_TIME_CONNECTORS.contains(&word));
+
+    if !is_connected
+        || date.get_timeshift().has_time()
+        || !time.get_timeshift().has_time()
+        || time.get_timeshift().has_date()
+    {
+        return None;
+    }
+
+    let mut merged = date.get_timeshift().clone();
+    merged.update(time.get_timeshift());
+
+    let start_idx = date.get_start_idx();
+    let end_idx = time.get_end_idx();
+    let mut match_result = MatchResult::new(merged, start_idx, end_idx);
+    match_result.set_matched_text(&source_str[start_idx..end_idx]);
+    match_result.set_approximate(date.is_approximate() || time.is_approximate());
+    match_result.set_negated(date.is_negated());
+    match_result.set_distance(date.distance() + time.distance());
+    Some(match_result)
+}
+
+/// Merges every date immediately followed by a time into a single match, see join_date_time.
+/// Matches must be sorted by their start offsets and must not intersect.
+pub(crate) fn merge_date_time<'a>(
+    source_str: &str,
+    matched_tokens: Vec<Result<MatchResult, SemanticError<'a>>>,
+) -> Vec<Result<MatchResult, SemanticError<'a>>> {
+    let mut result: Vec<Result<MatchResult, SemanticError>> = Vec::new();
+
+    for item in matched_tokens {
+        if let (Some(Ok(date)), Ok(time)) = (result.last_mut(), &item) {
+            if let Some(merged) = join_date_time(source_str, date, time) {
+                *date = merged;
+                continue;
+            }
+        }
+        result.push(item);
+    }
+
+    result
+}
+
+/// Leaves only the longest match among the matches which start at the same position, if there are
+/// several matches of the same length, the first one (i.e. the one produced by the rule with the
+/// highest priority, see ParseConfig::rule_priorities, or by the rule which comes first in the
+/// rules list) is kept. The priorities of the tokens a match is made of don't break the tie, they
+/// tell the places of the tokens in the pattern of their rule, see RuleResult::token_by_priority,
+/// so they are not comparable between different rules. Matches lying inside of the previous match
+/// are dropped too, e.g. "next week" in "end of next week": a rule for a part of a longer phrase
+/// still matches the part on its own, and remove_overlapped would report it along with the longer
+/// match as an intersection, although the longer match covers it. Matches which overlap only partly
+/// are kept, they are intersections. Matches must be sorted by their start offsets.
+pub(crate) fn keep_longest(
+    matched_tokens: Vec<Result<MatchResult, SemanticError>>,
+) -> Vec<Result<MatchResult, SemanticError>> {
+    let bounds = |item: &Result<MatchResult, SemanticError>| match item {
+        Ok(x) => (x.get_start_idx(), x.get_end_idx()),
+        Err(x) => (x.get_start_idx(), x.get_end_idx()),
+    };
+
+    let mut result: Vec<Result<MatchResult, SemanticError>> = Vec::new();
+
+    for item in matched_tokens {
+        if let Some(last) = result.last_mut() {
+            let (last_start, last_end) = bounds(last);
+            let (start, end) = bounds(&item);
+            if last_start == start {
+                if end > last_end {
+                    *last = item;
+                }
+                continue;
+            }
+            if end <= last_end {
+                continue;
+            }
+        }
+        result.push(item);
+    }
+
+    result
+}
+
 /// Generic rules applier, accepts a string to parse as its input and a slice of rules,
-/// then it tries to apply each rule from the list one by one, appending parsed tokens (if succeed)
-/// to the output vector.
+/// then it goes through the input word by word and tries every rule of the list at each word,
+/// appending parsed tokens (if succeed) to the output vector.
 ///
 /// Let's consider an example:
 ///
@@ -396,69 +776,395 @@ pub(crate) fn remove_overlapped<'a>(
 pub(crate) fn apply_generic<'a, Tz: TimeZone + 'a>(
     date_time: DateTime<Tz>,
     source_str: &'a str,
-    rules: &'a [FnRule<Tz>],
-    exact_match: bool,
+    rules: &'a [(RuleKind, FnRule<Tz>)],
+    modifiers: &Modifiers,
+    config: &ParseConfig,
+) -> Vec<Result<MatchResult, DateTimeError>> {
+    apply_rules(date_time, source_str, rules, &[], modifiers, config)
+}
+
+// built-in rule or a rule added at runtime
+enum AnyRule<'a, Tz: TimeZone> {
+    BuiltIn(FnRule<Tz>),
+    Custom(&'a CustomRule<Tz>),
+}
+
+impl<'a, Tz: TimeZone> AnyRule<'a, Tz> {
+    fn apply<'r>(
+        &self,
+        input: &'r str,
+        config: &ParseConfig,
+        date_time: DateTime<Tz>,
+    ) -> Result<RuleResult<'r>, SemanticError<'r>> {
+        match self {
+            AnyRule::BuiltIn(rule) => rule(input, config, date_time),
+            AnyRule::Custom(rule) => Ok(RuleResult::from_custom(
+                input,
+                rule(input, config, date_time),
+            )),
+        }
+    }
+}
+
+// a match or an error of a rule which the scan hasn't reached yet, its bounds are byte offsets
+// in the source string
+struct Found<'r> {
+    bounds: MatchBounds,
+    // where the rule has started to look for the match
+    prefix_start: usize,
+    item: Result<(Context, usize, bool), SemanticError<'r>>,
+}
+
+// a rule looks for its next match only once the scan has reached its previous one, so every rule
+// reads the input once
+struct RuleScan<'a, 'r, Tz: TimeZone> {
+    priority: isize,
+    rule: AnyRule<'a, Tz>,
+    // where the rule continues after its previous match
+    resume_idx: usize,
+    matches: usize,
+    found: Option<Found<'r>>,
+    done: bool,
+}
+
+impl<'a, 'r, Tz: TimeZone> RuleScan<'a, 'r, Tz> {
+    fn new(priority: isize, rule: AnyRule<'a, Tz>) -> Self {
+        Self {
+            priority,
+            rule,
+            resume_idx: 0,
+            matches: 0,
+            found: None,
+            done: false,
+        }
+    }
+
+    /// Returns the next match of the rule if it starts at the given offset or before it.
+    fn next_at(
+        &mut self,
+        idx: usize,
+        source_str: &'r str,
+        config: &ParseConfig,
+        date_time: &DateTime<Tz>,
+    ) -> Option<Found<'r>> {
+        if self.found.is_none() && !self.done {
+            self.found = self.find(source_str, config, date_time);
+            self.done = self.found.is_none();
+        }
+        if self.found.as_ref()?.bounds.start_idx > idx {
+            return None;
+        }
+        self.matches += 1;
+        self.found.take()
+    }
+
+    // applies the rule to the rest of the input, a failed match is skipped just like a successful
+    // one, so that the rule continues after it
+    fn find(
+        &mut self,
+        source_str: &'r str,
+        config: &ParseConfig,
+        date_time: &DateTime<Tz>,
+    ) -> Option<Found<'r>> {
+        let prefix_start = self.resume_idx;
+        let result = self
+            .rule
+            .apply(&source_str[prefix_start..], config, date_time.clone());
+        stats::record(|stats| stats.rule_attempts += 1);
+        let approximate = result.as_ref().map_or(false, RuleResult::is_approximate);
+        let (bounds, tail, item) = match result {
+            Ok(RuleResult {
+                tail,
+                bounds: Some(bounds),
+                distance,
+                context,
+                ..
+            }) => {
+                stats::record(|stats| stats.matches += 1);
+                (bounds, tail, Ok((context, distance, approximate)))
+            }
+            Ok(_) => return None,
+            Err(err) => (
+                MatchBounds::new(err.get_start_idx(), err.get_end_idx()),
+                err.get_tail(),
+                Err(err),
+            ),
+        };
+
+        let bounds = MatchBounds::new(
+            prefix_start + bounds.start_idx,
+            prefix_start + bounds.end_idx,
+        );
+        let item = item.map_err(|mut err| {
+            err.set_bounds(bounds);
+            err
+        });
+        // continue with the rest of the string
+        self.resume_idx = source_str.len() - tail.len();
+        Some(Found {
+            bounds,
+            prefix_start,
+            item,
+        })
+    }
+}
+
+/// Same as apply_generic, custom rules are applied after the built-in ones. Built-in rules are
+/// filtered by the config, custom rules are always applied.
+pub(crate) fn apply_rules<'a, Tz: TimeZone + 'a>(
+    date_time: DateTime<Tz>,
+    source_str: &'a str,
+    rules: &'a [(RuleKind, FnRule<Tz>)],
+    custom: &'a [Box<CustomRule<Tz>>],
+    modifiers: &Modifiers,
+    config: &ParseConfig,
 ) -> Vec<Result<MatchResult, DateTimeError>> {
     // empty vector of matched tokens and errors
     let mut matched_tokens = Vec::new();
 
-    for rule in rules {
-        let mut input = source_str;
-        let mut end_of_last_match_idx = 0;
+    let mut scans: Vec<RuleScan<Tz>> = rules
+        .iter()
+        .filter(|(kind, _)| config.rules.contains(*kind))
+        .map(|(kind, rule)| (config.rule_priorities.get(*kind), AnyRule::BuiltIn(*rule)))
+        .chain(
+            custom
+                .iter()
+                .map(|rule| (0, AnyRule::Custom(rule.as_ref()))),
+        )
+        .map(|(priority, rule)| RuleScan::new(priority, rule))
+        .collect();
 
-        // try to apply one rule as many times as possible
-        loop {
-            match rule(input, exact_match, date_time.clone()) {
-                Ok(RuleResult {
-                    tail,
-                    bounds: Some(bounds),
-                    context,
-                    ..
-                }) => {
-                    // applied rule had a match
-                    matched_tokens.push(Ok(MatchResult::new(
-                        context,
-                        end_of_last_match_idx + bounds.start_idx,
-                        end_of_last_match_idx + bounds.end_idx,
-                    )));
-
-                    // continue with the rest of the string
-                    end_of_last_match_idx += bounds.end_idx;
-                    input = tail;
-                }
-                Ok(RuleResult { bounds: None, .. }) => {
-                    // being inside this branch means that no more matches were found, we consider
-                    // current rule as fully applied and must continue with the next rule
-                    break;
-                }
-                Err(err) => {
-                    input = err.get_tail();
-                    matched_tokens.push(Err(err));
+    // every rule stops after max_matches matches of its own, the matches of all rules are
+    // complete only before the point where the first rule has stopped
+    let max_matches = config.max_matches.unwrap_or(std::usize::MAX);
+    let mut complete_until = source_str.len();
+
+    // the input is scanned word by word and every rule is tried at each word, the words before
+    // a match are the ones the rule has skipped, so markers like "not" are found right here
+    let mut pos = 0;
+    let mut last_word: Option<(usize, &str)> = None;
+    loop {
+        let word_start = pos + source_str[pos..].len()
+            - source_str[pos..].trim_start_matches(is_ignorable).len();
+
+        for scan in &mut scans {
+            while let Some(found) = scan.next_at(word_start, source_str, config, &date_time) {
+                if scan.matches >= max_matches {
+                    complete_until = complete_until.min(scan.resume_idx);
+                    scan.done = true;
                 }
+                let skipped = Skipped {
+                    start: found.prefix_start,
+                    last_word: last_word.filter(|(start, _)| {
+                        *start >= found.prefix_start && found.bounds.start_idx == word_start
+                    }),
+                };
+                let Found { bounds, item, .. } = found;
+                let item = item
+                    .map(|found| make_match_result(source_str, modifiers, skipped, bounds, found));
+                matched_tokens.push((scan.priority, item));
             }
         }
+
+        if word_start == source_str.len() || scans.iter().all(|scan| scan.done) {
+            break;
+        }
+
+        // symbols which are neither words nor ignorable, e.g. "/", are skipped one by one
+        let word = tokenize_word(CompleteStr(&source_str[word_start..]))
+            .map_or(CompleteStr(""), |(_, word)| word);
+        if word.is_empty() {
+            last_word = None;
+            pos = word_start
+                + source_str[word_start..]
+                    .chars()
+                    .next()
+                    .map_or(1, char::len_utf8);
+        } else {
+            last_word = Some((word_start, *word));
+            pos = word_start + word.len();
+        }
     }
 
     // all rules were applied at this step and the results were saved into matched_tokens vector
 
-    // first of all we sort resulting vector by tokens start offsets
-    matched_tokens.sort_by_key(|k| match k {
-        Ok(x) => x.get_start_idx(),
-        Err(x) => x.get_start_idx(),
+    // first of all we sort resulting vector by tokens start offsets, matches of rules with higher
+    // priorities go first among the ones at the same position
+    matched_tokens.sort_by_key(|(priority, k)| match k {
+        Ok(x) => (x.get_start_idx(), Reverse(*priority)),
+        Err(x) => (x.get_start_idx(), Reverse(*priority)),
     });
+    let matched_tokens = matched_tokens.into_iter().map(|(_, k)| k).collect();
+
+    // several rules may match at the same position, only the longest match is kept
+    let matched_tokens = keep_longest(matched_tokens);
 
     // then look for tokens bounds intersections, and treat them as errors
     let tmp = remove_overlapped(source_str, &matched_tokens);
 
-    // unbox errors
+    // a date and a time right after it make up a single match
+    let tmp = merge_date_time(source_str, tmp);
+
+    // unbox errors, rules work with byte offsets while users get offsets in characters, only the
+    // leftmost matches are kept if their number is limited
+    let mut counter = CharCounter::default();
     tmp.iter()
+        .take_while(|item| match item {
+            Ok(x) => x.get_start_idx() < complete_until,
+            Err(x) => x.get_start_idx() < complete_until,
+        })
+        .take(max_matches)
         .map(|item| match item {
             Err(x) => Err(x.extract_error()),
-            Ok(x) => Ok(*x),
+            Ok(x) => {
+                let mut x = x.clone();
+                x.set_bounds(x.get_bounds().to_char_bounds(source_str, &mut counter));
+                Ok(x)
+            }
         })
         .collect()
 }
 
+/// Iterator over matches of the rules, see en::parse_iter.
+pub(crate) struct MatchIter<Tz: TimeZone> {
+    date_time: DateTime<Tz>,
+    input: String,
+    rules: Vec<(RuleKind, FnRule<Tz>)>,
+    modifiers: Modifiers,
+    config: ParseConfig,
+    end_of_last_match_idx: usize,
+    counter: CharCounter,
+    // match found ahead while looking for a time right after a date
+    pending: Option<Result<MatchResult, DateTimeError>>,
+}
+
+impl<Tz: TimeZone> MatchIter<Tz> {
+    pub(crate) fn new(
+        date_time: DateTime<Tz>,
+        input: String,
+        rules: &[(RuleKind, FnRule<Tz>)],
+        modifiers: &Modifiers,
+        config: &ParseConfig,
+    ) -> Self {
+        Self {
+            date_time,
+            input,
+            rules: rules
+                .iter()
+                .filter(|(kind, _)| config.rules.contains(*kind))
+                .cloned()
+                .collect(),
+            modifiers: *modifiers,
+            config: *config,
+            end_of_last_match_idx: 0,
+            counter: CharCounter::default(),
+            pending: None,
+        }
+    }
+
+    // finds the next match, its bounds are byte offsets
+    fn next_match(&mut self) -> Option<Result<MatchResult, DateTimeError>> {
+        let input = &self.input[self.end_of_last_match_idx..];
+
+        // every rule looks for its nearest match, the leftmost one is taken, if several rules
+        // match at the same position the longest match is kept, just like in apply_generic
+        let mut nearest: Option<(
+            MatchBounds,
+            isize,
+            Result<(Context, usize, bool), DateTimeError>,
+        )> = None;
+        for (kind, rule) in &self.rules {
+            let priority = self.config.rule_priorities.get(*kind);
+            let result = rule(input, &self.config, self.date_time.clone());
+            let approximate = result.as_ref().map_or(false, RuleResult::is_approximate);
+            let (bounds, item) = match result {
+                Ok(RuleResult {
+                    bounds: Some(bounds),
+                    distance,
+                    context,
+                    ..
+                }) => (bounds, Ok((context, distance, approximate))),
+                Ok(_) => continue,
+                Err(err) => (
+                    MatchBounds::new(err.get_start_idx(), err.get_end_idx()),
+                    Err(err.extract_error()),
+                ),
+            };
+
+            let is_nearer =
+                nearest
+                    .as_ref()
+                    .map_or(true, |(nearest_bounds, nearest_priority, _)| {
+                        bounds.start_idx < nearest_bounds.start_idx
+                            || bounds.start_idx == nearest_bounds.start_idx
+                                && (bounds.end_idx > nearest_bounds.end_idx
+                                    || bounds.end_idx == nearest_bounds.end_idx
+                                        && priority > *nearest_priority)
+                    });
+            if is_nearer {
+                nearest = Some((bounds, priority, item));
+            }
+        }
+
+        let (bounds, _, item) = nearest?;
+        let prefix_start = self.end_of_last_match_idx;
+        let start_idx = prefix_start + bounds.start_idx;
+        let end_idx = prefix_start + bounds.end_idx;
+        let skipped = Skipped {
+            start: prefix_start,
+            last_word: last_skipped_word(&self.input[prefix_start..start_idx])
+                .map(|(word_start, word)| (prefix_start + word_start, word)),
+        };
+
+        let item = item.map(|found| {
+            make_match_result(
+                &self.input,
+                &self.modifiers,
+                skipped,
+                MatchBounds::new(start_idx, end_idx),
+                found,
+            )
+        });
+        // the search continues after an adjustment of the match, if any
+        self.end_of_last_match_idx = match &item {
+            Ok(match_result) => match_result.get_end_idx(),
+            Err(_) => end_idx,
+        };
+
+        Some(item)
+    }
+}
+
+impl<Tz: TimeZone> Iterator for MatchIter<Tz> {
+    type Item = Result<MatchResult, DateTimeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut item = match self.pending.take() {
+            Some(item) => item,
+            None => self.next_match()?,
+        };
+
+        // a date and a time right after it make up a single match, just like in apply_generic
+        if let Ok(date) = &item {
+            let next = self.next_match();
+            let merged = match &next {
+                Some(Ok(time)) => join_date_time(&self.input, date, time),
+                _ => None,
+            };
+            match merged {
+                Some(merged) => item = Ok(merged),
+                None => self.pending = next,
+            }
+        }
+
+        let (input, counter) = (&self.input, &mut self.counter);
+        Some(item.map(|mut match_result| {
+            match_result.set_bounds(match_result.get_bounds().to_char_bounds(input, counter));
+            match_result
+        }))
+    }
+}
+
 /// Returns start and end indices of a match, accepts following arguments:
 ///
 /// prefix - vector of characters before match start
@@ -471,13 +1177,360 @@ pub(crate) fn apply_generic<'a, Tz: TimeZone + 'a>(
 ///  |----prefix----|          |--tail--|
 ///  |---------------input--------------|
 ///
-/// start_idx = prefix.len() or 0 if there is no prefix
+/// start_idx = prefix.len() or 0 if there is no prefix, ignorable symbols like spaces or quotes
+/// right after the prefix are skipped
 /// end_idx = input.len() - tail.len()
+///
+/// Both indices are byte offsets, so that they can be used to slice the input, apply_generic
+/// converts them to character offsets for the final results.
 #[inline]
 pub(crate) fn match_bounds(
     prefix_len: usize,
     input: &str,
     tail: CompleteStr,
 ) -> crate::rules::rules::MatchBounds {
-    crate::rules::rules::MatchBounds::new(prefix_len, input.len() - tail.len())
+    let skipped =
+        input[prefix_len..].len() - input[prefix_len..].trim_start_matches(is_ignorable).len();
+    crate::rules::rules::MatchBounds::new(prefix_len + skipped, input.len() - tail.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        all_fits, apply_generic, best_fit, keep_longest, recognize_word, remove_overlapped,
+        tokenize_word, Dist, Interpretation, MatchIter, Modifiers,
+    };
+    use crate::rules::config::{ParseConfig, RuleKind, RulePriorities};
+    use crate::rules::errors::{invalid_time_error, SemanticError};
+    use crate::rules::rules::{Context, FnRule, MatchBounds, MatchResult, RuleResult, TokenDesc};
+    use crate::rules::tokens::{self, PToken, Priority, Token, When};
+    use chrono::prelude::*;
+    use nom::{call, named_args, types::CompleteStr};
+
+    define!(last: (Token::When(When::Last), Priority(0)), "last", Dist(1));
+    define!(past: (Token::When(When::Past), Priority(1)), "past", Dist(1));
+    define!(last_low: (Token::When(When::Last), Priority(1)), "last", Dist(1));
+    define!(lost: (Token::When(When::Last), Priority(0)), "lost", Dist(1));
+
+    #[test]
+    fn test_best_fit_priority() {
+        // "hast" is one edit away from both, "last" has higher priority
+        let (_, desc) = best_fit(CompleteStr("hast"), Dist(5), &[&past, &last]).unwrap();
+        assert_eq!(
+            desc.token,
+            PToken::PToken(Token::When(When::Last), Priority(0))
+        );
+        assert_eq!(desc.dist, Dist(1));
+
+        // closer match wins regardless of priority
+        let (_, desc) = best_fit(CompleteStr("past"), Dist(5), &[&last, &past]).unwrap();
+        assert_eq!(
+            desc.token,
+            PToken::PToken(Token::When(When::Past), Priority(1))
+        );
+
+        // same distance and priority
+        let (_, desc) = best_fit(CompleteStr("hast"), Dist(5), &[&past, &last_low]).unwrap();
+        assert_eq!(
+            desc.token,
+            PToken::Ambiguous(vec![Token::When(When::Past), Token::When(When::Last)])
+        );
+    }
+
+    #[test]
+    fn test_best_fit_same_token() {
+        // "lest" is one edit away from both patterns, they stand for the same token
+        let (_, desc) = best_fit(CompleteStr("lest"), Dist(5), &[&last, &lost]).unwrap();
+        assert_eq!(
+            desc.token,
+            PToken::PToken(Token::When(When::Last), Priority(0))
+        );
+        assert_eq!(desc.dist, Dist(1));
+    }
+
+    #[test]
+    fn test_all_fits() {
+        // "hast" is one edit away from both, priorities don't matter
+        let (_, fits) = all_fits(CompleteStr("hast"), Dist(5), &[&past, &last]).unwrap();
+        match fits {
+            Interpretation::Multiple(fits) => {
+                let tokens: Vec<_> = fits.into_iter().map(|fit| fit.token).collect();
+                assert_eq!(
+                    tokens,
+                    vec![
+                        PToken::PToken(Token::When(When::Past), Priority(1)),
+                        PToken::PToken(Token::When(When::Last), Priority(0)),
+                    ]
+                );
+            }
+            _ => panic!("two candidates expected"),
+        }
+
+        // the closest one only
+        let (_, fits) = all_fits(CompleteStr("past"), Dist(5), &[&last, &past]).unwrap();
+        match fits {
+            Interpretation::Single(fit) => assert_eq!(
+                fit.token,
+                PToken::PToken(Token::When(When::Past), Priority(1))
+            ),
+            _ => panic!("one candidate expected"),
+        }
+
+        assert!(all_fits(CompleteStr("monday"), Dist(1), &[&last, &past]).is_err());
+    }
+
+    #[test]
+    fn test_short_patterns() {
+        let am = || PToken::PToken(Token::AmPm(tokens::AmPm::Am), Priority(0));
+        let next = || PToken::PToken(Token::When(When::Next), Priority(0));
+
+        assert!(recognize_word(CompleteStr("an apple"), CompleteStr("am"), Dist(1), am()).is_err());
+        let (tail, desc) =
+            recognize_word(CompleteStr("am apple"), CompleteStr("am"), Dist(1), am()).unwrap();
+        assert_eq!(tail, CompleteStr(" apple"));
+        assert_eq!(desc.dist, Dist(0));
+
+        // longer patterns still tolerate typos
+        assert!(recognize_word(CompleteStr("nxet"), CompleteStr("next"), Dist(1), next()).is_ok());
+    }
+
+    #[test]
+    fn test_tokenize_word() {
+        let tokenize = |input| tokenize_word(CompleteStr(input)).unwrap();
+
+        assert_eq!(
+            tokenize("\u{00A0}monday"),
+            (CompleteStr(""), CompleteStr("monday"))
+        );
+        assert_eq!(
+            tokenize("\u{2014} \u{201C}next\u{201D}"),
+            (CompleteStr("\u{201D}"), CompleteStr("next"))
+        );
+        assert_eq!(
+            tokenize("aujourd\u{2019}hui"),
+            (CompleteStr(""), CompleteStr("aujourd\u{2019}hui"))
+        );
+        assert_eq!(
+            tokenize(" 'friday'"),
+            (CompleteStr("'"), CompleteStr("friday"))
+        );
+        assert_eq!(
+            tokenize("monday's meeting"),
+            (CompleteStr(" meeting"), CompleteStr("monday's"))
+        );
+
+        let monday = || PToken::PToken(Token::Weekday(tokens::Weekday::Monday), Priority(0));
+        let input = CompleteStr("monday's meeting");
+        let (tail, _) = recognize_word(input, CompleteStr("monday"), Dist(0), monday()).unwrap();
+        assert_eq!(tail, CompleteStr(" meeting"));
+    }
+
+    #[test]
+    fn test_keep_longest() {
+        let short = Context {
+            minute: Some(1),
+            ..Default::default()
+        };
+        let long = Context {
+            minute: Some(2),
+            ..Default::default()
+        };
+        let mut err = invalid_time_error("", "hour", 25);
+        err.set_bounds(MatchBounds::new(12, 20));
+
+        let res = keep_longest(vec![
+            Ok(MatchResult::new(short.clone(), 0, 4)),
+            Ok(MatchResult::new(long, 0, 11)),
+            Ok(MatchResult::new(short.clone(), 0, 11)),
+            Ok(MatchResult::new(short, 12, 15)),
+            Err(err),
+        ]);
+
+        assert_eq!(res.len(), 2);
+        let first = res[0].as_ref().unwrap();
+        assert_eq!(first.get_end_idx(), 11);
+        assert_eq!(first.get_timeshift().minute, Some(2));
+        assert_eq!(res[1].as_ref().unwrap_err().get_end_idx(), 20);
+    }
+
+    #[test]
+    fn test_keep_longest_contained() {
+        let outer = Context {
+            hour: Some(1),
+            ..Default::default()
+        };
+        let inner = Context {
+            hour: Some(2),
+            ..Default::default()
+        };
+
+        // e.g. "next week" and "week" inside of "end of next week"
+        let res = keep_longest(vec![
+            Ok(MatchResult::new(outer.clone(), 0, 16)),
+            Ok(MatchResult::new(inner.clone(), 7, 16)),
+            Ok(MatchResult::new(inner.clone(), 12, 16)),
+            Ok(MatchResult::new(inner.clone(), 17, 20)),
+            Ok(MatchResult::new(inner, 18, 19)),
+        ]);
+
+        let bounds: Vec<_> = res
+            .iter()
+            .map(|item| {
+                let item = item.as_ref().unwrap();
+                (item.get_start_idx(), item.get_end_idx())
+            })
+            .collect();
+        assert_eq!(bounds, vec![(0, 16), (17, 20)]);
+        assert_eq!(res[0].as_ref().unwrap().get_timeshift().hour, Some(1));
+    }
+
+    #[test]
+    fn test_nested_match_is_no_intersection() {
+        let outer = Context {
+            hour: Some(1),
+            ..Default::default()
+        };
+        let inner = Context {
+            hour: Some(2),
+            ..Default::default()
+        };
+
+        let matches = keep_longest(vec![
+            Ok(MatchResult::new(outer.clone(), 0, 16)),
+            Ok(MatchResult::new(inner.clone(), 7, 16)),
+        ]);
+        let res = remove_overlapped("end of next week", &matches);
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].as_ref().unwrap().get_timeshift().hour, Some(1));
+
+        // "end of next" and "next week" overlap, neither of them covers the other one
+        let matches = keep_longest(vec![
+            Ok(MatchResult::new(outer, 0, 11)),
+            Ok(MatchResult::new(inner, 7, 16)),
+        ]);
+        let res = remove_overlapped("end of next week", &matches);
+        assert_eq!(res.len(), 1);
+        assert!(res[0].is_err());
+    }
+
+    // both rules match the same word, their matches differ in the hour only
+    fn word_at<'r>(input: &'r str, hour: i32) -> Result<RuleResult<'r>, SemanticError<'r>> {
+        let found = input.find("friday").map(|start| {
+            let ctx = Context {
+                hour: Some(hour),
+                ..Default::default()
+            };
+            MatchResult::new(ctx, start, start + "friday".len())
+        });
+        Ok(RuleResult::from_custom(input, found))
+    }
+
+    fn first_rule<'r, Tz: TimeZone>(
+        input: &'r str,
+        _config: &ParseConfig,
+        _date_time: DateTime<Tz>,
+    ) -> Result<RuleResult<'r>, SemanticError<'r>> {
+        word_at(input, 1)
+    }
+
+    fn second_rule<'r, Tz: TimeZone>(
+        input: &'r str,
+        _config: &ParseConfig,
+        _date_time: DateTime<Tz>,
+    ) -> Result<RuleResult<'r>, SemanticError<'r>> {
+        word_at(input, 2)
+    }
+
+    #[test]
+    fn test_rule_priorities() {
+        let rules: Vec<(RuleKind, FnRule<Utc>)> = vec![
+            (RuleKind::Time, first_rule::<Utc>),
+            (RuleKind::Weekdays, second_rule::<Utc>),
+        ];
+        let base = Utc.ymd(2019, 1, 1).and_hms(0, 0, 0);
+        let hours = |config: &ParseConfig| {
+            let res = apply_generic(base, "on friday", &rules, &Modifiers::default(), config);
+            let iter = MatchIter::new(
+                base,
+                "on friday".to_string(),
+                &rules,
+                &Modifiers::default(),
+                config,
+            );
+            let iter_res: Vec<_> = iter.collect();
+            assert_eq!(res, iter_res);
+            assert_eq!(res.len(), 1);
+            res[0].as_ref().unwrap().get_timeshift().hour
+        };
+
+        // the first rule wins a tie by default
+        assert_eq!(hours(&ParseConfig::default()), Some(1));
+
+        let config = ParseConfig {
+            rule_priorities: RulePriorities::default().with(RuleKind::Weekdays, 1),
+            ..Default::default()
+        };
+        assert_eq!(hours(&config), Some(2));
+
+        let config = ParseConfig {
+            rule_priorities: RulePriorities::default().with(RuleKind::Time, -1),
+            ..Default::default()
+        };
+        assert_eq!(hours(&config), Some(2));
+    }
+
+    // a rule which pattern has fewer tokens than its interpreter expects
+    mod short_pattern {
+        use crate::rules::combinators::{stub, tokenize_count_symbols, Dist};
+        use crate::rules::config::ParseConfig;
+        use crate::rules::errors::SemanticError;
+        use crate::rules::rules::{Context, RuleResult, TokenDesc};
+        use crate::rules::tokens::{Priority, Token, When};
+        use chrono::prelude::*;
+        use nom::{alt, apply, call, many_till, named_args, tuple, types::CompleteStr};
+
+        define!(last: (Token::When(When::Last), Priority(0)), "last", Dist(1));
+
+        named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
+                                     ( TokenDesc, TokenDesc ) )>,
+            many_till!(tokenize_count_symbols,
+                alt!(
+                    tuple!(apply!(last, dist_limit), stub)
+                )
+            )
+        );
+
+        make_interpreter!(positions = 3);
+
+        fn make_time<'a, 'b, Tz: TimeZone>(
+            _res: &'a RuleResult,
+            _tz_aware: DateTime<Tz>,
+            _input: &'b str,
+            _config: &ParseConfig,
+        ) -> Result<Context, SemanticError<'b>> {
+            Ok(Context::default())
+        }
+    }
+
+    #[test]
+    fn test_short_token_vector() {
+        let base = Utc.ymd(2019, 1, 1).and_hms(0, 0, 0);
+        let result = short_pattern::interpret("last friday", &ParseConfig::default(), base);
+        assert_eq!(result.unwrap().bounds, None);
+
+        // other rules are applied as usual
+        let rules: Vec<(RuleKind, FnRule<Utc>)> = vec![
+            (RuleKind::Time, short_pattern::interpret::<Utc>),
+            (RuleKind::Weekdays, first_rule::<Utc>),
+        ];
+        let res = apply_generic(
+            base,
+            "last friday",
+            &rules,
+            &Modifiers::default(),
+            &ParseConfig::default(),
+        );
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].as_ref().unwrap().get_timeshift().hour, Some(1));
+    }
 }
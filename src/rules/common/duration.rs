@@ -0,0 +1,121 @@
+use super::combinators::{recognize_int, stub, tokenize_count_symbols, wrap_error, Dist};
+use super::config::ParseConfig;
+use super::consts;
+use super::errors::{SemanticError, UNKNOWN};
+use super::rules::{Context, MyResult, RuleResult, TokenDesc};
+use super::tokens::{PToken, Priority, Token};
+use chrono::prelude::*;
+use nom::{alt, call, many_till, named_args, tuple, types::CompleteStr};
+
+/// Recognizes a number glued to a unit letter, e.g. "30m", the token holds the number of seconds.
+/// Letters are compared exactly, a typo tolerance would confuse "m" with "h".
+fn duration_part(input: CompleteStr, priority: isize) -> MyResult {
+    if let Ok((tail, n)) = recognize_int(input) {
+        let consumed = &input[..input.len() - tail.len()];
+        let mut chars = tail.chars();
+        let unit = match chars.next().map(|c| c.to_ascii_lowercase()) {
+            Some('w') => consts::WEEK,
+            Some('d') => consts::DAY,
+            Some('h') => consts::HOUR,
+            Some('m') => consts::MINUTE,
+            Some('s') => consts::SECOND,
+            _ => return wrap_error(input, UNKNOWN),
+        };
+        // the letter must not start a word, e.g. "5 mins" or "3days"
+        let ends_word = chars.next().map_or(true, |c| !c.is_alphabetic());
+        if ends_word && !consumed.contains('-') {
+            if let Some(seconds) = n.checked_mul(unit) {
+                return Ok((
+                    CompleteStr(&tail[1..]),
+                    TokenDesc::new(
+                        PToken::PToken(Token::Number(seconds), Priority(priority)),
+                        Dist(0),
+                    ),
+                ));
+            }
+        }
+    }
+    wrap_error(input, UNKNOWN)
+}
+
+named_args!(parse<'a>(_dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
+                             ( TokenDesc, TokenDesc, TokenDesc, TokenDesc, TokenDesc ) )>,
+
+    many_till!(tokenize_count_symbols,
+        alt!(
+            // e.g.: 90m, 1h30m, 2d 4h, 1w 2d 3h 4m 5s
+            tuple!(call!(duration_part, 0), alt!(call!(duration_part, 1) | stub),
+                   alt!(call!(duration_part, 2) | stub), alt!(call!(duration_part, 3) | stub),
+                   alt!(call!(duration_part, 4) | stub))
+        )
+    )
+);
+
+make_interpreter!(positions = 5);
+
+fn make_time<'a, 'b, Tz: TimeZone>(
+    res: &'a RuleResult,
+    _tz_aware: DateTime<Tz>,
+    _input: &'b str,
+    _config: &ParseConfig,
+) -> Result<Context, SemanticError<'b>> {
+    let mut ctx = Context::default();
+
+    let mut seconds: i64 = 0;
+    for priority in 0..5 {
+        if let Some(Token::Number(n)) = res.token_by_priority(Priority(priority)) {
+            seconds += i64::from(n);
+        }
+    }
+
+    ctx.set_duration(seconds);
+
+    Ok(ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::interpret;
+    use crate::rules::config::ParseConfig;
+    use crate::rules::rules::MatchBounds;
+    use chrono::prelude::*;
+
+    fn fixed_time() -> DateTime<Local> {
+        Local.ymd(2019, 1, 1).and_hms(0, 0, 0)
+    }
+
+    #[test]
+    fn test_duration() {
+        let result = interpret("timer 1h30m", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(6, 11)));
+        assert_eq!(result.get_duration_sec(), 90 * 60);
+
+        let result = interpret("90m", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 90 * 60);
+
+        let result = interpret("2d 4h", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 5)));
+        assert_eq!(result.get_duration_sec(), 2 * 24 * 3600 + 4 * 3600);
+
+        let result = interpret("1w 2d 3h 4m 5s", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 14)));
+        assert_eq!(
+            result.get_duration_sec(),
+            9 * 24 * 3600 + 3 * 3600 + 4 * 60 + 5
+        );
+
+        let result = interpret("45S", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 45);
+
+        // a unit needs a number and a number needs a unit
+        let result = interpret("h", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, None);
+
+        let result = interpret("30 m", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, None);
+
+        // words are not units
+        let result = interpret("5 mins or 3days", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, None);
+    }
+}
@@ -0,0 +1,129 @@
+use super::combinators::{stub, tokenize_count_symbols, Dist};
+use super::common::{days_in_month, year};
+use super::config::ParseConfig;
+use super::errors::{invalid_date_error, SemanticError};
+use super::rules::{Context, RuleResult, TokenDesc};
+use super::tokens::{Priority, Token};
+use chrono::prelude::*;
+use nom::{alt, apply, many_till, named_args, tuple, types::CompleteStr};
+
+define_num!(month: (Token::Number, Priority(1)));
+define_num!(day: (Token::Number, Priority(2)));
+
+define_char!(dash: Priority(10), '-');
+
+named_args!(parse<'a>(_dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
+                             ( TokenDesc, TokenDesc, TokenDesc, TokenDesc, TokenDesc ) )>,
+
+    many_till!(tokenize_count_symbols,
+        alt!(
+            // e.g.: 2019-01-05
+            tuple!(apply!(year, Priority(0)), dash, month, dash, day) |
+            // e.g.: 2019-01
+            tuple!(apply!(year, Priority(0)), dash, month, stub, stub)
+        )
+    )
+);
+
+make_interpreter!(positions = 5);
+
+fn make_time<'a, 'b, Tz: TimeZone>(
+    res: &'a RuleResult,
+    _tz_aware: DateTime<Tz>,
+    input: &'b str,
+    _config: &ParseConfig,
+) -> Result<Context, SemanticError<'b>> {
+    let mut ctx = Context::default();
+
+    let mut year = 0;
+    let mut month = 0;
+
+    let token = res.token_by_priority(Priority(0));
+    if let Some(Token::Year(n)) = token {
+        year = n as i32;
+    }
+
+    let token = res.token_by_priority(Priority(1));
+    if let Some(Token::Number(n)) = token {
+        month = n;
+    }
+
+    if month < 1 || month > 12 {
+        return Err(invalid_date_error(input, "month", month));
+    }
+
+    let token = res.token_by_priority(Priority(2));
+    if let Some(Token::Number(day)) = token {
+        if day < 1 || day > days_in_month(year, month as u32) as i32 {
+            return Err(invalid_date_error(input, "day", day));
+        }
+        ctx.day = Some(day);
+    } else {
+        // a month without a day, e.g. "2019-01", means its first day
+        ctx.day = Some(1);
+    }
+
+    ctx.year = Some(year);
+    ctx.month = Some(month);
+
+    Ok(ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::interpret;
+    use crate::rules::config::ParseConfig;
+    use crate::rules::errors::invalid_date_error;
+    use crate::rules::rules::MatchBounds;
+    use chrono::prelude::*;
+
+    fn fixed_time() -> DateTime<Local> {
+        // 2019 1st January, Tuesday
+        Local.ymd(2019, 1, 1).and_hms(0, 0, 1)
+    }
+
+    #[test]
+    fn test_iso_date() {
+        let result = interpret(
+            "released on 2019-01-05",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(12, 22)));
+        assert_eq!(result.get_year(), 2019);
+        assert_eq!(result.get_month(), 1);
+        assert_eq!(result.get_day(), 5);
+
+        let result = interpret("2020-02-29", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_year(), 2020);
+        assert_eq!(result.get_month(), 2);
+        assert_eq!(result.get_day(), 29);
+
+        let result = interpret("2019-03", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_month(), 3);
+        assert_eq!(result.get_day(), 1);
+
+        let result = interpret("2019-02-30", &ParseConfig::default(), fixed_time());
+        assert_eq!(
+            result.unwrap_err().extract_error(),
+            invalid_date_error("2019-02-30", "day", 30).extract_error()
+        );
+
+        let result = interpret("2019-02-29", &ParseConfig::default(), fixed_time());
+        assert_eq!(
+            result.unwrap_err().extract_error(),
+            invalid_date_error("2019-02-29", "day", 29).extract_error()
+        );
+
+        let result = interpret("2019-13-01", &ParseConfig::default(), fixed_time());
+        assert_eq!(
+            result.unwrap_err().extract_error(),
+            invalid_date_error("2019-13-01", "month", 13).extract_error()
+        );
+
+        // times are not dates
+        let result = interpret("at 18-00", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, None);
+    }
+}
@@ -1,13 +1,105 @@
 use crate::rules::combinators;
 use crate::rules::common;
+use crate::rules::config;
+use crate::rules::consts;
 use crate::rules::errors;
 use crate::rules::rules;
 use crate::rules::tokens;
 
+use chrono::{offset::TimeZone, DateTime};
+use combinators::{recognize_uint, wrap_error, Dist};
+use config::{ParseConfig, RuleKind};
+use errors::{DateTimeError, UNKNOWN};
+use nom::types::CompleteStr;
+use rules::{FnRule, MatchResult, MyResult, TokenDesc};
+use tokens::{PToken, Priority, Token};
+
+pub mod duration;
+pub mod iso_date;
 pub mod slash_dmy;
 
-pub(crate) const DAYS_IN_MONTH: &[i32; 12] = &[31, 28, 29, 30, 31, 28, 31, 30, 30, 31, 29, 31];
+const DAYS_IN_MONTH: &[u32; 12] = &[31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
 
 pub(crate) fn is_leap_year(year: i32) -> bool {
     year % 4 == 0 && year % 100 != 0 || year % 400 == 0
 }
+
+/// Number of days in the given month of the year, months are counted from 1.
+pub(crate) fn days_in_month(year: i32, month: u32) -> u32 {
+    // 29 days in february for leap years
+    if month == 2 && is_leap_year(year) {
+        29
+    } else {
+        DAYS_IN_MONTH[month as usize - 1]
+    }
+}
+
+/// Recognizes a four-digit year with the given priority, e.g. "2020" in "Q3 2020", it takes no
+/// sign, so "2019-01" is a year and a month, and ISO dates are told apart from times like "18-00".
+pub(crate) fn year(input: CompleteStr, priority: Priority) -> MyResult {
+    if let Ok((tail, n)) = recognize_uint(input) {
+        if n >= 1000 && n <= 9999 {
+            return Ok((
+                tail,
+                TokenDesc::new(PToken::PToken(Token::Year(n as usize), priority), Dist(0)),
+            ));
+        }
+    }
+    wrap_error(input, UNKNOWN)
+}
+
+/// Parses the input with the weekday and casual date/time rules of a language other than English.
+/// Such rules recognize words of their own language, but produce the same tokens as the English
+/// ones, so they are interpreted by en::weekdays::make_time and en::casual_date_time::make_time.
+pub(crate) fn parse_translated<'a, Tz: TimeZone + 'a>(
+    tz_aware: DateTime<Tz>,
+    input: &'a str,
+    config: &ParseConfig,
+    weekdays: FnRule<Tz>,
+    casual_date_time: FnRule<Tz>,
+    modifiers: &combinators::Modifiers,
+) -> Vec<Result<MatchResult, DateTimeError>> {
+    let input_prepared = config.prepare_input(input);
+    let matches = combinators::apply_generic(
+        tz_aware,
+        &input_prepared.text,
+        &[
+            (RuleKind::Weekdays, weekdays),
+            (RuleKind::CasualDateTime, casual_date_time),
+        ],
+        modifiers,
+        config,
+    );
+    input_prepared.restore_all(matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{days_in_month, year};
+    use crate::rules::tokens::{PToken, Priority, Token};
+    use nom::types::CompleteStr;
+
+    #[test]
+    fn test_days_in_month() {
+        assert_eq!(days_in_month(2019, 1), 31);
+        assert_eq!(days_in_month(2019, 4), 30);
+        assert_eq!(days_in_month(2019, 12), 31);
+
+        assert_eq!(days_in_month(2019, 2), 28);
+        assert_eq!(days_in_month(2020, 2), 29);
+        // century years are leap only if divisible by 400
+        assert_eq!(days_in_month(1900, 2), 28);
+        assert_eq!(days_in_month(2000, 2), 29);
+    }
+
+    #[test]
+    fn test_year() {
+        let (tail, desc) = year(CompleteStr(" 2019-01"), Priority(3)).unwrap();
+        assert_eq!(tail, CompleteStr("-01"));
+        assert_eq!(desc.token, PToken::PToken(Token::Year(2019), Priority(3)));
+
+        assert!(year(CompleteStr("-2019"), Priority(3)).is_err());
+        assert!(year(CompleteStr("999"), Priority(3)).is_err());
+        assert!(year(CompleteStr("20190"), Priority(3)).is_err());
+    }
+}
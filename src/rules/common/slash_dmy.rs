@@ -1,18 +1,20 @@
-use super::combinators::{stub, tokenize_count_symbols};
-use super::common::{is_leap_year, DAYS_IN_MONTH};
-use super::errors::{invalid_time_error, SemanticError};
+use super::combinators::{stub, tokenize_count_symbols, Dist};
+use super::common::days_in_month;
+use super::config::{DateOrder, ParseConfig};
+use super::errors::{invalid_date_error, SemanticError};
 use super::rules::{Context, RuleResult, TokenDesc};
 use super::tokens::{Priority, Token};
 use chrono::prelude::*;
 use nom::{alt, many_till, named_args, tuple, types::CompleteStr};
 
+// the first two numbers are a day and a month in the order defined by ParseConfig::date_order
 define_num!(day: (Token::Number, Priority(0)));
 define_num!(month: (Token::Number, Priority(1)));
 define_num!(year: (Token::Number, Priority(2)));
 
 define_char!(slash: Priority(10), '/');
 
-named_args!(parse<'a>(_exact_match: bool)<CompleteStr<'a>, (Vec<usize>,
+named_args!(parse<'a>(_dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
                              ( TokenDesc, TokenDesc, TokenDesc, TokenDesc, TokenDesc ) )>,
 
     many_till!(tokenize_count_symbols,
@@ -29,6 +31,7 @@ fn make_time<'a, 'b, Tz: TimeZone>(
     res: &'a RuleResult,
     tz_aware: DateTime<Tz>,
     input: &'b str,
+    config: &ParseConfig,
 ) -> Result<Context, SemanticError<'b>> {
     let mut ctx = Context::default();
 
@@ -46,6 +49,18 @@ fn make_time<'a, 'b, Tz: TimeZone>(
         month = n;
     }
 
+    // a number greater than 12 can't be a month, so the order is obvious regardless of config
+    let month_first = if day > 12 {
+        false
+    } else if month > 12 {
+        true
+    } else {
+        config.date_order == DateOrder::MonthDay
+    };
+    if month_first {
+        std::mem::swap(&mut day, &mut month);
+    }
+
     let token = res.token_by_priority(Priority(2));
     let year = if let Some(Token::Number(n)) = token {
         n
@@ -55,23 +70,15 @@ fn make_time<'a, 'b, Tz: TimeZone>(
 
     // only A.C. dates are supported yet
     if year <= 0 {
-        return Err(invalid_time_error(input, "year", year));
+        return Err(invalid_date_error(input, "year", year));
     }
 
     if month < 1 || month > 12 {
-        return Err(invalid_time_error(input, "month", month));
+        return Err(invalid_date_error(input, "month", month));
     }
 
-    // DAYS_IN_MONTH slice counts from 0, however humans count months from 1
-    // 29 days in february for leap years
-    let days_in_month = if month == 2 && is_leap_year(year) {
-        29
-    } else {
-        DAYS_IN_MONTH[month as usize - 1]
-    };
-
-    if day < 1 || day > days_in_month {
-        return Err(invalid_time_error(input, "day", day));
+    if day < 1 || day > days_in_month(year, month as u32) as i32 {
+        return Err(invalid_date_error(input, "day", day));
     }
 
     ctx.year = Some(year);
@@ -84,7 +91,8 @@ fn make_time<'a, 'b, Tz: TimeZone>(
 #[cfg(test)]
 mod tests {
     use super::interpret;
-    use crate::rules::errors::invalid_time_error;
+    use crate::rules::config::{DateOrder, ParseConfig};
+    use crate::rules::errors::invalid_date_error;
     use chrono::prelude::*;
 
     fn fixed_time() -> DateTime<Local> {
@@ -94,32 +102,64 @@ mod tests {
 
     #[test]
     fn test_slash_dmy() {
-        let result = interpret("20/12/2010", false, fixed_time()).unwrap();
+        let result = interpret("20/12/2010", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.get_day(), 20);
         assert_eq!(result.get_month(), 12);
         assert_eq!(result.get_year(), 2010);
 
-        let result = interpret("3/10", false, fixed_time()).unwrap();
+        let result = interpret("3/10", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.get_day(), 3);
         assert_eq!(result.get_month(), 10);
         assert_eq!(result.get_year(), 2019);
 
-        let result = interpret("30/2/2018", false, fixed_time());
+        let result = interpret("30/2/2018", &ParseConfig::default(), fixed_time());
         assert_eq!(
             result.unwrap_err().extract_error(),
-            invalid_time_error("30/2/2018", "day", 30).extract_error()
+            invalid_date_error("30/2/2018", "day", 30).extract_error()
         );
 
-        let result = interpret("25/13/2018", false, fixed_time());
+        let result = interpret("25/13/2018", &ParseConfig::default(), fixed_time());
         assert_eq!(
             result.unwrap_err().extract_error(),
-            invalid_time_error("25/13/2018", "month", 13).extract_error()
+            invalid_date_error("25/13/2018", "month", 13).extract_error()
         );
 
-        let result = interpret("25/10/-2", false, fixed_time());
+        let result = interpret("25/10/-2", &ParseConfig::default(), fixed_time());
+        assert_eq!(
+            result.unwrap_err().extract_error(),
+            invalid_date_error("25/10/-2", "year", -2).extract_error()
+        );
+    }
+
+    #[test]
+    fn test_date_order() {
+        let month_day = ParseConfig {
+            date_order: DateOrder::MonthDay,
+            ..Default::default()
+        };
+
+        let result = interpret("01/05/2019", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_day(), 1);
+        assert_eq!(result.get_month(), 5);
+
+        let result = interpret("01/05/2019", &month_day, fixed_time()).unwrap();
+        assert_eq!(result.get_day(), 5);
+        assert_eq!(result.get_month(), 1);
+        assert_eq!(result.get_year(), 2019);
+
+        // day is obvious if it doesn't fit a month
+        let result = interpret("13/05", &month_day, fixed_time()).unwrap();
+        assert_eq!(result.get_day(), 13);
+        assert_eq!(result.get_month(), 5);
+
+        let result = interpret("05/13", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_day(), 13);
+        assert_eq!(result.get_month(), 5);
+
+        let result = interpret("13/13", &month_day, fixed_time());
         assert_eq!(
             result.unwrap_err().extract_error(),
-            invalid_time_error("25/10/-2", "year", -2).extract_error()
+            invalid_date_error("13/13", "month", 13).extract_error()
         );
     }
 }
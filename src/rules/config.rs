@@ -0,0 +1,289 @@
+use super::combinators::Dist;
+use super::errors::DateTimeError;
+use super::rules::{MatchBounds, MatchResult};
+use chrono::Weekday;
+use std::borrow::Cow;
+
+/// Order of day and month in numeric dates like "01/05/2019".
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DateOrder {
+    /// Day goes first, e.g. "01/05/2019" is the 1st of May.
+    DayMonth,
+    /// Month goes first as in the US, e.g. "01/05/2019" is January 5th.
+    MonthDay,
+}
+
+impl Default for DateOrder {
+    fn default() -> Self {
+        DateOrder::DayMonth
+    }
+}
+
+/// Convention for the first days of seasons.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SeasonConvention {
+    /// Seasons start on the first days of months, e.g. summer starts on June 1st.
+    Meteorological,
+    /// Seasons start on solstices and equinoxes, e.g. summer starts on June 21st. The dates
+    /// are fixed and may be a day off for some years.
+    Astronomical,
+}
+
+impl Default for SeasonConvention {
+    fn default() -> Self {
+        SeasonConvention::Meteorological
+    }
+}
+
+/// Hemisphere the seasons are given for, the southern one has summer instead of winter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Hemisphere {
+    Northern,
+    Southern,
+}
+
+impl Default for Hemisphere {
+    fn default() -> Self {
+        Hemisphere::Northern
+    }
+}
+
+/// Rules of the parser, e.g. to disable clock times when only weekdays are needed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RuleKind {
+    Weekdays,
+    Time,
+    PastTime,
+    ExactMonthDate,
+    Deadline,
+    CasualDateTime,
+    SlashDmy,
+    IsoDate,
+    Recurrence,
+    PeriodPart,
+    WeekNumber,
+    Quarter,
+    WeekdayCount,
+    Duration,
+    Business,
+    Weekend,
+    WeekdayList,
+    Holidays,
+    Seasons,
+    WeekdayOfMonth,
+}
+
+/// Set of enabled rules, all rules are enabled by default.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RuleSet(u32);
+
+impl RuleSet {
+    pub fn all() -> Self {
+        RuleSet(std::u32::MAX)
+    }
+
+    pub fn only(rules: &[RuleKind]) -> Self {
+        RuleSet(rules.iter().fold(0, |mask, rule| mask | Self::bit(*rule)))
+    }
+
+    pub fn without(self, rule: RuleKind) -> Self {
+        RuleSet(self.0 & !Self::bit(rule))
+    }
+
+    pub fn contains(&self, rule: RuleKind) -> bool {
+        self.0 & Self::bit(rule) != 0
+    }
+
+    fn bit(rule: RuleKind) -> u32 {
+        1 << rule as u32
+    }
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        RuleSet::all()
+    }
+}
+
+/// Priorities of the rules which decide between matches of the same length found at the same
+/// position, e.g. to prefer weekdays over clock times. Matches of rules with higher priorities
+/// win, all rules have zero priority by default and the first rule of a language wins then.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RulePriorities([isize; 32]);
+
+impl RulePriorities {
+    pub fn with(mut self, rule: RuleKind, priority: isize) -> Self {
+        self.0[rule as usize] = priority;
+        self
+    }
+
+    pub fn get(&self, rule: RuleKind) -> isize {
+        self.0[rule as usize]
+    }
+}
+
+impl Default for RulePriorities {
+    fn default() -> Self {
+        RulePriorities([0; 32])
+    }
+}
+
+/// Settings which affect how the rules recognize words in an input string.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParseConfig {
+    /// Disables typos recognition, words must match their patterns exactly, see en::parse_exact.
+    pub exact_match: bool,
+    /// Upper bound for Damerau-Levenshtein distance of any word, overrides larger distances
+    /// defined by the rules. None keeps distances defined by the rules as is.
+    pub max_edit_distance: Option<usize>,
+    /// Order of day and month in slash separated dates. It decides for ambiguous dates only,
+    /// components greater than 12 are always read as a day, e.g. "13/05" is the 13th of May.
+    pub date_order: DateOrder,
+    /// Rules to apply, rules of a language which are not in the set are skipped.
+    pub rules: RuleSet,
+    /// Keeps the input as is instead of lowercasing it before parsing. Match bounds and matched
+    /// texts are taken from the original input anyway, even if lowercasing changes its length
+    /// (e.g. "İ").
+    pub preserve_case: bool,
+    /// Returns at most that many leftmost matches (errors included), every rule stops after
+    /// that many matches of its own, so it bounds the work done for untrusted input. Fewer
+    /// matches may be returned if matches of a rule are dropped as parts of longer ones. None
+    /// means no limit.
+    pub max_matches: Option<usize>,
+    /// First day of a week for phrases like "end of this week", monday as in ISO by default.
+    pub week_start: Weekday,
+    /// Makes phrases like "next weekend" a range from saturday to sunday instead of a single
+    /// saturday.
+    pub weekend_range: bool,
+    /// Opening and closing hours of a working day, e.g. "start of day" is the opening one and
+    /// "EOD" is the closing one, 9 to 17 by default.
+    pub business_hours: (u32, u32),
+    /// First days of seasons for phrases like "summer", meteorological by default.
+    pub season_convention: SeasonConvention,
+    /// Hemisphere of the seasons, northern by default.
+    pub hemisphere: Hemisphere,
+    /// Moves a time of day without a date which has passed to the next day, e.g. "midnight" is
+    /// the start of tomorrow. Otherwise such a time stays on the current day.
+    pub prefer_future: bool,
+    /// Priorities of the rules for matches of the same length at the same position, rules added
+    /// at runtime have zero priority.
+    pub rule_priorities: RulePriorities,
+    /// Reports a meridiem after an hour of the 24-hour clock, e.g. "18:00 pm", as an invalid time
+    /// instead of ignoring it.
+    pub strict_meridiem: bool,
+    /// Count which vague quantities like "a few hours" or "several weeks" stand for, 3 by
+    /// default, "a couple" is always 2.
+    pub few_count: u32,
+    /// Resolves "today", "tomorrow" and "yesterday" given without a time of day to the start of
+    /// the day. Otherwise they keep the time of day of the reference time.
+    pub zero_time_of_day: bool,
+}
+
+impl Default for ParseConfig {
+    fn default() -> Self {
+        ParseConfig {
+            exact_match: false,
+            max_edit_distance: None,
+            date_order: DateOrder::default(),
+            rules: RuleSet::default(),
+            preserve_case: false,
+            max_matches: None,
+            week_start: Weekday::Mon,
+            weekend_range: false,
+            business_hours: (9, 17),
+            season_convention: SeasonConvention::default(),
+            hemisphere: Hemisphere::default(),
+            prefer_future: true,
+            rule_priorities: RulePriorities::default(),
+            strict_meridiem: false,
+            few_count: 3,
+            zero_time_of_day: false,
+        }
+    }
+}
+
+impl ParseConfig {
+    /// Maximum distance allowed by this config, rules clamp their own distances to it.
+    pub(crate) fn dist_limit(&self) -> Dist {
+        if self.exact_match {
+            Dist(0)
+        } else {
+            Dist(self.max_edit_distance.unwrap_or(std::usize::MAX))
+        }
+    }
+
+    /// Input string the rules are applied to.
+    pub(crate) fn prepare_input<'a>(&self, input: &'a str) -> PreparedInput<'a> {
+        if self.preserve_case {
+            return PreparedInput {
+                original: input,
+                text: Cow::Borrowed(input),
+                offsets: None,
+            };
+        }
+
+        let text = input.to_lowercase();
+        let offsets = if text.chars().count() == input.chars().count() {
+            None
+        } else {
+            // a character may become several ones, e.g. "İ" is "i" and a combining dot
+            let mut offsets = Vec::new();
+            for (idx, c) in input.chars().enumerate() {
+                offsets.extend(std::iter::repeat(idx).take(c.to_lowercase().count()));
+            }
+            Some(offsets)
+        };
+        PreparedInput {
+            original: input,
+            text: Cow::Owned(text),
+            offsets,
+        }
+    }
+}
+
+/// Input string prepared according to the config, see ParseConfig::prepare_input.
+pub(crate) struct PreparedInput<'a> {
+    original: &'a str,
+    pub(crate) text: Cow<'a, str>,
+    // index of the original character for every character of the text, None if every character
+    // of the text stands for a character of the original input at the same index
+    offsets: Option<Vec<usize>>,
+}
+
+impl<'a> PreparedInput<'a> {
+    /// Moves bounds of a match from characters of the text to characters of the original input,
+    /// a match ending inside of a character which became several ones doesn't include it. The
+    /// matched text is taken from the original input too, so it keeps the case, e.g. "5pm EST".
+    pub(crate) fn restore_bounds(&self, mut match_result: MatchResult) -> MatchResult {
+        if let Some(offsets) = &self.offsets {
+            let bounds = match_result.get_bounds();
+            let start_idx = offsets[bounds.start_idx];
+            let end_idx = match offsets.get(bounds.end_idx) {
+                Some(end_idx) => *end_idx,
+                None => offsets.last().map_or(0, |last| last + 1),
+            };
+            match_result.set_bounds(MatchBounds::new(start_idx, end_idx.max(start_idx)));
+        }
+
+        let bounds = match_result.get_bounds();
+        let byte_idx = |idx| {
+            self.original
+                .char_indices()
+                .nth(idx)
+                .map_or(self.original.len(), |(byte_idx, _)| byte_idx)
+        };
+        let text = &self.original[byte_idx(bounds.start_idx)..byte_idx(bounds.end_idx)];
+        match_result.set_matched_text(text);
+        match_result
+    }
+
+    pub(crate) fn restore_all(
+        &self,
+        matches: Vec<Result<MatchResult, DateTimeError>>,
+    ) -> Vec<Result<MatchResult, DateTimeError>> {
+        matches
+            .into_iter()
+            .map(|item| item.map(|match_result| self.restore_bounds(match_result)))
+            .collect()
+    }
+}
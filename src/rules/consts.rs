@@ -3,3 +3,9 @@ pub(crate) const MINUTE: i32 = 60 * SECOND;
 pub(crate) const HOUR: i32 = 60 * MINUTE;
 pub(crate) const DAY: i32 = 24 * HOUR;
 pub(crate) const WEEK: i32 = 7 * DAY;
+
+// default hours for parts of the day, e.g. "tomorrow morning"
+pub(crate) const MORNING_HOUR: i32 = 9;
+pub(crate) const AFTERNOON_HOUR: i32 = 15;
+pub(crate) const EVENING_HOUR: i32 = 19;
+pub(crate) const NIGHT_HOUR: i32 = 23;
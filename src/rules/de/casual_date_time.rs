@@ -0,0 +1,100 @@
+use super::combinators::{stub, tokenize_count_symbols, Dist};
+use super::en::casual_date_time::make_time;
+use super::rules::{RuleResult, TokenDesc};
+use super::tokens::{Priority, TimeOfDay, Token, When};
+use chrono::prelude::*;
+
+use nom::{alt, apply, call, many_till, named_args, tuple, types::CompleteStr};
+
+define!(now: (Token::When(When::Now), Priority(0)), "jetzt", Dist(1));
+
+define!(today: (Token::When(When::Today), Priority(1)), "heute", Dist(1));
+define!(tomorrow: (Token::When(When::Tomorrow), Priority(1)), "morgen", Dist(1));
+define!(yesterday: (Token::When(When::Yesterday), Priority(1)), "gestern", Dist(1));
+
+combine!(when => today | yesterday | tomorrow);
+
+define!(night: (Token::TimeOfDay(TimeOfDay::Night), Priority(2)), "nacht", Dist(1));
+// "morgen" is both "tomorrow" and "morning", it means the latter only after a day, e.g.
+// "heute morgen"
+define!(
+    morning:
+    [(Token::TimeOfDay(TimeOfDay::Morning), Priority(2)), "morgen", Dist(0)] |
+    [(Token::TimeOfDay(TimeOfDay::Morning), Priority(2)), "früh", Dist(1)]
+);
+define!(evening: (Token::TimeOfDay(TimeOfDay::Evening), Priority(2)), "abend", Dist(1));
+define!(noon: (Token::TimeOfDay(TimeOfDay::Noon), Priority(2)), "mittag", Dist(1));
+define!(midnight: (Token::TimeOfDay(TimeOfDay::Midnight), Priority(2)), "mitternacht", Dist(2));
+define!(afternoon: (Token::TimeOfDay(TimeOfDay::Afternoon), Priority(2)), "nachmittag", Dist(2));
+
+combine!(time_of_day => night | morning | evening | noon | midnight | afternoon);
+
+named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
+                             ( TokenDesc, TokenDesc, ) )>,
+    many_till!(tokenize_count_symbols,
+        alt!(
+            // morgen abend, heute morgen, etc.
+            tuple!(apply!(when, dist_limit), apply!(time_of_day, dist_limit)) |
+            // heute, morgen, gestern
+            tuple!(apply!(when, dist_limit), stub) |
+            // jetzt
+            tuple!(apply!(now, dist_limit), stub)
+        )
+    )
+);
+
+make_interpreter!(positions = 2);
+
+#[cfg(test)]
+mod tests {
+    use super::interpret;
+    use crate::rules::config::ParseConfig;
+    use crate::rules::rules::MatchBounds;
+    use chrono::prelude::*;
+
+    fn fixed_time() -> DateTime<Local> {
+        Local.ymd(2019, 1, 1).and_hms(0, 0, 0)
+    }
+
+    #[test]
+    fn test_casual_date() {
+        let result =
+            interpret("Die Frist ist jetzt", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(14, 19)));
+        assert_eq!(result.get_duration_sec(), 0);
+
+        let result =
+            interpret("Die Frist ist heute", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(14, 19)));
+        assert_eq!(result.get_duration_sec(), 0);
+
+        let result = interpret(
+            "Die Frist ist morgen",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(14, 20)));
+        assert_eq!(result.get_duration_sec(), 86400);
+
+        let result = interpret("gestrn", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), -86400);
+    }
+
+    #[test]
+    fn test_time_of_day() {
+        let result = interpret("morgen abend", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 12)));
+        assert_eq!(result.get_duration_sec(), 86400);
+        assert_eq!(result.get_hours(), 19);
+
+        let result = interpret("heute morgen", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 0);
+        assert_eq!(result.get_hours(), 9);
+
+        let result =
+            interpret("gestern nachmittag", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), -86400);
+        assert_eq!(result.get_hours(), 15);
+    }
+}
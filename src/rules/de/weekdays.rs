@@ -0,0 +1,123 @@
+use super::combinators::{stub, tokenize_count_symbols, Dist};
+use super::en::weekdays::make_time;
+use super::rules::{RuleResult, TokenDesc};
+use super::tokens::{Priority, TimeInterval, Token, Weekday as Day, When};
+use chrono::prelude::*;
+
+use nom::{alt, apply, call, many_till, named_args, tuple, types::CompleteStr};
+
+define!(montag: (Token::Weekday(Day::Monday), Priority(0)), "montag", Dist(1));
+define!(dienstag: (Token::Weekday(Day::Tuesday), Priority(0)), "dienstag", Dist(2));
+define!(mittwoch: (Token::Weekday(Day::Wednesday), Priority(0)), "mittwoch", Dist(2));
+define!(donnerstag: (Token::Weekday(Day::Thursday), Priority(0)), "donnerstag", Dist(2));
+define!(freitag: (Token::Weekday(Day::Friday), Priority(0)), "freitag", Dist(1));
+define!(
+    samstag:
+    [(Token::Weekday(Day::Saturday), Priority(0)), "samstag", Dist(1)] |
+    [(Token::Weekday(Day::Saturday), Priority(0)), "sonnabend", Dist(2)]
+);
+define!(sonntag: (Token::Weekday(Day::Sunday), Priority(0)), "sonntag", Dist(1));
+
+combine!(day_of_week => montag | dienstag | mittwoch | donnerstag | freitag | samstag | sonntag);
+
+define!(
+    this:
+    [(Token::When(When::This), Priority(1)), "diesen", Dist(1)] |
+    [(Token::When(When::This), Priority(1)), "dieser", Dist(1)] |
+    [(Token::When(When::This), Priority(1)), "diese", Dist(1)]
+);
+define!(
+    last:
+    [(Token::When(When::Last), Priority(1)), "letzten", Dist(1)] |
+    [(Token::When(When::Last), Priority(1)), "letzte", Dist(1)] |
+    [(Token::When(When::Last), Priority(1)), "vergangenen", Dist(2)]
+);
+define!(
+    next:
+    [(Token::When(When::Next), Priority(1)), "nächsten", Dist(1)] |
+    [(Token::When(When::Next), Priority(1)), "nächste", Dist(1)] |
+    [(Token::When(When::Next), Priority(1)), "kommenden", Dist(2)]
+);
+
+combine!(when => this | last | next);
+
+define!(week_noun: (Token::TimeInterval(TimeInterval::Week), Priority(2)), "woche", Dist(1));
+
+named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
+                             ( TokenDesc, TokenDesc, TokenDesc ) )>,
+    many_till!(tokenize_count_symbols,
+        alt!(
+            // day of week, when, "week", e.g. "dienstag nächste woche"
+            tuple!(apply!(day_of_week, dist_limit), apply!(when, dist_limit),
+                   apply!(week_noun, dist_limit)) |
+            // when and then any day of week, e.g. "letzten freitag"
+            tuple!(apply!(when, dist_limit), apply!(day_of_week, dist_limit), stub) |
+            // day of week
+            tuple!(apply!(day_of_week, dist_limit), stub, stub)
+        )
+    )
+);
+
+make_interpreter!(positions = 3);
+
+#[cfg(test)]
+mod tests {
+    use super::interpret;
+    use crate::rules::config::ParseConfig;
+    use crate::rules::errors::ambiguous_time_error;
+    use crate::rules::rules::MatchBounds;
+    use chrono::prelude::*;
+
+    fn fixed_time() -> DateTime<Local> {
+        // 2019 1st January, Tuesday
+        Local.ymd(2019, 1, 1).and_hms(0, 0, 1)
+    }
+
+    #[test]
+    fn test_past_last() {
+        let result = interpret("letzten Montag", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 14)));
+        assert_eq!(result.get_duration_sec(), -86400);
+
+        let result =
+            interpret("vergangenen samstag", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), -259200);
+
+        let result = interpret("letzte freitg", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), -345600);
+    }
+
+    #[test]
+    fn test_next() {
+        let result =
+            interpret("bis nächsten montag", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(4, 20)));
+        assert_eq!(result.get_duration_sec(), 518400);
+
+        let result = interpret("kommenden sonntag", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 432000);
+
+        let result = interpret(
+            "dienstag nächste woche",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
+        assert_eq!(result.get_duration_sec(), 604800);
+    }
+
+    #[test]
+    fn test_this() {
+        let result = interpret("diesen donnerstag", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 172800);
+
+        let result = interpret("freitag", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 259200);
+
+        let result = interpret("diesen montag", &ParseConfig::default(), fixed_time());
+        assert_eq!(
+            result.unwrap_err().extract_error(),
+            ambiguous_time_error("diesen montag").extract_error()
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use super::combinators::{recognize_int, tokenize_word};
+use super::consts;
+use nom::types::CompleteStr;
+
+// units of a relative adjustment, plural forms are recognized too, e.g. "minutes", "hrs"
+const ADJUSTMENT_UNITS: [(&str, i32); 8] = [
+    ("second", consts::SECOND),
+    ("sec", consts::SECOND),
+    ("minute", consts::MINUTE),
+    ("min", consts::MINUTE),
+    ("hour", consts::HOUR),
+    ("hr", consts::HOUR),
+    ("day", consts::DAY),
+    ("week", consts::WEEK),
+];
+
+/// Size of a unit of an adjustment in seconds, e.g. "minutes" or "hr", None for other words.
+pub(crate) fn unit_size(word: &str) -> Option<i32> {
+    let unit = word.to_lowercase();
+    let singular = unit.trim_end_matches('s');
+    ADJUSTMENT_UNITS
+        .iter()
+        .find(|(name, _)| *name == singular)
+        .map(|&(_, size)| size)
+}
+
+/// Looks for a relative adjustment at the start of the text right after a match, e.g. "plus 2
+/// days", returns the length of the adjustment and its value in seconds. The sign must be a word,
+/// a dash between a time and a number is a range connector, e.g. "9am - 5pm".
+pub(crate) fn adjustment(rest: &str) -> Option<(usize, i64)> {
+    let (tail, word) = tokenize_word(CompleteStr(rest)).ok()?;
+    // only spaces may separate the adjustment from the match
+    let gap = &rest[..rest.len() - tail.len() - word.len()];
+    if !gap.chars().all(char::is_whitespace) {
+        return None;
+    }
+    let sign = match word.to_lowercase().as_str() {
+        "plus" => 1,
+        "minus" => -1,
+        _ => return None,
+    };
+
+    let (tail, n) = recognize_int(tail).ok()?;
+    if n < 0 {
+        return None;
+    }
+
+    let (tail, word) = tokenize_word(tail).ok()?;
+    // a dot after the unit ends a sentence, it is not a part of the adjustment
+    let unit = word.trim_end_matches('.');
+    let dots = word.len() - unit.len();
+    let size = unit_size(unit)?;
+
+    Some((
+        rest.len() - tail.len() - dots,
+        i64::from(sign) * i64::from(n) * i64::from(size),
+    ))
+}
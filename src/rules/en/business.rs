@@ -0,0 +1,203 @@
+use super::combinators::{stub, tokenize_count_symbols, tokenize_word, wrap_error, Dist};
+use super::common::days_in_month;
+use super::config::ParseConfig;
+use super::consts;
+use super::errors::{SemanticError, UNKNOWN};
+use super::rules::{Context, MyResult, RuleResult, TokenDesc};
+use super::tokens::{Articles, PToken, PeriodPart, Prepositions, Priority, TimeInterval, Token};
+use chrono::prelude::*;
+
+use nom::{alt, apply, call, many_till, named_args, tuple, types::CompleteStr};
+
+const ABBREVIATIONS: [(&str, TimeInterval); 4] = [
+    ("eod", TimeInterval::Day),
+    ("cob", TimeInterval::Day),
+    ("eow", TimeInterval::Week),
+    ("eom", TimeInterval::Month),
+];
+
+/// Recognizes business abbreviations like "EOD" or "e.o.d.", the token is the interval which
+/// ends. Abbreviations are too short for typos recognition, so they must match exactly.
+fn abbreviation(input: CompleteStr) -> MyResult {
+    if let Ok((tail, word)) = tokenize_word(input) {
+        let letters: String = word.chars().filter(|c| *c != '.').collect();
+        let letters = letters.to_lowercase();
+
+        for (pattern, interval) in ABBREVIATIONS.iter() {
+            if letters != *pattern {
+                continue;
+            }
+            // a dot right after "eod" ends a sentence, in "e.o.d." it is a part of the abbreviation
+            let unmatched = if word.trim_end_matches('.').contains('.') {
+                0
+            } else {
+                word.len() - word.trim_end_matches('.').len()
+            };
+            return Ok((
+                CompleteStr(&input[input.len() - tail.len() - unmatched..]),
+                TokenDesc::new(
+                    PToken::PToken(Token::TimeInterval(interval.clone()), Priority(0)),
+                    Dist(0),
+                ),
+            ));
+        }
+    }
+    wrap_error(input, UNKNOWN)
+}
+
+define!(
+    start:
+    [(Token::PeriodPart(PeriodPart::Beginning), Priority(1)), "start", Dist(1)] |
+    [(Token::PeriodPart(PeriodPart::Beginning), Priority(1)), "beginning", Dist(2)]
+);
+define!(
+    end:
+    [(Token::PeriodPart(PeriodPart::End), Priority(1)), "end", Dist(0)] |
+    [(Token::PeriodPart(PeriodPart::End), Priority(1)), "close", Dist(1)]
+);
+
+combine!(part => start | end);
+
+define!(of: (Token::Prepositions(Prepositions::Of), Priority(2)), "of", Dist(0));
+define!(the: (Token::Articles(Articles::The), Priority(3)), "the", Dist(0));
+
+define!(
+    day:
+    [(Token::TimeInterval(TimeInterval::Day), Priority(0)), "day", Dist(0)] |
+    [(Token::TimeInterval(TimeInterval::Day), Priority(0)), "business", Dist(2)]
+);
+
+named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
+                             ( TokenDesc, TokenDesc, TokenDesc, TokenDesc ) )>,
+    many_till!(tokenize_count_symbols,
+        alt!(
+            // e.g. "start of day", "end of the day", "close of business"
+            tuple!(apply!(part, dist_limit), apply!(of, dist_limit),
+                   alt!(apply!(the, dist_limit) | stub), apply!(day, dist_limit)) |
+            // e.g. "EOD", "COB", "e.o.w."
+            tuple!(abbreviation, stub, stub, stub)
+        )
+    )
+);
+
+make_interpreter!(positions = 4);
+
+fn make_time<'a, 'b, Tz: TimeZone>(
+    res: &'a RuleResult,
+    tz_aware: DateTime<Tz>,
+    _input: &'b str,
+    config: &ParseConfig,
+) -> Result<Context, SemanticError<'b>> {
+    let mut ctx = Context::default();
+
+    match res.token_by_priority(Priority(0)) {
+        Some(Token::TimeInterval(TimeInterval::Week)) => {
+            // business week ends on friday, it is the past one for weekends
+            let weekday = tz_aware.weekday().num_days_from_monday() as i32;
+            ctx.set_duration((4 - weekday) * consts::DAY);
+        }
+        Some(Token::TimeInterval(TimeInterval::Month)) => {
+            ctx.day = Some(days_in_month(tz_aware.year(), tz_aware.month()) as i32);
+        }
+        _ => (),
+    }
+
+    // abbreviations stand for the end of a period
+    let (opening_hour, closing_hour) = config.business_hours;
+    ctx.hour = match res.token_by_priority(Priority(1)) {
+        Some(Token::PeriodPart(PeriodPart::Beginning)) => Some(opening_hour as i32),
+        _ => Some(closing_hour as i32),
+    };
+    ctx.minute = Some(0);
+
+    Ok(ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::interpret;
+    use crate::rules::config::ParseConfig;
+    use crate::rules::consts;
+    use crate::rules::rules::MatchBounds;
+    use chrono::prelude::*;
+
+    fn fixed_time() -> DateTime<Local> {
+        // tuesday
+        Local.ymd(2019, 2, 5).and_hms(10, 0, 0)
+    }
+
+    #[test]
+    fn test_end_of_day() {
+        let result = interpret("send it by EOD.", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(11, 14)));
+        assert_eq!(result.get_duration_sec(), 0);
+        assert_eq!(result.get_hours(), 17);
+        assert_eq!(result.get_minutes(), 0);
+
+        let result = interpret("cob", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 3)));
+        assert_eq!(result.get_hours(), 17);
+
+        let result = interpret("by e.o.d. today", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(3, 9)));
+        assert_eq!(result.get_hours(), 17);
+    }
+
+    #[test]
+    fn test_end_of_week_month() {
+        let result = interpret("eow", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 3 * i64::from(consts::DAY));
+        assert_eq!(result.get_hours(), 17);
+
+        let result = interpret("E.O.M", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 5)));
+        assert_eq!(result.get_day(), 28);
+        assert_eq!(result.get_hours(), 17);
+    }
+
+    #[test]
+    fn test_day_words() {
+        let result = interpret("start of day", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 12)));
+        assert_eq!(result.get_hours(), 9);
+        assert_eq!(result.get_minutes(), 0);
+
+        let result = interpret(
+            "by the end of the day",
+            &ParseConfig::default(),
+            fixed_time(),
+        );
+        let result = result.unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(7, 21)));
+        assert_eq!(result.get_hours(), 17);
+
+        let result = interpret("close of business", &ParseConfig::default(), fixed_time());
+        assert_eq!(result.unwrap().get_hours(), 17);
+    }
+
+    #[test]
+    fn test_business_hours() {
+        let config = ParseConfig {
+            business_hours: (8, 18),
+            ..Default::default()
+        };
+
+        let result = interpret("EOD", &config, fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 18);
+
+        let result = interpret("beginning of day", &config, fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 8);
+
+        let result = interpret("eow", &config, fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 3 * i64::from(consts::DAY));
+        assert_eq!(result.get_hours(), 18);
+    }
+
+    #[test]
+    fn test_exact_match_only() {
+        for input in &["eodd", "edo", "e.o", "geom"] {
+            let result = interpret(input, &ParseConfig::default(), fixed_time()).unwrap();
+            assert_eq!(result.bounds, None);
+        }
+    }
+}
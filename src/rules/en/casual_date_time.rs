@@ -1,8 +1,11 @@
 use super::combinators::{stub, tokenize_count_symbols, Dist};
+use super::config::ParseConfig;
 use super::consts;
 use super::errors::SemanticError;
 use super::rules::{Context, RuleResult, TokenDesc};
-use super::tokens::{Priority, Pronouns, TimeOfDay, Token, When};
+use super::tokens::{
+    Articles, Prepositions, Priority, Pronouns, TimeInterval, TimeOfDay, Token, When,
+};
 use chrono::prelude::*;
 
 use nom::{alt, apply, call, many_till, named_args, tuple, types::CompleteStr};
@@ -16,6 +19,11 @@ define!(this: (Token::Pronouns(Pronouns::This), Priority(1)), "this", Dist(1));
 
 combine!(adj => last | this | next | past);
 
+// "coming" is the same as "next" before a period, "this" before it changes nothing, e.g. "this
+// coming month"
+define!(coming: (Token::When(When::Next), Priority(1)), "coming", Dist(1));
+define!(this_coming: (Token::Pronouns(Pronouns::This), Priority(6)), "this", Dist(1));
+
 define!(today: (Token::When(When::Today), Priority(1)), "today", Dist(1));
 define!(tonight: (Token::When(When::Tonight), Priority(1)), "tonight", Dist(2));
 define!(
@@ -30,41 +38,98 @@ combine!(when => today | tonight | yesterday | tomorrow);
 define!(night: (Token::TimeOfDay(TimeOfDay::Night), Priority(2)), "night", Dist(1));
 define!(morning: (Token::TimeOfDay(TimeOfDay::Morning), Priority(2)), "morning", Dist(2));
 define!(evening: (Token::TimeOfDay(TimeOfDay::Evening), Priority(2)), "evening", Dist(2));
-define!(noon: (Token::TimeOfDay(TimeOfDay::Noon), Priority(2)), "noon", Dist(1));
+define!(
+    noon:
+    [(Token::TimeOfDay(TimeOfDay::Noon), Priority(2)), "noon", Dist(1)] |
+    [(Token::TimeOfDay(TimeOfDay::Noon), Priority(2)), "midday", Dist(1)]
+);
+define!(midnight: (Token::TimeOfDay(TimeOfDay::Midnight), Priority(2)), "midnight", Dist(2));
 define!(afternoon: (Token::TimeOfDay(TimeOfDay::Afternoon), Priority(2)), "afternoon", Dist(2));
 
-combine!(time_of_day => night | morning | evening | noon | afternoon);
+combine!(time_of_day => night | morning | evening | noon | midnight | afternoon);
+
+define!(month: (Token::TimeInterval(TimeInterval::Month), Priority(2)), "month", Dist(1));
+define!(year: (Token::TimeInterval(TimeInterval::Year), Priority(2)), "year", Dist(1));
+
+combine!(period => month | year);
+
+define!(the: (Token::Articles(Articles::The), Priority(3)), "the", Dist(0));
+define!(day: (Token::TimeInterval(TimeInterval::Day), Priority(3)), "day", Dist(0));
+define!(after: (Token::Prepositions(Prepositions::After), Priority(4)), "after", Dist(1));
+define!(before: (Token::Prepositions(Prepositions::Before), Priority(4)), "before", Dist(1));
+define!(at: (Token::Prepositions(Prepositions::At), Priority(5)), "at", Dist(0));
 
-named_args!(parse<'a>(exact_match: bool)<CompleteStr<'a>, (Vec<usize>,
-                             ( TokenDesc, TokenDesc, ) )>,
+named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
+                             ( TokenDesc, TokenDesc, TokenDesc, TokenDesc, ) )>,
     many_till!(tokenize_count_symbols,
         alt!(
+            // the day after tomorrow, day after tomorrow
+            tuple!(alt!(apply!(the, dist_limit) | stub), apply!(day, dist_limit),
+                   apply!(after, dist_limit), apply!(tomorrow, dist_limit)) |
+            // the day before yesterday, day before yesterday
+            tuple!(alt!(apply!(the, dist_limit) | stub), apply!(day, dist_limit),
+                   apply!(before, dist_limit), apply!(yesterday, dist_limit)) |
+            // next month, last year, etc.
+            tuple!(apply!(adj, dist_limit), apply!(period, dist_limit), stub, stub) |
+            // coming month, this coming year
+            tuple!(alt!(apply!(this_coming, dist_limit) | stub), apply!(coming, dist_limit),
+                   apply!(period, dist_limit), stub) |
             // last night, this morning, etc.
-            tuple!(apply!(adj, exact_match), apply!(time_of_day, exact_match)) |
-            // tomorrow evening, today morning, etc.
-            tuple!(apply!(when, exact_match), apply!(time_of_day, exact_match)) |
+            tuple!(apply!(adj, dist_limit), apply!(time_of_day, dist_limit), stub, stub) |
+            // tomorrow evening, today morning, tomorrow at noon, etc.
+            tuple!(apply!(when, dist_limit), alt!(apply!(at, dist_limit) | stub),
+                   apply!(time_of_day, dist_limit), stub) |
+            // noon tomorrow, midnight tonight, etc.
+            tuple!(apply!(time_of_day, dist_limit), apply!(when, dist_limit), stub, stub) |
             // today, tomorrow, yesterday, etc.
-            tuple!(apply!(when, exact_match), stub) |
+            tuple!(apply!(when, dist_limit), stub, stub, stub) |
             // now
-            tuple!(apply!(now, exact_match), stub) |
+            tuple!(apply!(now, dist_limit), stub, stub, stub) |
             // night, morning, evening, etc
-            tuple!(apply!(time_of_day, exact_match), stub)
+            tuple!(apply!(time_of_day, dist_limit), stub, stub, stub)
         )
     )
 );
 
-make_interpreter!(positions = 2);
+make_interpreter!(positions = 4);
 
-fn make_time<'a, 'b, Tz: TimeZone>(
+pub(crate) fn make_time<'a, 'b, Tz: TimeZone>(
     res: &'a RuleResult,
-    _tz_aware: DateTime<Tz>,
+    tz_aware: DateTime<Tz>,
     _input: &'b str,
+    config: &ParseConfig,
 ) -> Result<Context, SemanticError<'b>> {
     let mut ctx = Context::default();
 
+    if let Some(Token::TimeInterval(interval)) = res.token_by_priority(Priority(2)) {
+        let shift = match res.token_by_priority(Priority(1)) {
+            Some(Token::When(When::Next)) => 1,
+            Some(Token::When(When::Last)) | Some(Token::When(When::Past)) => -1,
+            _ => 0,
+        };
+        match interval {
+            // "this month" and "this year" are still dates
+            TimeInterval::Month if shift == 0 => ctx.month = Some(tz_aware.month() as i32),
+            TimeInterval::Year if shift == 0 => ctx.year = Some(tz_aware.year()),
+            TimeInterval::Month => ctx.months = shift,
+            TimeInterval::Year => ctx.years = shift,
+            _ => (),
+        }
+        return Ok(ctx);
+    }
+
+    // "the day after tomorrow" and "the day before yesterday" shift one more day
+    let days = match res.token_by_priority(Priority(4)) {
+        Some(_) => 2,
+        None => 1,
+    };
+
     let token = res.token_by_priority(Priority(1));
     if token.is_some() {
         match token.unwrap() {
+            Token::When(When::Today) => {
+                ctx.zero_time = config.zero_time_of_day;
+            }
             Token::When(When::Last) | Token::When(When::Past) => {
                 ctx.set_duration(-24 * i64::from(consts::HOUR));
             }
@@ -72,41 +137,66 @@ fn make_time<'a, 'b, Tz: TimeZone>(
                 ctx.set_duration(24 * i64::from(consts::HOUR));
             }
             Token::When(When::Tomorrow) => {
-                ctx.set_duration(24 * i64::from(consts::HOUR));
+                ctx.set_duration(days * 24 * i64::from(consts::HOUR));
+                ctx.zero_time = config.zero_time_of_day;
             }
             Token::When(When::Yesterday) => {
-                ctx.set_duration(-24 * i64::from(consts::HOUR));
+                ctx.set_duration(-days * 24 * i64::from(consts::HOUR));
+                ctx.zero_time = config.zero_time_of_day;
             }
             Token::When(When::Tonight) => {
-                ctx.hour = Some(23);
+                // it is the same day even if the time has passed, e.g. "tonight" said at 23:30
+                // refers to the current night rather than to the next one
+                ctx.hour = Some(consts::NIGHT_HOUR);
                 ctx.minute = Some(0);
             }
             _ => (),
         }
     }
 
+    // bare "noon" and "midnight" which have passed refer to the next day
+    let bare = res.token_by_priority(Priority(1)).is_none();
+    let has_passed = |hour: u32| {
+        bare && config.prefer_future
+            && (tz_aware.hour(), tz_aware.minute(), tz_aware.second()) > (hour, 0, 0)
+    };
+
     let token = res.token_by_priority(Priority(2));
 
     if token.is_some() {
         match token.unwrap() {
             Token::TimeOfDay(TimeOfDay::Morning) => {
-                ctx.hour = Some(8);
+                ctx.hour = Some(consts::MORNING_HOUR);
                 ctx.minute = Some(0);
             }
             Token::TimeOfDay(TimeOfDay::Noon) => {
                 ctx.hour = Some(12);
                 ctx.minute = Some(0);
+                if has_passed(12) {
+                    ctx.roll_day();
+                }
+            }
+            Token::TimeOfDay(TimeOfDay::Midnight) => {
+                // midnight tonight is the one at the end of the day
+                if let Some(Token::When(When::Tonight)) = res.token_by_priority(Priority(1)) {
+                    ctx.set_duration(consts::DAY);
+                }
+                ctx.hour = Some(0);
+                ctx.minute = Some(0);
+                if has_passed(0) {
+                    ctx.roll_day();
+                }
             }
             Token::TimeOfDay(TimeOfDay::Afternoon) => {
-                ctx.hour = Some(15);
+                ctx.hour = Some(consts::AFTERNOON_HOUR);
                 ctx.minute = Some(0);
             }
             Token::TimeOfDay(TimeOfDay::Evening) => {
-                ctx.hour = Some(18);
+                ctx.hour = Some(consts::EVENING_HOUR);
                 ctx.minute = Some(0);
             }
             Token::TimeOfDay(TimeOfDay::Night) => {
-                ctx.hour = Some(23);
+                ctx.hour = Some(consts::NIGHT_HOUR);
                 ctx.minute = Some(0);
             }
             _ => (),
@@ -119,6 +209,7 @@ fn make_time<'a, 'b, Tz: TimeZone>(
 #[cfg(test)]
 mod tests {
     use super::interpret;
+    use crate::rules::config::ParseConfig;
     use crate::rules::consts;
     use crate::rules::rules::MatchBounds;
     use chrono::prelude::*;
@@ -129,7 +220,12 @@ mod tests {
 
     #[test]
     fn test_casual_date() {
-        let result = interpret("The deadline is now, ok", false, fixed_time()).unwrap();
+        let result = interpret(
+            "The deadline is now, ok",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
         assert_eq!(
             result.bounds,
             Some(MatchBounds {
@@ -139,7 +235,12 @@ mod tests {
         );
         assert_eq!(result.get_duration_sec(), 0);
 
-        let result = interpret("The deadline is today", false, fixed_time()).unwrap();
+        let result = interpret(
+            "The deadline is today",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
         assert_eq!(
             result.bounds,
             Some(MatchBounds {
@@ -149,7 +250,12 @@ mod tests {
         );
         assert_eq!(result.get_duration_sec(), 0);
 
-        let result = interpret("The deadline is tonight", false, fixed_time()).unwrap();
+        let result = interpret(
+            "The deadline is tonight",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
         assert_eq!(
             result.bounds,
             Some(MatchBounds {
@@ -160,7 +266,12 @@ mod tests {
         assert_eq!(result.get_hours(), 23);
         assert_eq!(result.get_minutes(), 0);
 
-        let result = interpret("The deadline is tomorrow", false, fixed_time()).unwrap();
+        let result = interpret(
+            "The deadline is tomorrow",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
         assert_eq!(
             result.bounds,
             Some(MatchBounds {
@@ -170,7 +281,12 @@ mod tests {
         );
         assert_eq!(result.get_duration_sec(), 24 * consts::HOUR as i64);
 
-        let result = interpret("The deadline was yesterday", false, fixed_time()).unwrap();
+        let result = interpret(
+            "The deadline was yesterday",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
         assert_eq!(
             result.bounds,
             Some(MatchBounds {
@@ -180,7 +296,12 @@ mod tests {
         );
         assert_eq!(result.get_duration_sec(), -24 * consts::HOUR as i64);
 
-        let result = interpret("Please call me tomorrow evening", false, fixed_time()).unwrap();
+        let result = interpret(
+            "Please call me tomorrow evening",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
         assert_eq!(
             result.bounds,
             Some(MatchBounds {
@@ -189,9 +310,14 @@ mod tests {
             })
         );
         assert_eq!(result.get_duration_sec(), 24 * consts::HOUR as i64);
-        assert_eq!(result.get_hours(), 18);
+        assert_eq!(result.get_hours(), 19);
 
-        let result = interpret("He told me that yesterday morning", false, fixed_time()).unwrap();
+        let result = interpret(
+            "He told me that yesterday morning",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
         assert_eq!(
             result.bounds,
             Some(MatchBounds {
@@ -200,9 +326,14 @@ mod tests {
             })
         );
         assert_eq!(result.get_duration_sec(), -24 * consts::HOUR as i64);
-        assert_eq!(result.get_hours(), 8);
+        assert_eq!(result.get_hours(), 9);
 
-        let result = interpret("last night I fell asleep", false, fixed_time()).unwrap();
+        let result = interpret(
+            "last night I fell asleep",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
         assert_eq!(
             result.bounds,
             Some(MatchBounds {
@@ -213,7 +344,12 @@ mod tests {
         assert_eq!(result.get_duration_sec(), -24 * consts::HOUR as i64);
         assert_eq!(result.get_hours(), 23);
 
-        let result = interpret("come next evening please", false, fixed_time()).unwrap();
+        let result = interpret(
+            "come next evening please",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
         assert_eq!(
             result.bounds,
             Some(MatchBounds {
@@ -222,6 +358,193 @@ mod tests {
             })
         );
         assert_eq!(result.get_duration_sec(), 24 * consts::HOUR as i64);
-        assert_eq!(result.get_hours(), 18);
+        assert_eq!(result.get_hours(), 19);
+    }
+
+    #[test]
+    fn test_part_of_day() {
+        let result = interpret(
+            "Please call me tomorrow evening",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
+        assert_eq!(result.get_duration_sec(), 24 * consts::HOUR as i64);
+        assert_eq!(result.get_hours(), 19);
+        assert_eq!(result.get_minutes(), 0);
+
+        let result = interpret(
+            "see you this afternoon",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(8, 22)));
+        assert_eq!(result.get_duration_sec(), 0);
+        assert_eq!(result.get_hours(), 15);
+
+        let result = interpret("tomorrow morning", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 24 * consts::HOUR as i64);
+        assert_eq!(result.get_hours(), 9);
+
+        let result = interpret("this evening", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 0);
+        assert_eq!(result.get_hours(), 19);
+    }
+
+    #[test]
+    fn test_day_after_tomorrow() {
+        let result = interpret(
+            "see you the day after tomorrow",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(8, 30)));
+        assert_eq!(result.get_duration_sec(), 2 * consts::DAY as i64);
+
+        let result =
+            interpret("day befre yesterday", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 19)));
+        assert_eq!(result.get_duration_sec(), -2 * consts::DAY as i64);
+
+        let result = interpret(
+            "the day aftr tomorow",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
+        assert_eq!(result.get_duration_sec(), 2 * consts::DAY as i64);
+
+        let result = interpret("the day after", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, None);
+    }
+
+    #[test]
+    fn test_tonight() {
+        let morning = Local.ymd(2019, 1, 1).and_hms(8, 15, 0);
+        let result = interpret("tonight", &ParseConfig::default(), morning).unwrap();
+        assert_eq!(result.get_duration_sec(), 0);
+        assert_eq!(result.get_hours(), consts::NIGHT_HOUR);
+        assert_eq!(result.get_minutes(), 0);
+
+        // no roll over to the next day late at night
+        let late = Local.ymd(2019, 1, 1).and_hms(23, 30, 0);
+        let result = interpret("tonight", &ParseConfig::default(), late).unwrap();
+        assert_eq!(result.get_duration_sec(), 0);
+        assert_eq!(result.get_hours(), consts::NIGHT_HOUR);
+    }
+
+    #[test]
+    fn test_noon_midnight() {
+        let result =
+            interpret("let's meet at noon", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(14, 18)));
+        assert_eq!(result.get_hours(), 12);
+        assert_eq!(result.get_minutes(), 0);
+
+        let result = interpret("lunch at midday", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(9, 15)));
+        assert_eq!(result.get_hours(), 12);
+
+        let result =
+            interpret("call me at midnight", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(11, 19)));
+        assert_eq!(result.get_hours(), 0);
+        assert_eq!(result.get_minutes(), 0);
+
+        let result =
+            interpret("call me at midnigth", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 0);
+
+        let result = interpret("tomorrow at noon", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 16)));
+        assert_eq!(result.get_duration_sec(), i64::from(consts::DAY));
+        assert_eq!(result.get_hours(), 12);
+
+        let result = interpret("at noon tomorrow", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(3, 16)));
+        assert_eq!(result.get_duration_sec(), i64::from(consts::DAY));
+        assert_eq!(result.get_hours(), 12);
+
+        let result = interpret("midnight tonight", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 16)));
+        assert_eq!(result.get_duration_sec(), i64::from(consts::DAY));
+        assert_eq!(result.get_hours(), 0);
+        assert_eq!(result.get_minutes(), 0);
+
+        let result =
+            interpret("tonight at midnight", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), i64::from(consts::DAY));
+        assert_eq!(result.get_hours(), 0);
+    }
+
+    #[test]
+    fn test_next_last_period() {
+        let result = interpret("next month", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 10)));
+        assert_eq!(result.get_months(), 1);
+        assert_eq!(result.get_duration_sec(), 0);
+
+        let result = interpret("last month", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_months(), -1);
+        assert_eq!(result.get_year(), 0);
+
+        let result = interpret("last year", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 9)));
+        assert_eq!(result.get_years(), -1);
+
+        let result = interpret("see you nxt year", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_years(), 1);
+
+        let result = interpret("this month", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_month(), 1);
+        assert_eq!(result.get_months(), 0);
+
+        let result = interpret("the coming month", &ParseConfig::default(), fixed_time());
+        let result = result.unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(4, 16)));
+        assert_eq!(result.get_months(), 1);
+
+        let result = interpret("this coming year", &ParseConfig::default(), fixed_time());
+        let result = result.unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 16)));
+        assert_eq!(result.get_years(), 1);
+    }
+
+    #[test]
+    fn test_prefer_future() {
+        let afternoon = Local.ymd(2019, 1, 1).and_hms(15, 0, 0);
+
+        // both have passed, so they are the next day's ones
+        let result = interpret("at midnight", &ParseConfig::default(), afternoon).unwrap();
+        assert_eq!(result.get_duration_sec(), i64::from(consts::DAY));
+        assert_eq!(result.get_hours(), 0);
+
+        let result = interpret("noon", &ParseConfig::default(), afternoon).unwrap();
+        assert_eq!(result.get_duration_sec(), i64::from(consts::DAY));
+        assert_eq!(result.get_hours(), 12);
+
+        // noon has not come yet and midnight is right now
+        let result = interpret("noon", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 0);
+        let result = interpret("midnight", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 0);
+
+        // a given day is not moved
+        let result = interpret("today at noon", &ParseConfig::default(), afternoon).unwrap();
+        assert_eq!(result.get_duration_sec(), 0);
+
+        let config = ParseConfig {
+            prefer_future: false,
+            ..Default::default()
+        };
+        let result = interpret("at midnight", &config, afternoon).unwrap();
+        assert_eq!(result.get_duration_sec(), 0);
+        assert_eq!(result.get_hours(), 0);
+
+        let result = interpret("midday", &config, afternoon).unwrap();
+        assert_eq!(result.get_duration_sec(), 0);
+        assert_eq!(result.get_hours(), 12);
     }
 }
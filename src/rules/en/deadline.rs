@@ -1,5 +1,6 @@
 use super::combinators::{stub, tokenize_count_symbols, Dist};
 use super::common_matchers::match_num;
+use super::config::ParseConfig;
 use super::consts;
 use super::errors::{invalid_time_error, SemanticError};
 use super::rules::{Context, RuleResult, TokenDesc};
@@ -13,6 +14,8 @@ use nom::{alt, apply, call, many_till, named_args, tuple, types::CompleteStr};
 define!(
     adverb:
     [(Token::Adverbs(Adverbs::Few), Priority(0)), "few", Dist(0)] |
+    [(Token::Adverbs(Adverbs::Couple), Priority(0)), "couple", Dist(1)] |
+    [(Token::Adverbs(Adverbs::Several), Priority(0)), "several", Dist(2)] |
     [(Token::Adverbs(Adverbs::Half), Priority(0)), "half", Dist(1)]
 );
 
@@ -80,6 +83,12 @@ define!(
     [(Token::TimeInterval(TimeInterval::Week), Priority(4)), "week", Dist(1)]
 );
 
+define!(
+    fortnights:
+    [(Token::TimeInterval(TimeInterval::Fortnight), Priority(4)), "fortnights", Dist(2)] |
+    [(Token::TimeInterval(TimeInterval::Fortnight), Priority(4)), "fortnight", Dist(2)]
+);
+
 define!(
     months:
     [(Token::TimeInterval(TimeInterval::Month), Priority(4)), "months", Dist(1)] |
@@ -92,38 +101,54 @@ define!(
     [(Token::TimeInterval(TimeInterval::Year), Priority(4)), "year", Dist(1)]
 );
 
-combine!(time_interval => seconds | minutes | hours | days | weeks | months | years);
+combine!(time_interval => seconds | minutes | hours | days | weeks | fortnights | months
+                              | years);
+
+define!(from: (Token::Prepositions(Prepositions::From), Priority(5)), "from", Dist(1));
+define!(now: (Token::When(When::Now), Priority(6)), "now", Dist(0));
 
-named_args!(parse<'a>(exact_match: bool)<CompleteStr<'a>, (Vec<usize>,
+named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
                              ( TokenDesc, TokenDesc, TokenDesc, TokenDesc, TokenDesc ) )>,
 
     many_till!(tokenize_count_symbols,
         alt!(
             // e.g.: in the half of year
-            tuple!(apply!(when, exact_match), apply!(article, true), apply!(adverb, exact_match),
-                   apply!(prepos, true), apply!(time_interval, exact_match)) |
+            tuple!(apply!(when, dist_limit), apply!(article, Dist(0)), apply!(adverb, dist_limit),
+                   apply!(prepos, Dist(0)), apply!(time_interval, dist_limit)) |
             // e.g.: in half of year
-            tuple!(apply!(when, exact_match), apply!(adverb, exact_match), apply!(prepos, true),
-                   apply!(time_interval, exact_match), stub) |
+            tuple!(apply!(when, dist_limit), apply!(adverb, dist_limit), apply!(prepos, Dist(0)),
+                   apply!(time_interval, dist_limit), stub) |
             // e.g.: in a five months
-            tuple!(apply!(when, exact_match), apply!(article, true),
-                   apply!(int_word, exact_match), apply!(time_interval, exact_match), stub) |
+            tuple!(apply!(when, dist_limit), apply!(article, Dist(0)),
+                   apply!(int_word, dist_limit), apply!(time_interval, dist_limit), stub) |
             // e.g.: in a 5 months
-            tuple!(apply!(when, exact_match), apply!(article, true), number,
-                   apply!(time_interval, exact_match), stub) |
+            tuple!(apply!(when, dist_limit), apply!(article, Dist(0)), number,
+                   apply!(time_interval, dist_limit), stub) |
             // e.g.: in five months
-            tuple!(apply!(when, exact_match), apply!(int_word, exact_match),
-                   apply!(time_interval, exact_match), stub, stub) |
+            tuple!(apply!(when, dist_limit), apply!(int_word, dist_limit),
+                   apply!(time_interval, dist_limit), stub, stub) |
             // e.g.: in 5 months
-            tuple!(apply!(when, exact_match), number, apply!(time_interval, exact_match), stub, stub) |
+            tuple!(apply!(when, dist_limit), number, apply!(time_interval, dist_limit), stub, stub) |
             // e.g.: in the few days
-            tuple!(apply!(when, exact_match), apply!(article, true),
-                   apply!(adverb, exact_match), apply!(time_interval, exact_match), stub) |
-            // e.g.: in few days
-            tuple!(apply!(when, exact_match), apply!(adverb, exact_match),
-                   apply!(time_interval, exact_match), stub, stub) |
+            tuple!(apply!(when, dist_limit), apply!(article, Dist(0)),
+                   apply!(adverb, dist_limit), apply!(time_interval, dist_limit), stub) |
+            // e.g.: in few days, in several weeks
+            tuple!(apply!(when, dist_limit), apply!(adverb, dist_limit),
+                   apply!(time_interval, dist_limit), stub, stub) |
+            // e.g.: in a week, in a fortnight
+            tuple!(apply!(when, dist_limit), apply!(article, Dist(0)),
+                   apply!(time_interval, dist_limit), stub, stub) |
             // e.g.: in a month, in a second, etc.
-            tuple!(apply!(when, exact_match), apply!(time_interval, exact_match), stub, stub, stub)
+            tuple!(apply!(when, dist_limit), apply!(time_interval, dist_limit), stub, stub, stub) |
+            // e.g.: 5 years from now
+            tuple!(number, apply!(time_interval, dist_limit), apply!(from, dist_limit),
+                   apply!(now, dist_limit), stub) |
+            // e.g.: three days from now
+            tuple!(apply!(int_word, dist_limit), apply!(time_interval, dist_limit),
+                   apply!(from, dist_limit), apply!(now, dist_limit), stub) |
+            // e.g.: a week from now
+            tuple!(apply!(article, Dist(0)), apply!(time_interval, dist_limit),
+                   apply!(from, dist_limit), apply!(now, dist_limit), stub)
         )
     )
 );
@@ -132,8 +157,9 @@ make_interpreter!(positions = 5);
 
 fn make_time<'a, 'b, Tz: TimeZone>(
     res: &'a RuleResult,
-    tz_aware: DateTime<Tz>,
+    _tz_aware: DateTime<Tz>,
     input: &'b str,
+    config: &ParseConfig,
 ) -> Result<Context, SemanticError<'b>> {
     let mut ctx = Context::default();
 
@@ -144,7 +170,10 @@ fn make_time<'a, 'b, Tz: TimeZone>(
 
     if token.is_some() {
         match token.unwrap() {
-            Token::Adverbs(Adverbs::Few) => num = 3,
+            Token::Adverbs(Adverbs::Couple) => num = 2,
+            Token::Adverbs(Adverbs::Few) | Token::Adverbs(Adverbs::Several) => {
+                num = config.few_count as i32
+            }
             Token::Adverbs(Adverbs::Half) => half = true,
             _ => (),
         }
@@ -183,23 +212,23 @@ fn make_time<'a, 'b, Tz: TimeZone>(
             } else {
                 num * consts::WEEK
             }),
+            Token::TimeInterval(TimeInterval::Fortnight) => ctx.set_duration(if half {
+                consts::WEEK
+            } else {
+                num * 2 * consts::WEEK
+            }),
             Token::TimeInterval(TimeInterval::Month) => {
                 if half {
                     ctx.set_duration(14 * consts::DAY);
                 } else {
-                    ctx.month = Some(tz_aware.month() as i32 + num);
+                    ctx.months = num;
                 }
             }
             Token::TimeInterval(TimeInterval::Year) => {
                 if half {
-                    let mut new_month = tz_aware.month() as i32 + 6;
-                    if new_month > 12 {
-                        ctx.year = Some(tz_aware.year() + 1);
-                        new_month %= 12;
-                    }
-                    ctx.month = Some(new_month);
+                    ctx.months = 6;
                 } else {
-                    ctx.year = Some(tz_aware.year() + num);
+                    ctx.years = num;
                 }
             }
             _ => (),
@@ -212,8 +241,10 @@ fn make_time<'a, 'b, Tz: TimeZone>(
 #[cfg(test)]
 mod tests {
     use super::interpret;
+    use crate::rules::config::ParseConfig;
     use crate::rules::consts;
     use crate::rules::errors::invalid_time_error;
+    use crate::rules::rules::MatchBounds;
     use chrono::prelude::*;
 
     fn fixed_time() -> DateTime<Local> {
@@ -223,25 +254,30 @@ mod tests {
 
     #[test]
     fn test_deadline() {
-        let result = interpret("in 2 months", false, fixed_time()).unwrap();
-        assert_eq!(result.get_month(), 3);
+        let result = interpret("in 2 months", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_months(), 2);
 
-        let result = interpret("in three months", false, fixed_time()).unwrap();
-        assert_eq!(result.get_month(), 4);
+        let result = interpret("in three months", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_months(), 3);
 
-        let result = interpret("in a half year", false, fixed_time()).unwrap();
-        assert_eq!(result.get_month(), 7);
+        let result = interpret("in a half year", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_months(), 6);
 
-        let result = interpret("in the few days", false, fixed_time()).unwrap();
+        let result = interpret("in the few days", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.get_duration_sec(), 3 * consts::DAY as i64);
 
-        let result = interpret("in 5 minutes", false, fixed_time()).unwrap();
+        let result = interpret("in 5 minutes", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.get_duration_sec(), 5 * consts::MINUTE as i64);
 
-        let result = interpret("in 5 minutes I will go home", false, fixed_time()).unwrap();
+        let result = interpret(
+            "in 5 minutes I will go home",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
         assert_eq!(result.get_duration_sec(), 5 * consts::MINUTE as i64);
 
-        let result = interpret("in -3 minute", false, fixed_time());
+        let result = interpret("in -3 minute", &ParseConfig::default(), fixed_time());
         assert_eq!(
             result.unwrap_err().extract_error(),
             invalid_time_error("in -3 minute", "number", -3).extract_error()
@@ -249,7 +285,7 @@ mod tests {
 
         let result = interpret(
             "we have to do something within 10 days.",
-            false,
+            &ParseConfig::default(),
             fixed_time(),
         )
         .unwrap();
@@ -257,21 +293,26 @@ mod tests {
 
         let result = interpret(
             "we have to do something within five days.",
-            false,
+            &ParseConfig::default(),
             fixed_time(),
         )
         .unwrap();
         assert_eq!(result.get_duration_sec(), 5 * consts::DAY as i64);
 
-        let result = interpret("in a half year", false, fixed_time()).unwrap();
-        assert_eq!(result.get_month(), 7);
+        let result = interpret("in a half year", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_months(), 6);
 
-        let result = interpret("drop me a line in a half hour", false, fixed_time()).unwrap();
+        let result = interpret(
+            "drop me a line in a half hour",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
         assert_eq!(result.get_duration_sec(), 30 * consts::MINUTE as i64);
 
         let result = interpret(
             "if will finish this project in the hlf of mnth",
-            false,
+            &ParseConfig::default(),
             fixed_time(),
         )
         .unwrap();
@@ -279,10 +320,107 @@ mod tests {
 
         let result = interpret(
             "if will finish this project in hlf of mnth",
-            false,
+            &ParseConfig::default(),
             fixed_time(),
         )
         .unwrap();
         assert_eq!(result.get_duration_sec(), 2 * consts::WEEK as i64);
     }
+
+    #[test]
+    fn test_years() {
+        // years are added by the calendar, day and month stay the same
+        let result = interpret("in 3 years", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 10)));
+        assert_eq!(result.get_years(), 3);
+        assert_eq!(result.get_month(), 0);
+        assert_eq!(result.get_day(), 0);
+        assert_eq!(result.get_duration_sec(), 0);
+
+        let result = interpret(
+            "it will be ready 5 years from now",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(17, 33)));
+        assert_eq!(result.get_years(), 5);
+
+        let result = interpret("two days from now", &ParseConfig::default(), fixed_time());
+        assert_eq!(result.unwrap().get_duration_sec(), 2 * consts::DAY as i64);
+
+        let result = interpret("a week from now", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 15)));
+        assert_eq!(result.get_duration_sec(), consts::WEEK as i64);
+    }
+
+    #[test]
+    fn test_in_duration() {
+        let result = interpret("in 3 days", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 9)));
+        assert_eq!(result.get_duration_sec(), 3 * consts::DAY as i64);
+
+        let result = interpret(
+            "see you in two weeks",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(8, 20)));
+        assert_eq!(result.get_duration_sec(), 2 * consts::WEEK as i64);
+
+        let result = interpret("in 4 hours", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 4 * consts::HOUR as i64);
+
+        let result = interpret("in ten minutes", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 10 * consts::MINUTE as i64);
+    }
+
+    #[test]
+    fn test_vague_quantities() {
+        let result = interpret("in a couple of days", &ParseConfig::default(), fixed_time());
+        let result = result.unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 19)));
+        assert_eq!(result.get_duration_sec(), 2 * consts::DAY as i64);
+        assert!(result.is_approximate());
+
+        let result = interpret("in a few hours", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 14)));
+        assert_eq!(result.get_duration_sec(), 3 * consts::HOUR as i64);
+        assert!(result.is_approximate());
+
+        let result = interpret("in several weeks", &ParseConfig::default(), fixed_time());
+        assert_eq!(result.unwrap().get_duration_sec(), 3 * consts::WEEK as i64);
+
+        let config = ParseConfig {
+            few_count: 4,
+            ..Default::default()
+        };
+        let result = interpret("in several weeks", &config, fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 4 * consts::WEEK as i64);
+        let result = interpret("in a couple of days", &config, fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 2 * consts::DAY as i64);
+
+        // an exact count is not approximate
+        let result = interpret("in 3 days", &ParseConfig::default(), fixed_time()).unwrap();
+        assert!(!result.is_approximate());
+    }
+
+    #[test]
+    fn test_fortnight() {
+        let result = interpret("in a fortnight", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 14)));
+        assert_eq!(result.get_duration_sec(), 14 * consts::DAY as i64);
+
+        let result = interpret("in two fortnigths", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 28 * consts::DAY as i64);
+
+        let result = interpret(
+            "in half of fortnight",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
+        assert_eq!(result.get_duration_sec(), 7 * consts::DAY as i64);
+    }
 }
@@ -1,12 +1,13 @@
-use super::combinators::{stub, tokenize_count_symbols, Dist};
-use super::common::{is_leap_year, DAYS_IN_MONTH};
+use super::combinators::{stub, tokenize_count_symbols, tokenize_word, wrap_error, Dist};
+use super::common::{self, days_in_month};
 use super::common_matchers::match_ordinal;
-use super::errors::{invalid_time_error, SemanticError};
-use super::rules::{Context, RuleResult, TokenDesc};
-use super::tokens::{Month, Ordinals, Prepositions, Priority, Token};
+use super::config::ParseConfig;
+use super::errors::{invalid_date_error, SemanticError, UNKNOWN};
+use super::rules::{Context, MyResult, RuleResult, TokenDesc};
+use super::tokens::{Articles, Month, Ordinals, PToken, Prepositions, Priority, Token, When};
 use chrono::prelude::*;
 
-use nom::{alt, apply, call, many_till, named_args, tuple, types::CompleteStr};
+use nom::{alt, apply, call, many_till, named_args, tuple, types::CompleteStr, IResult};
 
 define_num!(day_num: (Token::Number, Priority(0)));
 
@@ -33,108 +34,76 @@ define!(numeric_ord:
     [(Token::Ordinals(Ordinals::Twentieth), Priority(1)), "20th", Dist(0)] |
     [(Token::Ordinals(Ordinals::TwentyFirst), Priority(1)), "21st", Dist(0)] |
     [(Token::Ordinals(Ordinals::TwentySecond), Priority(1)), "22nd", Dist(0)] |
-    [(Token::Ordinals(Ordinals::TwentyThird), Priority(1)), "23rt", Dist(0)] |
+    [(Token::Ordinals(Ordinals::TwentyThird), Priority(1)), "23rd", Dist(0)] |
     [(Token::Ordinals(Ordinals::TwentyFourth), Priority(1)), "24th", Dist(0)] |
     [(Token::Ordinals(Ordinals::TwentyFifth), Priority(1)), "25th", Dist(0)] |
     [(Token::Ordinals(Ordinals::TwentySixth), Priority(1)), "26th", Dist(0)] |
     [(Token::Ordinals(Ordinals::TwentySeventh), Priority(1)), "27th", Dist(0)] |
     [(Token::Ordinals(Ordinals::TwentyEighth), Priority(1)), "28th", Dist(0)] |
     [(Token::Ordinals(Ordinals::TwentyNinth), Priority(1)), "29th", Dist(0)] |
-    [(Token::Ordinals(Ordinals::Thirtieth), Priority(1)), "30nt", Dist(0)] |
+    [(Token::Ordinals(Ordinals::Thirtieth), Priority(1)), "30th", Dist(0)] |
     [(Token::Ordinals(Ordinals::ThirtiethFirst), Priority(1)), "31st", Dist(0)]
 );
 
-define!(twentieth:
-    [(Token::Ordinals(Ordinals::Twentieth), Priority(2)), "twentieth", Dist(3)] |
-    [(Token::Ordinals(Ordinals::Twentieth), Priority(2)), "twenty", Dist(2)]
-);
-define!(thirtieth:
-    [(Token::Ordinals(Ordinals::Thirtieth), Priority(2)), "thirtieth", Dist(3)] |
-    [(Token::Ordinals(Ordinals::Thirtieth), Priority(2)), "thirty", Dist(2)]
-);
-
-combine!(tens => twentieth | thirtieth);
-
-define!(first:
-    [(Token::Ordinals(Ordinals::First), Priority(3)), "first", Dist(1)] |
-    [(Token::Ordinals(Ordinals::First), Priority(3)), "one", Dist(0)]
-);
-define!(second:
-    [(Token::Ordinals(Ordinals::Second), Priority(3)), "second", Dist(1)] |
-    [(Token::Ordinals(Ordinals::Second), Priority(3)), "two", Dist(0)]
-);
-define!(third:
-    [(Token::Ordinals(Ordinals::Third), Priority(3)), "third", Dist(1)] |
-    [(Token::Ordinals(Ordinals::Third), Priority(3)), "three", Dist(1)]
-);
-define!(fourth:
-    [(Token::Ordinals(Ordinals::Fourth), Priority(3)), "fourth", Dist(1)] |
-    [(Token::Ordinals(Ordinals::Fourth), Priority(3)), "four", Dist(1)]
-);
-define!(fifth:
-    [(Token::Ordinals(Ordinals::Fifth), Priority(3)), "fifth", Dist(1)] |
-    [(Token::Ordinals(Ordinals::Fifth), Priority(3)), "five", Dist(1)]
-);
-define!(sixth:
-    [(Token::Ordinals(Ordinals::Sixth), Priority(3)), "sixth", Dist(1)] |
-    [(Token::Ordinals(Ordinals::Sixth), Priority(3)), "six", Dist(0)]
-);
-define!(seventh:
-    [(Token::Ordinals(Ordinals::Seventh), Priority(3)), "seventh", Dist(1)] |
-    [(Token::Ordinals(Ordinals::Seventh), Priority(3)), "seven", Dist(1)]
-);
-define!(eighth:
-    [(Token::Ordinals(Ordinals::Eighth), Priority(3)), "eighth", Dist(1)] |
-    [(Token::Ordinals(Ordinals::Eighth), Priority(3)), "eight", Dist(1)]
-);
-define!(ninth:
-    [(Token::Ordinals(Ordinals::Ninth), Priority(3)), "ninth", Dist(1)] |
-    [(Token::Ordinals(Ordinals::Ninth), Priority(3)), "nine", Dist(1)]
-);
-define!(tenth:
-    [(Token::Ordinals(Ordinals::Tenth), Priority(3)), "tenth", Dist(1)] |
-    [(Token::Ordinals(Ordinals::Tenth), Priority(3)), "ten", Dist(0)]
-);
-define!(eleventh:
-    [(Token::Ordinals(Ordinals::Eleventh), Priority(3)), "eleventh", Dist(2)] |
-    [(Token::Ordinals(Ordinals::Eleventh), Priority(3)), "eleven", Dist(1)]
-);
-define!(twelfth:
-    [(Token::Ordinals(Ordinals::Twelfth), Priority(3)), "twelfth", Dist(1)] |
-    [(Token::Ordinals(Ordinals::Twelfth), Priority(3)), "twelve", Dist(1)]
-);
-define!(thirteenth:
-    [(Token::Ordinals(Ordinals::Thirteenth), Priority(3)), "thirteenth", Dist(2)] |
-    [(Token::Ordinals(Ordinals::Thirteenth), Priority(3)), "thirteen", Dist(1)]
-);
-define!(fourteenth:
-    [(Token::Ordinals(Ordinals::Fourteenth), Priority(3)), "fourteenth", Dist(2)] |
-    [(Token::Ordinals(Ordinals::Fourteenth), Priority(3)), "fourteen", Dist(1)]
-);
-define!(fifteenth:
-    [(Token::Ordinals(Ordinals::Fifteenth), Priority(3)), "fifteenth", Dist(2)] |
-    [(Token::Ordinals(Ordinals::Fifteenth), Priority(3)), "fifteen", Dist(1)]
-);
-define!(sixteenth:
-    [(Token::Ordinals(Ordinals::Sixteenth), Priority(3)), "sixteenth", Dist(2)] |
-    [(Token::Ordinals(Ordinals::Sixteenth), Priority(3)), "sixteen", Dist(1)]
-);
-define!(seventeenth:
-    [(Token::Ordinals(Ordinals::Seventeenth), Priority(3)), "seventeenth", Dist(2)] |
-    [(Token::Ordinals(Ordinals::Seventeenth), Priority(3)), "seventeen", Dist(2)]
-);
-define!(eighteenth:
-    [(Token::Ordinals(Ordinals::Eighteenth), Priority(3)), "eighteenth", Dist(2)] |
-    [(Token::Ordinals(Ordinals::Eighteenth), Priority(3)), "eighteen", Dist(1)]
-);
-define!(nineteenth:
-    [(Token::Ordinals(Ordinals::Nineteenth), Priority(3)), "nineteenth", Dist(2)] |
-    [(Token::Ordinals(Ordinals::Nineteenth), Priority(3)), "nineteen", Dist(1)]
-);
-
-combine!(ordinal => first | second | third | fourth | fifth | sixth | seventh | eighth | ninth |
-                    tenth | eleventh | twelfth | thirteenth | fourteenth | fifteenth |
-                    sixteenth | seventeenth | eighteenth | nineteenth | twentieth | thirtieth);
+define!(twentieth: (Token::Ordinals(Ordinals::Twentieth), Priority(2)), "twentieth", Dist(3));
+define!(twenty: (Token::Ordinals(Ordinals::Twentieth), Priority(2)), "twenty", Dist(2));
+define!(thirtieth: (Token::Ordinals(Ordinals::Thirtieth), Priority(2)), "thirtieth", Dist(3));
+define!(thirty: (Token::Ordinals(Ordinals::Thirtieth), Priority(2)), "thirty", Dist(2));
+
+combine!(tens => twentieth | twenty | thirtieth | thirty);
+
+define!(first: (Token::Ordinals(Ordinals::First), Priority(3)), "first", Dist(1));
+define!(second: (Token::Ordinals(Ordinals::Second), Priority(3)), "second", Dist(1));
+define!(third: (Token::Ordinals(Ordinals::Third), Priority(3)), "third", Dist(1));
+define!(fourth: (Token::Ordinals(Ordinals::Fourth), Priority(3)), "fourth", Dist(1));
+define!(fifth: (Token::Ordinals(Ordinals::Fifth), Priority(3)), "fifth", Dist(1));
+define!(sixth: (Token::Ordinals(Ordinals::Sixth), Priority(3)), "sixth", Dist(1));
+define!(seventh: (Token::Ordinals(Ordinals::Seventh), Priority(3)), "seventh", Dist(1));
+define!(eighth: (Token::Ordinals(Ordinals::Eighth), Priority(3)), "eighth", Dist(1));
+define!(ninth: (Token::Ordinals(Ordinals::Ninth), Priority(3)), "ninth", Dist(1));
+define!(tenth: (Token::Ordinals(Ordinals::Tenth), Priority(3)), "tenth", Dist(1));
+define!(eleventh: (Token::Ordinals(Ordinals::Eleventh), Priority(3)), "eleventh", Dist(2));
+define!(twelfth: (Token::Ordinals(Ordinals::Twelfth), Priority(3)), "twelfth", Dist(1));
+define!(thirteenth: (Token::Ordinals(Ordinals::Thirteenth), Priority(3)), "thirteenth", Dist(2));
+define!(fourteenth: (Token::Ordinals(Ordinals::Fourteenth), Priority(3)), "fourteenth", Dist(2));
+define!(fifteenth: (Token::Ordinals(Ordinals::Fifteenth), Priority(3)), "fifteenth", Dist(2));
+define!(sixteenth: (Token::Ordinals(Ordinals::Sixteenth), Priority(3)), "sixteenth", Dist(2));
+define!(seventeenth: (Token::Ordinals(Ordinals::Seventeenth), Priority(3)), "seventeenth", Dist(2));
+define!(eighteenth: (Token::Ordinals(Ordinals::Eighteenth), Priority(3)), "eighteenth", Dist(2));
+define!(nineteenth: (Token::Ordinals(Ordinals::Nineteenth), Priority(3)), "nineteenth", Dist(2));
+
+define!(one: (Token::Ordinals(Ordinals::First), Priority(3)), "one", Dist(0));
+define!(two: (Token::Ordinals(Ordinals::Second), Priority(3)), "two", Dist(0));
+define!(three: (Token::Ordinals(Ordinals::Third), Priority(3)), "three", Dist(1));
+define!(four: (Token::Ordinals(Ordinals::Fourth), Priority(3)), "four", Dist(1));
+define!(five: (Token::Ordinals(Ordinals::Fifth), Priority(3)), "five", Dist(1));
+define!(six: (Token::Ordinals(Ordinals::Sixth), Priority(3)), "six", Dist(0));
+define!(seven: (Token::Ordinals(Ordinals::Seventh), Priority(3)), "seven", Dist(1));
+define!(eight: (Token::Ordinals(Ordinals::Eighth), Priority(3)), "eight", Dist(1));
+define!(nine: (Token::Ordinals(Ordinals::Ninth), Priority(3)), "nine", Dist(1));
+define!(ten: (Token::Ordinals(Ordinals::Tenth), Priority(3)), "ten", Dist(0));
+define!(eleven: (Token::Ordinals(Ordinals::Eleventh), Priority(3)), "eleven", Dist(1));
+define!(twelve: (Token::Ordinals(Ordinals::Twelfth), Priority(3)), "twelve", Dist(1));
+define!(thirteen: (Token::Ordinals(Ordinals::Thirteenth), Priority(3)), "thirteen", Dist(1));
+define!(fourteen: (Token::Ordinals(Ordinals::Fourteenth), Priority(3)), "fourteen", Dist(1));
+define!(fifteen: (Token::Ordinals(Ordinals::Fifteenth), Priority(3)), "fifteen", Dist(1));
+define!(sixteen: (Token::Ordinals(Ordinals::Sixteenth), Priority(3)), "sixteen", Dist(1));
+define!(seventeen: (Token::Ordinals(Ordinals::Seventeenth), Priority(3)), "seventeen", Dist(2));
+define!(eighteen: (Token::Ordinals(Ordinals::Eighteenth), Priority(3)), "eighteen", Dist(1));
+define!(nineteen: (Token::Ordinals(Ordinals::Nineteenth), Priority(3)), "nineteen", Dist(1));
+
+// ordinal words only, without cardinal aliases like "one" or "two"
+combine!(ordinal_word => first | second | third | fourth | fifth | sixth | seventh | eighth |
+                         ninth | tenth | eleventh | twelfth | thirteenth | fourteenth | fifteenth |
+                         sixteenth | seventeenth | eighteenth | nineteenth | twentieth | thirtieth);
+
+// ordinal words along with cardinal aliases, e.g. "third" or "three"
+combine!(ordinal => first | one | second | two | third | three | fourth | four | fifth | five |
+                    sixth | six | seventh | seven | eighth | eight | ninth | nine | tenth | ten |
+                    eleventh | eleven | twelfth | twelve | thirteenth | thirteen | fourteenth |
+                    fourteen | fifteenth | fifteen | sixteenth | sixteen | seventeenth | seventeen |
+                    eighteenth | eighteen | nineteenth | nineteen | twentieth | twenty | thirtieth |
+                    thirty);
 
 define!(of: (Token::Prepositions(Prepositions::Of), Priority(4)), "of", Dist(0));
 
@@ -187,63 +156,155 @@ define!(december:
 combine!(month => january | february | march | april | may | june | july | august | september |
                   october | november | december);
 
-named_args!(parse<'a>(exact_match: bool)<CompleteStr<'a>, (Vec<usize>,
-                             ( TokenDesc, TokenDesc, TokenDesc, TokenDesc ) )>,
+pub(crate) fn month_number(token: &Token) -> Option<i32> {
+    match token {
+        Token::Month(Month::January) => Some(1),
+        Token::Month(Month::February) => Some(2),
+        Token::Month(Month::March) => Some(3),
+        Token::Month(Month::April) => Some(4),
+        Token::Month(Month::May) => Some(5),
+        Token::Month(Month::June) => Some(6),
+        Token::Month(Month::July) => Some(7),
+        Token::Month(Month::August) => Some(8),
+        Token::Month(Month::September) => Some(9),
+        Token::Month(Month::October) => Some(10),
+        Token::Month(Month::November) => Some(11),
+        Token::Month(Month::December) => Some(12),
+        _ => None,
+    }
+}
 
-    many_till!(tokenize_count_symbols,
+// years around the present only, otherwise amounts like "a fine of 5000" would be years
+fn year(input: CompleteStr) -> MyResult {
+    let (tail, desc) = common::year(input, Priority(7))?;
+    match &desc.token {
+        PToken::PToken(Token::Year(1900..=2200), _) => Ok((tail, desc)),
+        _ => wrap_error(input, UNKNOWN),
+    }
+}
+
+define!(the: (Token::Articles(Articles::The), Priority(6)), "the", Dist(0));
+
+// a preposition before a year alone is a part of the match, e.g. "in 2021"
+define!(
+    year_prefix:
+    [(Token::When(When::In), Priority(8)), "in", Dist(0)] |
+    [(Token::Prepositions(Prepositions::Of), Priority(8)), "of", Dist(0)]
+);
+
+/// Recognizes a year alone with no word right after it, e.g. "2021" but not "2000 people".
+fn standalone_year(input: CompleteStr) -> MyResult {
+    let (tail, desc) = year(input)?;
+    if tail.trim_start().starts_with(char::is_alphanumeric) {
+        return wrap_error(input, UNKNOWN);
+    }
+    Ok((tail, desc))
+}
+
+/// Skips a word like tokenize_count_symbols, a year right after a word is skipped along with it,
+/// so that quantities like "room 2100" are not years, a preposition makes it a year though, e.g.
+/// "in 2021", as the rule matches before skipping the preposition.
+fn skip_word(input: CompleteStr) -> IResult<CompleteStr, usize> {
+    if let Ok((tail, word)) = tokenize_word(input) {
+        let rest = CompleteStr(tail.trim_start());
+        // a colon or a dot after the word ends the phrase, e.g. "plans: 2021"
+        if word.ends_with(char::is_alphabetic) && rest.starts_with(char::is_numeric) {
+            if let Ok((tail, _)) = year(rest) {
+                return Ok((tail, input.len() - tail.len()));
+            }
+        }
+    }
+    tokenize_count_symbols(input)
+}
+
+// optional "the" before a day, e.g. "the 3rd of march"
+named_args!(the_prefix<'a>(dist_limit: Dist)<CompleteStr<'a>, TokenDesc>,
+    alt!(apply!(the, dist_limit) | stub)
+);
+
+named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
+                             ( TokenDesc, TokenDesc, TokenDesc, TokenDesc, TokenDesc, TokenDesc ) )>,
+
+    many_till!(skip_word,
         alt!(
             // 31th of february, 1st of january
-            tuple!(apply!(numeric_ord, exact_match), apply!(of, exact_match), apply!(month, exact_match),
-                   stub) |
+            tuple!(apply!(the_prefix, dist_limit), apply!(numeric_ord, dist_limit),
+                   apply!(of, dist_limit), apply!(month, dist_limit), stub, alt!(year | stub)) |
 
             // 31th december, 1st january
-            tuple!(apply!(numeric_ord, exact_match), apply!(month, exact_match), stub, stub) |
+            tuple!(apply!(the_prefix, dist_limit), apply!(numeric_ord, dist_limit),
+                   apply!(month, dist_limit), stub, stub, alt!(year | stub)) |
             // february 21th, january 1st
-            tuple!(apply!(month, exact_match), apply!(numeric_ord, exact_match), stub, stub) |
+            tuple!(apply!(month, dist_limit), apply!(numeric_ord, dist_limit), stub, stub, stub,
+                   alt!(year | stub)) |
 
             // twentieth first of december (from 20 to 31 inclusive)
-            tuple!(apply!(tens, exact_match), apply!(ordinal, exact_match), apply!(of, exact_match),
-                   apply!(month, exact_match)) |
+            tuple!(apply!(the_prefix, dist_limit), apply!(tens, dist_limit),
+                   apply!(ordinal, dist_limit), apply!(of, dist_limit), apply!(month, dist_limit),
+                   alt!(year | stub)) |
             // eighteenth of december (from 1 to 19 inclusive)
-            tuple!(apply!(ordinal, exact_match), apply!(of, exact_match), apply!(month, exact_match),
-                   stub) |
+            tuple!(apply!(the_prefix, dist_limit), apply!(ordinal, dist_limit),
+                   apply!(of, dist_limit), apply!(month, dist_limit), stub, alt!(year | stub)) |
 
             // december of twentieth first (from 20 to 31 inclusive)
-            tuple!(apply!(month, exact_match), apply!(of, exact_match), apply!(tens, exact_match),
-                   apply!(ordinal, exact_match)) |
+            tuple!(apply!(month, dist_limit), apply!(of, dist_limit), apply!(tens, dist_limit),
+                   apply!(ordinal, dist_limit), stub, alt!(year | stub)) |
             // december of eighteenth (from 1 to 19 inclusive)
-            tuple!(apply!(month, exact_match), apply!(of, exact_match), apply!(ordinal, exact_match),
-                   stub) |
+            tuple!(apply!(month, dist_limit), apply!(of, dist_limit), apply!(ordinal, dist_limit),
+                   stub, stub, alt!(year | stub)) |
 
             // twentieth first december (from 20 to 31 inclusive)
-            tuple!(apply!(tens, exact_match), apply!(ordinal, exact_match), apply!(month, exact_match),
-                   stub) |
+            tuple!(apply!(the_prefix, dist_limit), apply!(tens, dist_limit),
+                   apply!(ordinal, dist_limit), apply!(month, dist_limit), stub,
+                   alt!(year | stub)) |
             // eighteenth december (from 1 to 19 inclusive)
-            tuple!(apply!(ordinal, exact_match), apply!(month, exact_match), stub, stub) |
+            tuple!(apply!(the_prefix, dist_limit), apply!(ordinal, dist_limit),
+                   apply!(month, dist_limit), stub, stub, alt!(year | stub)) |
 
             // december twentieth first (from 20 to 31 inclusive)
-            tuple!(apply!(month, exact_match), apply!(tens, exact_match), apply!(ordinal, exact_match),
-                   stub) |
+            tuple!(apply!(month, dist_limit), apply!(tens, dist_limit), apply!(ordinal, dist_limit),
+                   stub, stub, alt!(year | stub)) |
             // december eighteenth  (from 1 to 19 inclusive)
-            tuple!(apply!(month, exact_match), apply!(ordinal, exact_match), stub, stub) |
+            tuple!(apply!(month, dist_limit), apply!(ordinal, dist_limit), stub, stub, stub,
+                   alt!(year | stub)) |
+
+            // march 2020, goes before "march 4" for the year not to be taken for a day
+            tuple!(apply!(month, dist_limit), year, stub, stub, stub, stub) |
 
             // 4 march
-            tuple!(day_num, apply!(month, exact_match), stub, stub) |
+            tuple!(apply!(the_prefix, dist_limit), day_num, apply!(month, dist_limit), stub, stub,
+                   alt!(year | stub)) |
             // march 4
-            tuple!(apply!(month, exact_match), day_num, stub, stub) |
+            tuple!(apply!(month, dist_limit), day_num, stub, stub, stub, alt!(year | stub)) |
+
+            // january, december
+            tuple!(apply!(month, dist_limit), stub, stub, stub, stub, stub) |
+
+            // the 3rd, the 21st
+            tuple!(apply!(the, dist_limit), apply!(numeric_ord, dist_limit), stub, stub, stub,
+                   stub) |
+            // the twenty first
+            tuple!(apply!(the, dist_limit), apply!(tens, dist_limit),
+                   apply!(ordinal_word, dist_limit), stub, stub, stub) |
+            // the third, the twentieth
+            tuple!(apply!(the, dist_limit), apply!(ordinal_word, dist_limit), stub, stub, stub,
+                   stub) |
 
-            // january, december 
-            tuple!(apply!(month, exact_match), stub, stub, stub)
+            // a year with a preposition, e.g. in 2021, the summer of 2021
+            tuple!(apply!(year_prefix, dist_limit), year, stub, stub, stub, stub) |
+            // a year alone, e.g. 2021
+            tuple!(standalone_year, stub, stub, stub, stub, stub)
         )
     )
 );
 
-make_interpreter!(positions = 4);
+make_interpreter!(positions = 6);
 
 fn make_time<'a, 'b, Tz: TimeZone>(
     res: &'a RuleResult,
     tz_aware: DateTime<Tz>,
     input: &'b str,
+    _config: &ParseConfig,
 ) -> Result<Context, SemanticError<'b>> {
     let mut ctx = Context::default();
 
@@ -278,7 +339,7 @@ fn make_time<'a, 'b, Tz: TimeZone>(
     if let Some(ones) = match_ordinal(res.token_by_priority(Priority(3))) as Option<i32> {
         if tens.is_some() {
             if ones >= 10 {
-                return Err(invalid_time_error(input, "day", ones + tens.unwrap()));
+                return Err(invalid_date_error(input, "day", ones + tens.unwrap()));
             }
             // for numbers less than 10 - sum tens and ones
             day = Some(ones + tens.unwrap())
@@ -287,48 +348,51 @@ fn make_time<'a, 'b, Tz: TimeZone>(
         }
     }
 
+    let year_token = res.token_by_priority(Priority(7));
+    let month_token = res.token_by_priority(Priority(5));
+
+    // a year alone, e.g. "2021", changes nothing but the year
+    if let (None, None, Some(Token::Year(year))) = (day, &month_token, &year_token) {
+        ctx.year = Some(*year as i32);
+        return Ok(ctx);
+    }
+
     // if day is omitted, assume it is 1st day of a month
     let day = day.unwrap_or(1);
     if day <= 0 {
-        return Err(invalid_time_error(input, "day", day));
+        return Err(invalid_date_error(input, "day", day));
     }
 
     ctx.day = Some(day);
 
-    let mut month = 1;
-
-    let token = res.token_by_priority(Priority(5));
-    if token.is_some() {
-        month = match token.unwrap() {
-            Token::Month(Month::January) => 1,
-            Token::Month(Month::February) => 2,
-            Token::Month(Month::March) => 3,
-            Token::Month(Month::April) => 4,
-            Token::Month(Month::May) => 5,
-            Token::Month(Month::June) => 6,
-            Token::Month(Month::July) => 7,
-            Token::Month(Month::August) => 8,
-            Token::Month(Month::September) => 9,
-            Token::Month(Month::October) => 10,
-            Token::Month(Month::November) => 11,
-            Token::Month(Month::December) => 12,
-            _ => 1,
+    let mut year = tz_aware.year();
+    let mut month = tz_aware.month() as i32;
+
+    if let Some(token) = month_token {
+        month = month_number(&token).unwrap_or(1);
+    } else if day < tz_aware.day() as i32 {
+        // no month given, e.g. "the 3rd", take the nearest upcoming day with such number
+        if month == 12 {
+            month = 1;
+            year += 1;
+            ctx.year = Some(year);
+        } else {
+            month += 1;
         }
-    };
+    }
 
-    if month < 1 || month > 12 {
-        return Err(invalid_time_error(input, "month", month));
+    // explicit year, e.g. "march 2020"
+    if let Some(Token::Year(explicit)) = year_token {
+        year = explicit as i32;
+        ctx.year = Some(year);
     }
 
-    // 29 days in february for leap years
-    let days_in_month = if month == 2 && is_leap_year(tz_aware.year()) {
-        29
-    } else {
-        DAYS_IN_MONTH[month as usize - 1]
-    };
+    if month < 1 || month > 12 {
+        return Err(invalid_date_error(input, "month", month));
+    }
 
-    if day > days_in_month {
-        return Err(invalid_time_error(input, "day", day));
+    if day > days_in_month(year, month as u32) as i32 {
+        return Err(invalid_date_error(input, "day", day));
     }
 
     ctx.month = Some(month);
@@ -339,7 +403,9 @@ fn make_time<'a, 'b, Tz: TimeZone>(
 #[cfg(test)]
 mod tests {
     use super::interpret;
-    use crate::rules::errors::invalid_time_error;
+    use crate::rules::config::ParseConfig;
+    use crate::rules::errors::invalid_date_error;
+    use crate::rules::rules::MatchBounds;
     use chrono::prelude::*;
 
     fn fixed_time() -> DateTime<Local> {
@@ -348,54 +414,221 @@ mod tests {
 
     #[test]
     fn test_exact_month() {
-        let result = interpret("3rd march", false, fixed_time()).unwrap();
+        let result = interpret("3rd march", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.get_day(), 3);
         assert_eq!(result.get_month(), 3);
 
-        let result = interpret("3rd of march", false, fixed_time()).unwrap();
+        let result = interpret("3rd of march", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.get_day(), 3);
         assert_eq!(result.get_month(), 3);
 
-        let result = interpret("3 march", false, fixed_time()).unwrap();
+        let result = interpret("3 march", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.get_day(), 3);
         assert_eq!(result.get_month(), 3);
 
-        let result = interpret("twenty seventh of april", false, fixed_time()).unwrap();
+        let result = interpret(
+            "twenty seventh of april",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
         assert_eq!(result.get_day(), 27);
         assert_eq!(result.get_month(), 4);
 
-        let result = interpret("thirtieth of december", false, fixed_time()).unwrap();
+        let result = interpret(
+            "thirtieth of december",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
         assert_eq!(result.get_day(), 30);
         assert_eq!(result.get_month(), 12);
 
-        let result = interpret("december", false, fixed_time()).unwrap();
+        let result = interpret("december", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.get_month(), 12);
 
-        let result = interpret("twenty fourteen of april", false, fixed_time());
+        let result = interpret(
+            "twenty fourteen of april",
+            &ParseConfig::default(),
+            fixed_time(),
+        );
         assert_eq!(
             result.unwrap_err().extract_error(),
-            invalid_time_error("twenty fourteen of april", "day", 34).extract_error()
+            invalid_date_error("twenty fourteen of april", "day", 34).extract_error()
         );
 
-        let result = interpret("-3 march", false, fixed_time());
+        let result = interpret("-3 march", &ParseConfig::default(), fixed_time());
         assert_eq!(
             result.unwrap_err().extract_error(),
-            invalid_time_error("-3 march", "day", -3).extract_error()
+            invalid_date_error("-3 march", "day", -3).extract_error()
         );
 
-        let result = interpret("thirteen of february", false, fixed_time()).unwrap();
+        let result = interpret(
+            "thirteen of february",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
         assert_eq!(result.get_day(), 13);
         assert_eq!(result.get_month(), 2);
 
-        let result = interpret("31st february", false, fixed_time());
+        let result = interpret("31st february", &ParseConfig::default(), fixed_time());
         assert_eq!(
             result.unwrap_err().extract_error(),
-            invalid_time_error("31st february", "day", 31).extract_error()
+            invalid_date_error("31st february", "day", 31).extract_error()
         );
 
-        let result = interpret("feb. 4", false, fixed_time()).unwrap();
+        let result = interpret("feb. 4", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.get_day(), 4);
         assert_eq!(result.get_month(), 2);
     }
 
+    #[test]
+    fn test_ordinal_day() {
+        let result = interpret("on the 21st", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(3, 11)));
+        assert_eq!(result.get_day(), 21);
+        assert_eq!(result.get_month(), 1);
+
+        let result = interpret("the third", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_day(), 3);
+        assert_eq!(result.get_month(), 1);
+
+        let result = interpret("the twenty first", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_day(), 21);
+
+        let result = interpret("the 3rd of march", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 16)));
+        assert_eq!(result.get_day(), 3);
+        assert_eq!(result.get_month(), 3);
+
+        // cardinal numbers are not ordinals
+        let result = interpret("the one", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, None);
+
+        // day has already passed, so it's the next month
+        let result = interpret(
+            "the 3rd",
+            &ParseConfig::default(),
+            Local.ymd(2019, 1, 15).and_hms(0, 0, 0),
+        )
+        .unwrap();
+        assert_eq!(result.get_day(), 3);
+        assert_eq!(result.get_month(), 2);
+        assert_eq!(result.get_year(), 0);
+
+        let result = interpret(
+            "the 5th",
+            &ParseConfig::default(),
+            Local.ymd(2019, 12, 20).and_hms(0, 0, 0),
+        )
+        .unwrap();
+        assert_eq!(result.get_day(), 5);
+        assert_eq!(result.get_month(), 1);
+        assert_eq!(result.get_year(), 2020);
+
+        let result = interpret(
+            "the 30th",
+            &ParseConfig::default(),
+            Local.ymd(2019, 1, 31).and_hms(0, 0, 0),
+        );
+        assert_eq!(
+            result.unwrap_err().extract_error(),
+            invalid_date_error("the 30th", "day", 30).extract_error()
+        );
+    }
+
+    #[test]
+    fn test_year() {
+        let result = interpret("march 2020", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 10)));
+        assert_eq!(result.get_day(), 1);
+        assert_eq!(result.get_month(), 3);
+        assert_eq!(result.get_year(), 2020);
+
+        let result = interpret(
+            "the 29th of february 2020",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
+        assert_eq!(result.get_day(), 29);
+        assert_eq!(result.get_month(), 2);
+        assert_eq!(result.get_year(), 2020);
+
+        let result = interpret("march 4 2021", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 12)));
+        assert_eq!(result.get_day(), 4);
+        assert_eq!(result.get_year(), 2021);
+
+        let result = interpret("in 2021", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 7)));
+        assert_eq!(result.get_year(), 2021);
+        assert_eq!(result.get_month(), 0);
+
+        // the day depends on the year
+        let result = interpret("29 february 2021", &ParseConfig::default(), fixed_time());
+        assert_eq!(
+            result.unwrap_err().extract_error(),
+            invalid_date_error("29 february 2021", "day", 29).extract_error()
+        );
+
+        let result = interpret("the summer of 2021", &ParseConfig::default(), fixed_time());
+        assert_eq!(result.unwrap().bounds, Some(MatchBounds::new(11, 18)));
+
+        // numbers out of the range are not years
+        let result = interpret("in 1000", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, None);
+
+        // a year may stand alone
+        let result = interpret("2021", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 4)));
+        assert_eq!(result.get_year(), 2021);
+        assert_eq!(result.get_month(), 0);
+
+        let result = interpret("plans: 2021.", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(7, 11)));
+
+        // but quantities are not years
+        for input in &["we had 2000 people", "room 2100", "1950 meters"] {
+            let result = interpret(input, &ParseConfig::default(), fixed_time()).unwrap();
+            assert_eq!(result.bounds, None, "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_full_date() {
+        let result = interpret("3rd of may 2020", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 15)));
+        assert_eq!(result.get_day(), 3);
+        assert_eq!(result.get_month(), 5);
+        assert_eq!(result.get_year(), 2020);
+
+        let result = interpret(
+            "the 21st of december",
+            &ParseConfig::default(),
+            fixed_time(),
+        );
+        let result = result.unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 20)));
+        assert_eq!(result.get_day(), 21);
+        assert_eq!(result.get_month(), 12);
+        assert_eq!(result.get_year(), 0);
+
+        // a comma before the year is skipped like any other punctuation
+        let result = interpret("may 3rd, 2020", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 13)));
+        assert_eq!(result.get_day(), 3);
+        assert_eq!(result.get_month(), 5);
+        assert_eq!(result.get_year(), 2020);
+
+        let result = interpret("3rd of may, 2020", &ParseConfig::default(), fixed_time());
+        assert_eq!(result.unwrap().get_year(), 2020);
+
+        let result = interpret("31st of february", &ParseConfig::default(), fixed_time());
+        assert_eq!(
+            result.unwrap_err().extract_error(),
+            invalid_date_error("31st of february", "day", 31).extract_error()
+        );
+    }
 }
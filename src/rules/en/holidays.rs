@@ -0,0 +1,188 @@
+use super::combinators::{stub, tokenize_count_symbols, Dist};
+use super::config::ParseConfig;
+use super::errors::SemanticError;
+use super::rules::{Context, RuleResult, TokenDesc};
+use super::tokens::{Holiday, Priority, TimeInterval, Token};
+use chrono::prelude::*;
+
+use nom::{alt, apply, call, many_till, named_args, tuple, types::CompleteStr};
+
+// "easter" is exact only, otherwise "eastern" in "eastern time" would be a holiday
+define!(
+    holiday:
+    [(Token::Holiday(Holiday::Christmas), Priority(0)), "christmas", Dist(2)] |
+    [(Token::Holiday(Holiday::Christmas), Priority(0)), "xmas", Dist(0)] |
+    [(Token::Holiday(Holiday::Halloween), Priority(0)), "halloween", Dist(2)] |
+    [(Token::Holiday(Holiday::Easter), Priority(0)), "easter", Dist(0)]
+);
+
+define!(new: (Token::Holiday(Holiday::NewYear), Priority(0)), "new", Dist(0));
+define!(
+    year:
+    [(Token::TimeInterval(TimeInterval::Year), Priority(1)), "year", Dist(1)] |
+    [(Token::TimeInterval(TimeInterval::Year), Priority(1)), "years", Dist(1)]
+);
+
+define!(day: (Token::TimeInterval(TimeInterval::Day), Priority(2)), "day", Dist(0));
+
+named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
+                             ( TokenDesc, TokenDesc, TokenDesc ) )>,
+    many_till!(tokenize_count_symbols,
+        alt!(
+            // e.g. "new year", "new year's day"
+            tuple!(apply!(new, dist_limit), apply!(year, dist_limit),
+                   alt!(apply!(day, dist_limit) | stub)) |
+            // e.g. "christmas", "christmas day", "easter"
+            tuple!(apply!(holiday, dist_limit), stub, alt!(apply!(day, dist_limit) | stub))
+        )
+    )
+);
+
+make_interpreter!(positions = 3);
+
+/// Date of easter sunday in the Gregorian calendar as (month, day), computed by the anonymous
+/// Gregorian algorithm, also known as the Meeus/Jones/Butcher algorithm.
+fn easter(year: i32) -> (u32, u32) {
+    let a = year.rem_euclid(19);
+    let b = year.div_euclid(100);
+    let c = year.rem_euclid(100);
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    (month as u32, day as u32)
+}
+
+fn holiday_date(holiday: &Holiday, year: i32) -> (u32, u32) {
+    match holiday {
+        Holiday::NewYear => (1, 1),
+        Holiday::Easter => easter(year),
+        Holiday::Halloween => (10, 31),
+        Holiday::Christmas => (12, 25),
+    }
+}
+
+/// A holiday is the nearest upcoming one, today included, so "christmas" on december 26th is the
+/// next year's christmas.
+fn make_time<'a, 'b, Tz: TimeZone>(
+    res: &'a RuleResult,
+    tz_aware: DateTime<Tz>,
+    _input: &'b str,
+    _config: &ParseConfig,
+) -> Result<Context, SemanticError<'b>> {
+    let mut ctx = Context::default();
+
+    let holiday = match res.token_by_priority(Priority(0)) {
+        Some(Token::Holiday(holiday)) => holiday,
+        _ => unreachable!(),
+    };
+
+    let mut year = tz_aware.year();
+    let (mut month, mut day) = holiday_date(&holiday, year);
+    if (month, day) < (tz_aware.month(), tz_aware.day()) {
+        year += 1;
+        ctx.year = Some(year);
+        // easter moves from year to year
+        let (next_month, next_day) = holiday_date(&holiday, year);
+        month = next_month;
+        day = next_day;
+    }
+
+    ctx.month = Some(month as i32);
+    ctx.day = Some(day as i32);
+
+    Ok(ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{easter, interpret};
+    use crate::rules::config::ParseConfig;
+    use crate::rules::rules::MatchBounds;
+    use chrono::prelude::*;
+
+    fn fixed_time() -> DateTime<Local> {
+        Local.ymd(2019, 11, 1).and_hms(12, 0, 0)
+    }
+
+    #[test]
+    fn test_fixed_holidays() {
+        let result = interpret(
+            "send cards before christmas",
+            &ParseConfig::default(),
+            fixed_time(),
+        );
+        let result = result.unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(18, 27)));
+        assert_eq!(result.get_year(), 0);
+        assert_eq!(result.get_month(), 12);
+        assert_eq!(result.get_day(), 25);
+
+        let result = interpret("xmas day", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 8)));
+        assert_eq!(result.get_day(), 25);
+
+        // this year's halloween has passed
+        let result = interpret("halloween", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_year(), 2020);
+        assert_eq!(result.get_month(), 10);
+        assert_eq!(result.get_day(), 31);
+
+        let result = interpret("new year's day", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 14)));
+        assert_eq!(result.get_year(), 2020);
+        assert_eq!(result.get_month(), 1);
+        assert_eq!(result.get_day(), 1);
+
+        // the possessive is a form of "year" even if typos are not tolerated
+        let exact = ParseConfig {
+            exact_match: true,
+            ..Default::default()
+        };
+        for input in &["new year's day", "new year\u{2019}s day"] {
+            let result = interpret(input, &exact, fixed_time()).unwrap();
+            assert_eq!(result.bounds, Some(MatchBounds::new(0, input.len())));
+            assert_eq!(result.get_day(), 1);
+        }
+
+        let result = interpret("happy new year!", &ParseConfig::default(), fixed_time());
+        assert_eq!(result.unwrap().bounds, Some(MatchBounds::new(6, 14)));
+
+        // today's holiday is not over yet
+        let today = Local.ymd(2019, 12, 25).and_hms(12, 0, 0);
+        let result = interpret("christmas", &ParseConfig::default(), today).unwrap();
+        assert_eq!(result.get_year(), 0);
+        assert_eq!(result.get_day(), 25);
+    }
+
+    #[test]
+    fn test_easter() {
+        assert_eq!(easter(2019), (4, 21));
+        assert_eq!(easter(2024), (3, 31));
+        assert_eq!(easter(2025), (4, 20));
+        assert_eq!(easter(2038), (4, 25));
+
+        // easter of the next year has another date
+        let result = interpret("easter", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_year(), 2020);
+        assert_eq!(result.get_month(), 4);
+        assert_eq!(result.get_day(), 12);
+
+        let before = Local.ymd(2024, 3, 1).and_hms(12, 0, 0);
+        let result = interpret("easter sunday", &ParseConfig::default(), before).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 6)));
+        assert_eq!(result.get_month(), 3);
+        assert_eq!(result.get_day(), 31);
+
+        // no holiday in "eastern"
+        let result = interpret("9am eastern", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, None);
+    }
+}
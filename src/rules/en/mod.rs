@@ -1,37 +1,244 @@
-mod casual_date_time;
+mod adjustment;
+mod business;
+pub(crate) mod casual_date_time;
 mod deadline;
 mod exact_month_date;
+mod holidays;
 mod past_time;
+mod period_part;
+mod quarter;
+mod recurrence;
+mod seasons;
 mod time;
-mod weekdays;
+mod week_number;
+mod weekday_count;
+mod weekday_list;
+mod weekday_of_month;
+pub(crate) mod weekdays;
+mod weekend;
 
 use crate::rules::combinators;
 use crate::rules::common;
 use crate::rules::common_matchers;
+use crate::rules::config;
+use crate::rules::config::{ParseConfig, RuleKind};
 use crate::rules::consts;
 use crate::rules::errors;
 use crate::rules::rules;
+use crate::rules::stats::{self, ParseStats};
 use crate::rules::tokens;
+#[cfg(feature = "std-time")]
+use chrono::offset::{FixedOffset, Utc};
 use chrono::{offset::TimeZone, DateTime};
 
+pub(crate) fn rules<Tz: TimeZone>() -> Vec<(RuleKind, rules::FnRule<Tz>)> {
+    vec![
+        (RuleKind::Weekdays, weekdays::interpret::<Tz>),
+        (RuleKind::Time, time::interpret::<Tz>),
+        (RuleKind::PastTime, past_time::interpret::<Tz>),
+        (RuleKind::ExactMonthDate, exact_month_date::interpret::<Tz>),
+        (RuleKind::Deadline, deadline::interpret::<Tz>),
+        (RuleKind::CasualDateTime, casual_date_time::interpret::<Tz>),
+        (RuleKind::Recurrence, recurrence::interpret::<Tz>),
+        (RuleKind::PeriodPart, period_part::interpret::<Tz>),
+        (RuleKind::WeekNumber, week_number::interpret::<Tz>),
+        (RuleKind::Quarter, quarter::interpret::<Tz>),
+        (RuleKind::WeekdayCount, weekday_count::interpret::<Tz>),
+        (RuleKind::Business, business::interpret::<Tz>),
+        (RuleKind::Weekend, weekend::interpret::<Tz>),
+        (RuleKind::WeekdayList, weekday_list::interpret::<Tz>),
+        (RuleKind::WeekdayOfMonth, weekday_of_month::interpret::<Tz>),
+        (RuleKind::Holidays, holidays::interpret::<Tz>),
+        (RuleKind::Seasons, seasons::interpret::<Tz>),
+        (RuleKind::SlashDmy, common::slash_dmy::interpret::<Tz>),
+        (RuleKind::IsoDate, common::iso_date::interpret::<Tz>),
+        (RuleKind::Duration, common::duration::interpret::<Tz>),
+    ]
+}
+
+/// Words around matches of the English rules, see combinators::Modifiers.
+pub(crate) const MODIFIERS: combinators::Modifiers = combinators::Modifiers {
+    approximation: &["around", "about", "approximately"],
+    negation: &["not", "except", "no"],
+    adjustment: Some(adjustment::adjustment),
+};
+
 pub fn parse<'a, Tz: TimeZone + 'a>(
     tz_aware: DateTime<Tz>,
     input: &'a str,
-    exact_match: bool,
+    config: &ParseConfig,
 ) -> Vec<Result<rules::MatchResult, errors::DateTimeError>> {
-    let input_lowered = input.to_lowercase();
-    combinators::apply_generic(
+    // every rule needs a word or a number, e.g. "" or ",,," has nothing to match
+    if !input.chars().any(char::is_alphanumeric) {
+        return Vec::new();
+    }
+    let input_prepared = config.prepare_input(input);
+    let matches = combinators::apply_generic(
         tz_aware,
-        &input_lowered,
-        &[
-            weekdays::interpret::<Tz>,
-            time::interpret::<Tz>,
-            past_time::interpret::<Tz>,
-            exact_month_date::interpret::<Tz>,
-            deadline::interpret::<Tz>,
-            casual_date_time::interpret::<Tz>,
-            common::slash_dmy::interpret::<Tz>,
-        ],
-        exact_match,
+        &input_prepared.text,
+        &rules::<Tz>(),
+        &MODIFIERS,
+        config,
+    );
+    input_prepared.restore_all(matches)
+}
+
+/// Same as `parse`, also counts rule attempts, matches and edit distance computations, e.g. to
+/// see how fuzzy matching performs on long inputs.
+pub fn parse_with_stats<'a, Tz: TimeZone + 'a>(
+    tz_aware: DateTime<Tz>,
+    input: &'a str,
+    config: &ParseConfig,
+) -> (
+    Vec<Result<rules::MatchResult, errors::DateTimeError>>,
+    ParseStats,
+) {
+    stats::collect(|| parse(tz_aware, input, config))
+}
+
+/// Lazy version of `parse`, matches are found one by one from left to right, so it is possible
+/// to stop after the first one. Unlike `parse` it doesn't report intersections of matches, the
+/// leftmost (and then the longest) match wins and the search continues right after it.
+pub fn parse_iter<'a, Tz: TimeZone + 'a>(
+    tz_aware: DateTime<Tz>,
+    input: &'a str,
+    config: &ParseConfig,
+) -> impl Iterator<Item = Result<rules::MatchResult, errors::DateTimeError>> + 'a {
+    let input_prepared = config.prepare_input(input);
+    combinators::MatchIter::new(
+        tz_aware,
+        input_prepared.text.to_string(),
+        &rules::<Tz>(),
+        &MODIFIERS,
+        config,
+    )
+    .map(move |item| item.map(|match_result| input_prepared.restore_bounds(match_result)))
+}
+
+/// Same as `parse` but with typos recognition disabled: every word of the input is compared
+/// to the rule patterns using plain string equality.
+pub fn parse_exact<'a, Tz: TimeZone + 'a>(
+    tz_aware: DateTime<Tz>,
+    input: &'a str,
+) -> Vec<Result<rules::MatchResult, errors::DateTimeError>> {
+    parse(
+        tz_aware,
+        input,
+        &ParseConfig {
+            exact_match: true,
+            ..Default::default()
+        },
+    )
+}
+
+/// Parses the input relative to the current time in the given timezone and returns absolute
+/// date/time for each match, use `Parser` to merge adjacent matches together.
+#[cfg(feature = "std-time")]
+pub fn parse_to_datetimes<Tz: TimeZone>(
+    tz: Tz,
+    input: &str,
+    exact_match: bool,
+) -> Vec<Result<DateTime<Tz>, errors::DateTimeError>> {
+    let now = tz.from_utc_datetime(&Utc::now().naive_utc());
+    parse_at(tz, input, exact_match, now)
+}
+
+/// Same as `parse_to_datetimes` but relative to the given time instead of the current one, e.g.
+/// for reproducible results or to parse historical text.
+pub fn parse_at<Tz: TimeZone>(
+    tz: Tz,
+    input: &str,
+    exact_match: bool,
+    now: DateTime<Tz>,
+) -> Vec<Result<DateTime<Tz>, errors::DateTimeError>> {
+    let now = now.with_timezone(&tz);
+    parse(
+        now.clone(),
+        input,
+        &ParseConfig {
+            exact_match,
+            ..Default::default()
+        },
     )
+    .into_iter()
+    .map(|item| item.and_then(|match_result| match_result.resolve(now.clone())))
+    .collect()
+}
+
+/// Same as `parse_to_datetimes` in UTC, e.g. for servers which don't depend on the local timezone.
+#[cfg(feature = "std-time")]
+pub fn parse_utc(
+    input: &str,
+    exact_match: bool,
+) -> Vec<Result<DateTime<Utc>, errors::DateTimeError>> {
+    parse_to_datetimes(Utc, input, exact_match)
+}
+
+/// Same as `parse_to_datetimes` in a timezone given by its offset from UTC in seconds, e.g. 3600
+/// for UTC+1. An offset of a day or more is reported as the only error.
+#[cfg(feature = "std-time")]
+pub fn parse_with_offset(
+    offset_seconds: i32,
+    input: &str,
+    exact_match: bool,
+) -> Vec<Result<DateTime<FixedOffset>, errors::DateTimeError>> {
+    match FixedOffset::east_opt(offset_seconds) {
+        Some(offset) => parse_to_datetimes(offset, input, exact_match),
+        None => vec![Err(errors::out_of_range_error(
+            "utc offset",
+            i64::from(offset_seconds),
+        ))],
+    }
+}
+
+/// Splits the whole input into matches and the text between them, e.g. to highlight dates.
+/// Segments follow each other without gaps or overlaps, text of failed matches is unmatched.
+#[cfg(feature = "std-time")]
+pub fn parse_all<Tz: TimeZone>(tz: Tz, input: &str, exact_match: bool) -> Vec<rules::Segment> {
+    let now = tz.from_utc_datetime(&Utc::now().naive_utc());
+    parse_all_at(tz, input, exact_match, now)
+}
+
+/// Same as `parse_all` but relative to the given time instead of the current one.
+pub fn parse_all_at<Tz: TimeZone>(
+    tz: Tz,
+    input: &str,
+    exact_match: bool,
+    now: DateTime<Tz>,
+) -> Vec<rules::Segment> {
+    let matches = parse(
+        now.with_timezone(&tz),
+        input,
+        &ParseConfig {
+            exact_match,
+            ..Default::default()
+        },
+    );
+
+    let chars: Vec<char> = input.chars().collect();
+    let mut segments = Vec::new();
+    let mut end_of_last_match_idx = 0;
+
+    let push_unmatched = |segments: &mut Vec<rules::Segment>, start: usize, end: usize| {
+        if start < end {
+            segments.push(rules::Segment::Unmatched {
+                start,
+                end,
+                text: chars[start..end].iter().collect(),
+            });
+        }
+    };
+
+    for match_result in matches.into_iter().filter_map(Result::ok) {
+        push_unmatched(
+            &mut segments,
+            end_of_last_match_idx,
+            match_result.get_start_idx(),
+        );
+        end_of_last_match_idx = match_result.get_end_idx();
+        segments.push(rules::Segment::Matched(match_result));
+    }
+    push_unmatched(&mut segments, end_of_last_match_idx, chars.len());
+
+    segments
 }
@@ -1,5 +1,6 @@
 use super::combinators::{stub, tokenize_count_symbols, Dist};
 use super::common_matchers::match_num;
+use super::config::ParseConfig;
 use super::consts;
 use super::errors::{invalid_time_error, SemanticError};
 use super::rules::{Context, RuleResult, TokenDesc};
@@ -51,24 +52,24 @@ combine!(time_interval => seconds | minutes | hours | days | weeks | months | ye
 
 define!(ago: (Token::Ago, Priority(2)), "ago", Dist(0));
 
-named_args!(parse<'a>(exact_match: bool)<CompleteStr<'a>, (Vec<usize>,
+named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
                              ( TokenDesc, TokenDesc, TokenDesc, TokenDesc ) )>,
     many_till!(tokenize_count_symbols,
         alt!(
             // half an hour ago
-            tuple!(apply!(a_few_half, exact_match), apply!(article, true), apply!(time_interval, exact_match),
-                   apply!(ago, exact_match)) |
+            tuple!(apply!(a_few_half, dist_limit), apply!(article, Dist(0)), apply!(time_interval, dist_limit),
+                   apply!(ago, dist_limit)) |
             // few hours ago, a minute ago, half month ago
-            tuple!(apply!(a_few_half, exact_match), apply!(time_interval, exact_match),
-                   apply!(ago, true), stub) |
+            tuple!(apply!(a_few_half, dist_limit), apply!(time_interval, dist_limit),
+                   apply!(ago, Dist(0)), stub) |
             // two hours ago, one minute ago, five month ago
-            tuple!(apply!(int_word, exact_match), apply!(time_interval, exact_match),
-                   apply!(ago, true), stub) |
+            tuple!(apply!(int_word, dist_limit), apply!(time_interval, dist_limit),
+                   apply!(ago, Dist(0)), stub) |
             // 2 hours ago, 1 minute ago, 5 month ago
-            tuple!(number, apply!(time_interval, exact_match), apply!(ago, true), stub) |
+            tuple!(number, apply!(time_interval, dist_limit), apply!(ago, Dist(0)), stub) |
             // a month ago, an year ago
-            tuple!(apply!(a_few_half, exact_match), apply!(time_interval, exact_match),
-                   apply!(ago, true), stub)
+            tuple!(apply!(a_few_half, dist_limit), apply!(time_interval, dist_limit),
+                   apply!(ago, Dist(0)), stub)
         )
     )
 );
@@ -77,8 +78,9 @@ make_interpreter!(positions = 4);
 
 fn make_time<'a, 'b, Tz: TimeZone>(
     res: &'a RuleResult,
-    tz_aware: DateTime<Tz>,
+    _tz_aware: DateTime<Tz>,
     input: &'b str,
+    config: &ParseConfig,
 ) -> Result<Context, SemanticError<'b>> {
     let mut ctx = Context::default();
     let mut num = 0;
@@ -94,7 +96,7 @@ fn make_time<'a, 'b, Tz: TimeZone>(
                     num = 1;
                 }
                 Token::Adverbs(Adverbs::Few) => {
-                    num = 3;
+                    num = config.few_count as i32;
                 }
                 Token::Adverbs(Adverbs::Half) => {
                     half = true;
@@ -149,14 +151,14 @@ fn make_time<'a, 'b, Tz: TimeZone>(
                 if half {
                     ctx.set_duration(-14 * consts::DAY);
                 } else {
-                    ctx.month = Some(tz_aware.month() as i32 - num);
+                    ctx.months = -num;
                 }
             }
             Token::TimeInterval(TimeInterval::Year) => {
                 if half {
-                    ctx.month = Some(tz_aware.month() as i32 - 6);
+                    ctx.months = -6;
                 } else {
-                    ctx.year = Some(tz_aware.year() - num);
+                    ctx.years = -num;
                 }
             }
             _ => unreachable!(),
@@ -169,8 +171,10 @@ fn make_time<'a, 'b, Tz: TimeZone>(
 #[cfg(test)]
 mod tests {
     use super::interpret;
+    use crate::rules::config::ParseConfig;
     use crate::rules::consts;
     use crate::rules::errors::invalid_time_error;
+    use crate::rules::rules::MatchBounds;
     use chrono::prelude::*;
 
     fn fixed_time() -> DateTime<Local> {
@@ -180,43 +184,90 @@ mod tests {
 
     #[test]
     fn test_past_time() {
-        let result = interpret("half an hour ago", false, fixed_time()).unwrap();
+        let result = interpret("half an hour ago", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.get_duration_sec() as i32, -30 * consts::MINUTE);
 
-        let result = interpret("2 hour ago", false, fixed_time()).unwrap();
+        let result = interpret("2 hour ago", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.get_duration_sec() as i32, -2 * consts::HOUR);
 
-        let result = interpret("5 minuts ago", false, fixed_time()).unwrap();
+        let result = interpret("5 minuts ago", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.get_duration_sec() as i32, -5 * consts::MINUTE);
 
-        let result = interpret("5 mnte ago I went to the zoo", false, fixed_time()).unwrap();
+        let result = interpret(
+            "5 mnte ago I went to the zoo",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
         assert_eq!(result.get_duration_sec() as i32, -5 * consts::MINUTE);
 
-        let result = interpret("-5 mnte ago I went to the zoo", false, fixed_time());
+        let result = interpret(
+            "-5 mnte ago I went to the zoo",
+            &ParseConfig::default(),
+            fixed_time(),
+        );
         assert_eq!(
             result.unwrap_err().extract_error(),
             invalid_time_error("-5 mnte ago", "number", -5).extract_error()
         );
 
-        let result = interpret("we did something 10 days ago.", false, fixed_time()).unwrap();
+        let result = interpret(
+            "we did something 10 days ago.",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
         assert_eq!(result.get_duration_sec() as i32, -10 * consts::DAY);
 
-        let result = interpret("we did something five days ago.", false, fixed_time()).unwrap();
+        let result = interpret(
+            "we did something five days ago.",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
         assert_eq!(result.get_duration_sec() as i32, -5 * consts::DAY);
 
-        let result = interpret("5 seconds ago a car was moved", false, fixed_time()).unwrap();
+        let result = interpret(
+            "5 seconds ago a car was moved",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
         assert_eq!(result.get_duration_sec() as i32, -5 * consts::SECOND);
 
-        let result = interpret("two weks ago", false, fixed_time()).unwrap();
+        let result = interpret("two weks ago", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.get_duration_sec() as i32, -2 * consts::WEEK);
 
-        let result = interpret("a month ago", false, fixed_time()).unwrap();
-        assert_eq!(result.get_month(), 0);
+        let result = interpret("a month ago", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_months(), -1);
+
+        let result = interpret("a few months ago", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_months(), -3);
+
+        let result = interpret("half year ago", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_months(), -6);
+    }
+
+    #[test]
+    fn test_units_ago() {
+        let result = interpret("5 days ago", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 10)));
+        assert_eq!(result.get_duration_sec() as i32, -5 * consts::DAY);
 
-        let result = interpret("a few months ago", false, fixed_time()).unwrap();
-        assert_eq!(result.get_month(), -2);
+        let result = interpret(
+            "it happened 3 days ago",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(12, 22)));
+        assert_eq!(result.get_duration_sec() as i32, -3 * consts::DAY);
+
+        let result = interpret("two hours ago", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 13)));
+        assert_eq!(result.get_duration_sec() as i32, -2 * consts::HOUR);
 
-        let result = interpret("half year ago", false, fixed_time()).unwrap();
-        assert_eq!(result.get_month(), -5);
+        let result = interpret("1 week ago", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec() as i32, -consts::WEEK);
     }
 }
@@ -0,0 +1,212 @@
+use super::combinators::{stub, tokenize_count_symbols, Dist};
+use super::common::days_in_month;
+use super::config::ParseConfig;
+use super::errors::SemanticError;
+use super::rules::{Context, RuleResult, TokenDesc};
+use super::tokens::{Articles, PeriodPart, Prepositions, Priority, TimeInterval, Token, When};
+use chrono::prelude::*;
+use time::Duration;
+
+use nom::{alt, apply, call, many_till, named_args, tuple, types::CompleteStr};
+
+define!(
+    beginning:
+    [(Token::PeriodPart(PeriodPart::Beginning), Priority(0)), "beginning", Dist(2)] |
+    [(Token::PeriodPart(PeriodPart::Beginning), Priority(0)), "start", Dist(1)]
+);
+define!(middle: (Token::PeriodPart(PeriodPart::Middle), Priority(0)), "middle", Dist(1));
+define!(mid: (Token::PeriodPart(PeriodPart::Middle), Priority(0)), "mid", Dist(0));
+define!(end: (Token::PeriodPart(PeriodPart::End), Priority(0)), "end", Dist(0));
+
+combine!(part => beginning | middle | mid | end);
+
+define!(of: (Token::Prepositions(Prepositions::Of), Priority(1)), "of", Dist(0));
+define_char!(dash: Priority(1), '-');
+
+define!(the: (Token::Articles(Articles::The), Priority(2)), "the", Dist(0));
+
+define!(this: (Token::When(When::This), Priority(3)), "this", Dist(1));
+define!(last: (Token::When(When::Last), Priority(3)), "last", Dist(1));
+define!(next: (Token::When(When::Next), Priority(3)), "next", Dist(1));
+
+combine!(when => this | last | next);
+
+define!(week: (Token::TimeInterval(TimeInterval::Week), Priority(4)), "week", Dist(1));
+define!(month: (Token::TimeInterval(TimeInterval::Month), Priority(4)), "month", Dist(1));
+define!(year: (Token::TimeInterval(TimeInterval::Year), Priority(4)), "year", Dist(1));
+
+combine!(unit => week | month | year);
+
+named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
+                             ( TokenDesc, TokenDesc, TokenDesc, TokenDesc, TokenDesc ) )>,
+    many_till!(tokenize_count_symbols,
+        alt!(
+            // e.g. "end of the week", "beginning of next month"
+            tuple!(apply!(part, dist_limit), apply!(of, dist_limit),
+                   alt!(apply!(the, dist_limit) | stub), alt!(apply!(when, dist_limit) | stub),
+                   apply!(unit, dist_limit)) |
+            // e.g. "mid-month"
+            tuple!(apply!(mid, dist_limit), dash, apply!(unit, dist_limit), stub, stub)
+        )
+    )
+);
+
+make_interpreter!(positions = 5);
+
+fn make_time<'a, 'b, Tz: TimeZone>(
+    res: &'a RuleResult,
+    tz_aware: DateTime<Tz>,
+    _input: &'b str,
+    config: &ParseConfig,
+) -> Result<Context, SemanticError<'b>> {
+    let mut ctx = Context::default();
+
+    let shift: i32 = match res.token_by_priority(Priority(3)) {
+        Some(Token::When(When::Next)) => 1,
+        Some(Token::When(When::Last)) => -1,
+        _ => 0,
+    };
+
+    let part = match res.token_by_priority(Priority(0)) {
+        Some(Token::PeriodPart(part)) => part,
+        _ => return Ok(ctx),
+    };
+
+    match res.token_by_priority(Priority(4)) {
+        Some(Token::TimeInterval(TimeInterval::Week)) => {
+            // days since the first day of the week
+            let weekday = i64::from(
+                (tz_aware.weekday().num_days_from_monday() + 7
+                    - config.week_start.num_days_from_monday())
+                    % 7,
+            );
+            let day = match part {
+                PeriodPart::Beginning => 0,
+                PeriodPart::Middle => 2,
+                PeriodPart::End => 6,
+            };
+            ctx.set_duration(Duration::days(day - weekday + 7 * i64::from(shift)).num_seconds());
+        }
+        Some(Token::TimeInterval(TimeInterval::Month)) => {
+            let mut year = tz_aware.year();
+            let mut month = tz_aware.month() as i32 + shift;
+            if month > 12 {
+                year += 1;
+                month = 1;
+            } else if month < 1 {
+                year -= 1;
+                month = 12;
+            }
+            ctx.year = Some(year);
+            ctx.month = Some(month);
+            ctx.day = Some(match part {
+                PeriodPart::Beginning => 1,
+                PeriodPart::Middle => 15,
+                PeriodPart::End => days_in_month(year, month as u32) as i32,
+            });
+        }
+        Some(Token::TimeInterval(TimeInterval::Year)) => {
+            let (month, day) = match part {
+                PeriodPart::Beginning => (1, 1),
+                PeriodPart::Middle => (7, 1),
+                PeriodPart::End => (12, 31),
+            };
+            ctx.year = Some(tz_aware.year() + shift);
+            ctx.month = Some(month);
+            ctx.day = Some(day);
+        }
+        _ => (),
+    }
+
+    Ok(ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::interpret;
+    use crate::rules::config::ParseConfig;
+    use crate::rules::consts;
+    use crate::rules::rules::MatchBounds;
+    use chrono::prelude::*;
+
+    fn fixed_time() -> DateTime<Local> {
+        // 2019 1st January, Tuesday
+        Local.ymd(2019, 1, 1).and_hms(0, 0, 0)
+    }
+
+    #[test]
+    fn test_end() {
+        let result = interpret(
+            "by the end of the week",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(7, 22)));
+        assert_eq!(result.get_duration_sec(), 5 * consts::DAY as i64);
+
+        let result = interpret("end of next month", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_year(), 2019);
+        assert_eq!(result.get_month(), 2);
+        assert_eq!(result.get_day(), 28);
+
+        let result = interpret("end of this year", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_month(), 12);
+        assert_eq!(result.get_day(), 31);
+    }
+
+    #[test]
+    fn test_week_start() {
+        let result = interpret("end of this week", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 5 * consts::DAY as i64);
+
+        // sunday starts the week, so it ends on saturday
+        let config = ParseConfig {
+            week_start: Weekday::Sun,
+            ..Default::default()
+        };
+        let result = interpret("end of this week", &config, fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 4 * consts::DAY as i64);
+
+        let result = interpret("beginning of this week", &config, fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), -2 * consts::DAY as i64);
+    }
+
+    #[test]
+    fn test_beginning() {
+        let result = interpret(
+            "beginning of next month",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 23)));
+        assert_eq!(result.get_month(), 2);
+        assert_eq!(result.get_day(), 1);
+
+        let result =
+            interpret("start of next week", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 6 * consts::DAY as i64);
+    }
+
+    #[test]
+    fn test_middle() {
+        let result = interpret("mid-month", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 9)));
+        assert_eq!(result.get_month(), 1);
+        assert_eq!(result.get_day(), 15);
+
+        let result = interpret(
+            "middle of last month",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
+        assert_eq!(result.get_year(), 2018);
+        assert_eq!(result.get_month(), 12);
+        assert_eq!(result.get_day(), 15);
+
+        let result = interpret("mid of the week", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), consts::DAY as i64);
+    }
+}
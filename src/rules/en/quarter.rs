@@ -0,0 +1,135 @@
+use super::combinators::{stub, tokenize_count_symbols, tokenize_word, wrap_error, Dist};
+use super::common::year;
+use super::config::ParseConfig;
+use super::errors::{invalid_date_error, SemanticError, UNKNOWN};
+use super::rules::{Context, MyResult, RuleResult, TokenDesc};
+use super::tokens::{Adverbs, Ordinals, PToken, Priority, Token};
+use chrono::prelude::*;
+
+use nom::{alt, apply, call, many_till, named_args, tuple, types::CompleteStr};
+
+define!(first: (Token::Ordinals(Ordinals::First), Priority(0)), "first", Dist(1));
+define!(second: (Token::Ordinals(Ordinals::Second), Priority(0)), "second", Dist(1));
+define!(third: (Token::Ordinals(Ordinals::Third), Priority(0)), "third", Dist(1));
+define!(fourth: (Token::Ordinals(Ordinals::Fourth), Priority(0)), "fourth", Dist(1));
+
+combine!(ordinal => first | second | third | fourth);
+
+define!(quarter: (Token::Adverbs(Adverbs::Quarter), Priority(1)), "quarter", Dist(2));
+
+/// Recognizes short notation of a quarter, e.g. "Q3", the number is glued to the letter
+fn short_quarter(input: CompleteStr) -> MyResult {
+    if let Ok((tail, word)) = tokenize_word(input) {
+        let word = word.to_lowercase();
+        if word.starts_with('q') && word.len() == 2 {
+            if let Ok(n) = word[1..].parse::<i32>() {
+                return Ok((
+                    tail,
+                    TokenDesc::new(PToken::PToken(Token::Number(n), Priority(0)), Dist(0)),
+                ));
+            }
+        }
+    }
+    wrap_error(input, UNKNOWN)
+}
+
+named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
+                             ( TokenDesc, TokenDesc, TokenDesc ) )>,
+    many_till!(tokenize_count_symbols,
+        alt!(
+            // e.g. "third quarter", "first quarter 2020"
+            tuple!(apply!(ordinal, dist_limit), apply!(quarter, dist_limit),
+                   alt!(apply!(year, Priority(2)) | stub)) |
+            // e.g. "Q1", "Q3 2020"
+            tuple!(short_quarter, alt!(apply!(year, Priority(2)) | stub), stub)
+        )
+    )
+);
+
+make_interpreter!(positions = 3);
+
+fn make_time<'a, 'b, Tz: TimeZone>(
+    res: &'a RuleResult,
+    tz_aware: DateTime<Tz>,
+    input: &'b str,
+    _config: &ParseConfig,
+) -> Result<Context, SemanticError<'b>> {
+    let mut ctx = Context::default();
+
+    let quarter = match res.token_by_priority(Priority(0)) {
+        Some(Token::Ordinals(Ordinals::First)) => 1,
+        Some(Token::Ordinals(Ordinals::Second)) => 2,
+        Some(Token::Ordinals(Ordinals::Third)) => 3,
+        Some(Token::Ordinals(Ordinals::Fourth)) => 4,
+        Some(Token::Number(n)) => n,
+        _ => 0,
+    };
+
+    if quarter < 1 || quarter > 4 {
+        return Err(invalid_date_error(input, "quarter", quarter));
+    }
+
+    // current year unless it is given explicitly
+    ctx.year = match res.token_by_priority(Priority(2)) {
+        Some(Token::Year(year)) => Some(year as i32),
+        _ => Some(tz_aware.year()),
+    };
+    // quarter starts on its first day
+    ctx.month = Some((quarter - 1) * 3 + 1);
+    ctx.day = Some(1);
+
+    Ok(ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::interpret;
+    use crate::rules::config::ParseConfig;
+    use crate::rules::errors::invalid_date_error;
+    use crate::rules::rules::MatchBounds;
+    use chrono::prelude::*;
+
+    fn fixed_time() -> DateTime<Local> {
+        Local.ymd(2019, 5, 10).and_hms(0, 0, 0)
+    }
+
+    #[test]
+    fn test_short_quarter() {
+        let result = interpret("due in Q1", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(7, 9)));
+        assert_eq!(result.get_year(), 2019);
+        assert_eq!(result.get_month(), 1);
+        assert_eq!(result.get_day(), 1);
+
+        let result = interpret("q4 2020", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 7)));
+        assert_eq!(result.get_year(), 2020);
+        assert_eq!(result.get_month(), 10);
+        assert_eq!(result.get_day(), 1);
+
+        let result = interpret("q5", &ParseConfig::default(), fixed_time());
+        assert_eq!(
+            result.unwrap_err().extract_error(),
+            invalid_date_error("q5", "quarter", 5).extract_error()
+        );
+    }
+
+    #[test]
+    fn test_quarter_words() {
+        let result = interpret("third quarter", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 13)));
+        assert_eq!(result.get_year(), 2019);
+        assert_eq!(result.get_month(), 7);
+        assert_eq!(result.get_day(), 1);
+
+        let result = interpret(
+            "results of the second quarter 2018",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(15, 34)));
+        assert_eq!(result.get_year(), 2018);
+        assert_eq!(result.get_month(), 4);
+    }
+}
@@ -0,0 +1,124 @@
+use super::combinators::{stub, tokenize_count_symbols, Dist};
+use super::config::ParseConfig;
+use super::errors::SemanticError;
+use super::rules::{Context, Recurrence, RuleResult, TokenDesc};
+use super::tokens::{Priority, TimeInterval, Token};
+use chrono::prelude::*;
+
+use nom::{alt, apply, call, many_till, named_args, tuple, types::CompleteStr};
+
+define!(
+    every:
+    [(Token::Every, Priority(0)), "every", Dist(1)] |
+    [(Token::Every, Priority(0)), "each", Dist(0)]
+);
+define!(other: (Token::Other, Priority(1)), "other", Dist(1));
+
+define!(hour: (Token::TimeInterval(TimeInterval::Hour), Priority(2)), "hour", Dist(1));
+define!(day: (Token::TimeInterval(TimeInterval::Day), Priority(2)), "day", Dist(0));
+define!(week: (Token::TimeInterval(TimeInterval::Week), Priority(2)), "week", Dist(1));
+define!(month: (Token::TimeInterval(TimeInterval::Month), Priority(2)), "month", Dist(1));
+define!(year: (Token::TimeInterval(TimeInterval::Year), Priority(2)), "year", Dist(1));
+
+combine!(unit => hour | day | week | month | year);
+
+define!(hourly: (Token::TimeInterval(TimeInterval::Hour), Priority(2)), "hourly", Dist(1));
+define!(daily: (Token::TimeInterval(TimeInterval::Day), Priority(2)), "daily", Dist(1));
+define!(weekly: (Token::TimeInterval(TimeInterval::Week), Priority(2)), "weekly", Dist(1));
+define!(monthly: (Token::TimeInterval(TimeInterval::Month), Priority(2)), "monthly", Dist(1));
+define!(
+    yearly:
+    [(Token::TimeInterval(TimeInterval::Year), Priority(2)), "yearly", Dist(1)] |
+    [(Token::TimeInterval(TimeInterval::Year), Priority(2)), "annually", Dist(2)]
+);
+
+combine!(frequency => hourly | daily | weekly | monthly | yearly);
+
+named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
+                             ( TokenDesc, TokenDesc, TokenDesc ) )>,
+    many_till!(tokenize_count_symbols,
+        alt!(
+            // e.g.: every other week
+            tuple!(apply!(every, dist_limit), apply!(other, dist_limit), apply!(unit, dist_limit)) |
+            // e.g.: every day
+            tuple!(apply!(every, dist_limit), apply!(unit, dist_limit), stub) |
+            // e.g.: daily, weekly
+            tuple!(apply!(frequency, dist_limit), stub, stub)
+        )
+    )
+);
+
+make_interpreter!(positions = 3);
+
+fn make_time<'a, 'b, Tz: TimeZone>(
+    res: &'a RuleResult,
+    _tz_aware: DateTime<Tz>,
+    _input: &'b str,
+    _config: &ParseConfig,
+) -> Result<Context, SemanticError<'b>> {
+    let mut ctx = Context::default();
+
+    // weekdays like "every monday" are handled by the weekdays rule
+    if let Some(Token::TimeInterval(unit)) = res.token_by_priority(Priority(2)) {
+        ctx.recurrence = Some(Recurrence {
+            unit,
+            step: if res.token_by_priority(Priority(1)).is_some() {
+                2
+            } else {
+                1
+            },
+            weekday: None,
+        });
+    }
+
+    Ok(ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::interpret;
+    use crate::rules::config::ParseConfig;
+    use crate::rules::rules::{MatchBounds, Recurrence};
+    use crate::rules::tokens::TimeInterval;
+    use chrono::prelude::*;
+
+    fn fixed_time() -> DateTime<Local> {
+        // 2019 1st January, Tuesday
+        Local.ymd(2019, 1, 1).and_hms(0, 0, 0)
+    }
+
+    fn recurrence(unit: TimeInterval, step: u32) -> Option<Recurrence> {
+        Some(Recurrence {
+            unit,
+            step,
+            weekday: None,
+        })
+    }
+
+    #[test]
+    fn test_recurrence() {
+        let result = interpret("backup every day", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(7, 16)));
+        assert_eq!(result.get_duration_sec(), 0);
+        assert_eq!(result.context.recurrence, recurrence(TimeInterval::Day, 1));
+
+        let result = interpret("every other week", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.context.recurrence, recurrence(TimeInterval::Week, 2));
+
+        let result = interpret("each month", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(
+            result.context.recurrence,
+            recurrence(TimeInterval::Month, 1)
+        );
+
+        let result = interpret("daily standup", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 5)));
+        assert_eq!(result.context.recurrence, recurrence(TimeInterval::Day, 1));
+
+        let result = interpret("weekly", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.context.recurrence, recurrence(TimeInterval::Week, 1));
+
+        let result = interpret("every monday", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, None);
+    }
+}
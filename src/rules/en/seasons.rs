@@ -0,0 +1,154 @@
+use super::combinators::{stub, tokenize_count_symbols, Dist};
+use super::config::{Hemisphere, ParseConfig, SeasonConvention};
+use super::errors::SemanticError;
+use super::rules::{Context, RuleResult, TokenDesc};
+use super::tokens::{Priority, Season, Token};
+use chrono::prelude::*;
+
+use nom::{alt, apply, call, many_till, named_args, tuple, types::CompleteStr};
+
+// season names are common words, e.g. "falls" or "winner", so they must match exactly
+define!(
+    season:
+    [(Token::Season(Season::Spring), Priority(0)), "spring", Dist(0)] |
+    [(Token::Season(Season::Summer), Priority(0)), "summer", Dist(0)] |
+    [(Token::Season(Season::Autumn), Priority(0)), "autumn", Dist(1)] |
+    [(Token::Season(Season::Autumn), Priority(0)), "fall", Dist(0)] |
+    [(Token::Season(Season::Winter), Priority(0)), "winter", Dist(0)]
+);
+
+named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>, ( TokenDesc, TokenDesc ) )>,
+    many_till!(tokenize_count_symbols,
+        alt!(
+            // e.g. "summer", "in the fall"
+            tuple!(apply!(season, dist_limit), stub)
+        )
+    )
+);
+
+make_interpreter!(positions = 2);
+
+// the first day of a season in the northern hemisphere as (month, day)
+fn season_start(season: &Season, convention: SeasonConvention) -> (u32, u32) {
+    match (convention, season) {
+        (SeasonConvention::Meteorological, Season::Spring) => (3, 1),
+        (SeasonConvention::Meteorological, Season::Summer) => (6, 1),
+        (SeasonConvention::Meteorological, Season::Autumn) => (9, 1),
+        (SeasonConvention::Meteorological, Season::Winter) => (12, 1),
+        (SeasonConvention::Astronomical, Season::Spring) => (3, 20),
+        (SeasonConvention::Astronomical, Season::Summer) => (6, 21),
+        (SeasonConvention::Astronomical, Season::Autumn) => (9, 22),
+        (SeasonConvention::Astronomical, Season::Winter) => (12, 21),
+    }
+}
+
+/// A season is the start of the nearest upcoming one, today included. Seasons of the southern
+/// hemisphere are the opposite ones of the northern hemisphere, e.g. summer starts in december.
+fn make_time<'a, 'b, Tz: TimeZone>(
+    res: &'a RuleResult,
+    tz_aware: DateTime<Tz>,
+    _input: &'b str,
+    config: &ParseConfig,
+) -> Result<Context, SemanticError<'b>> {
+    let mut ctx = Context::default();
+
+    let season = match res.token_by_priority(Priority(0)) {
+        Some(Token::Season(season)) => season,
+        _ => unreachable!(),
+    };
+    let season = match config.hemisphere {
+        Hemisphere::Northern => season,
+        Hemisphere::Southern => match season {
+            Season::Spring => Season::Autumn,
+            Season::Summer => Season::Winter,
+            Season::Autumn => Season::Spring,
+            Season::Winter => Season::Summer,
+        },
+    };
+
+    let (month, day) = season_start(&season, config.season_convention);
+    if (month, day) < (tz_aware.month(), tz_aware.day()) {
+        ctx.year = Some(tz_aware.year() + 1);
+    }
+    ctx.month = Some(month as i32);
+    ctx.day = Some(day as i32);
+
+    Ok(ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::interpret;
+    use crate::rules::config::{Hemisphere, ParseConfig, SeasonConvention};
+    use crate::rules::rules::MatchBounds;
+    use chrono::prelude::*;
+
+    fn fixed_time() -> DateTime<Local> {
+        Local.ymd(2019, 4, 10).and_hms(12, 0, 0)
+    }
+
+    #[test]
+    fn test_seasons() {
+        let result = interpret(
+            "vacation in the summer",
+            &ParseConfig::default(),
+            fixed_time(),
+        );
+        let result = result.unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(16, 22)));
+        assert_eq!(result.get_year(), 0);
+        assert_eq!(result.get_month(), 6);
+        assert_eq!(result.get_day(), 1);
+
+        let result = interpret("fall", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_month(), 9);
+
+        let result = interpret("autum", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_month(), 9);
+
+        // this year's spring has started already
+        let result = interpret("spring", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_year(), 2020);
+        assert_eq!(result.get_month(), 3);
+        assert_eq!(result.get_day(), 1);
+
+        let result = interpret("it falls", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, None);
+    }
+
+    #[test]
+    fn test_hemisphere() {
+        let config = ParseConfig {
+            hemisphere: Hemisphere::Southern,
+            ..Default::default()
+        };
+
+        let result = interpret("summer", &config, fixed_time()).unwrap();
+        assert_eq!(result.get_month(), 12);
+        assert_eq!(result.get_day(), 1);
+
+        let result = interpret("winter", &config, fixed_time()).unwrap();
+        assert_eq!(result.get_year(), 0);
+        assert_eq!(result.get_month(), 6);
+    }
+
+    #[test]
+    fn test_astronomical() {
+        let config = ParseConfig {
+            season_convention: SeasonConvention::Astronomical,
+            ..Default::default()
+        };
+
+        let result = interpret("summer", &config, fixed_time()).unwrap();
+        assert_eq!(result.get_month(), 6);
+        assert_eq!(result.get_day(), 21);
+
+        let config = ParseConfig {
+            hemisphere: Hemisphere::Southern,
+            ..config
+        };
+        let result = interpret("summer", &config, fixed_time()).unwrap();
+        assert_eq!(result.get_month(), 12);
+        assert_eq!(result.get_day(), 21);
+    }
+}
@@ -1,17 +1,70 @@
-use super::combinators::{stub, tokenize_count_symbols, Dist};
-use super::errors::{invalid_time_error, SemanticError};
-use super::rules::{Context, RuleResult, TokenDesc};
-use super::tokens::{AmPm, Priority, Token};
+use super::adjustment::unit_size;
+use super::casual_date_time::{midnight, noon};
+use super::combinators::{
+    recognize_int, stub, tokenize_count_symbols, tokenize_word, wrap_error, Dist,
+};
+use super::common_matchers::match_num;
+use super::config::ParseConfig;
+use super::errors::{invalid_time_error, SemanticError, UNKNOWN};
+use super::rules::{Context, MyResult, RuleResult, TokenDesc};
+use super::tokens::{
+    Adverbs, AmPm, Articles, IntWord, PToken, Prepositions, Priority, TimeOfDay, Token, When,
+};
 use chrono::prelude::*;
-use nom::{alt, apply, call, many_till, named_args, tuple, types::CompleteStr};
+use nom::{
+    alt, apply, call, many_till, named_args, not, preceded, terminated, tuple, types::CompleteStr,
+};
 
 define_num!(hours: (Token::Number, Priority(0)));
 
+define!(one: (Token::IntWord(IntWord::One), Priority(0)), "one", Dist(0));
+define!(two: (Token::IntWord(IntWord::Two), Priority(0)), "two", Dist(0));
+define!(three: (Token::IntWord(IntWord::Three), Priority(0)), "three", Dist(1));
+define!(four: (Token::IntWord(IntWord::Four), Priority(0)), "four", Dist(1));
+define!(five: (Token::IntWord(IntWord::Five), Priority(0)), "five", Dist(1));
+define!(six: (Token::IntWord(IntWord::Six), Priority(0)), "six", Dist(0));
+define!(seven: (Token::IntWord(IntWord::Seven), Priority(0)), "seven", Dist(1));
+define!(eight: (Token::IntWord(IntWord::Eight), Priority(0)), "eight", Dist(1));
+define!(nine: (Token::IntWord(IntWord::Nine), Priority(0)), "nine", Dist(1));
+define!(ten: (Token::IntWord(IntWord::Ten), Priority(0)), "ten", Dist(0));
+define!(eleven: (Token::IntWord(IntWord::Eleven), Priority(0)), "eleven", Dist(1));
+define!(twelve: (Token::IntWord(IntWord::Twelve), Priority(0)), "twelve", Dist(1));
+
+combine!(int_word => one | two | three | four | five | six | seven | eight | nine | ten
+                         | eleven | twelve);
+
+// hours either as a number or as a word, e.g. 5 or five
+named_args!(hour<'a>(dist_limit: Dist)<CompleteStr<'a>, TokenDesc>,
+    alt!(hours | apply!(int_word, dist_limit))
+);
+
 define_char!(colon: Priority(1), ':');
 define_char!(dash: Priority(1), '-');
 
 define_num!(minutes: (Token::Number, Priority(2)));
 
+// minutes after a dot, e.g. "5.30pm", there must be two digits of at most 59 not followed by
+// another number, so that fractions like "1.5", prices like "9.99" and dates like "03.08.2018"
+// are not taken for a time
+fn dot_minutes(input: CompleteStr) -> MyResult {
+    if let Some(after_dot) = input.strip_prefix('.') {
+        let digits = after_dot.chars().take_while(|c| c.is_digit(10)).count();
+        let tail = &after_dot[digits..];
+        let mut rest = tail.chars();
+        let continues_number =
+            rest.next() == Some('.') && rest.next().map_or(false, |c| c.is_digit(10));
+        if digits == 2 && !continues_number {
+            if let Ok(n @ 0..=59) = after_dot[..2].parse::<i32>() {
+                return Ok((
+                    CompleteStr(tail),
+                    TokenDesc::new(PToken::PToken(Token::Number(n), Priority(2)), Dist(0)),
+                ));
+            }
+        }
+    }
+    wrap_error(input, UNKNOWN)
+}
+
 define!(
     am:
     [(Token::AmPm(AmPm::Am), Priority(3)), "a.m.", Dist(0)] |
@@ -24,47 +77,327 @@ define!(
 
 define!(
     pm:
-    [(Token::AmPm(AmPm::Am), Priority(3)), "p.m.", Dist(0)] |
-    [(Token::AmPm(AmPm::Am), Priority(3)), "p.m", Dist(0)] |
-    [(Token::AmPm(AmPm::Am), Priority(3)), "pm.", Dist(0)] |
+    [(Token::AmPm(AmPm::Pm), Priority(3)), "p.m.", Dist(0)] |
+    [(Token::AmPm(AmPm::Pm), Priority(3)), "p.m", Dist(0)] |
+    [(Token::AmPm(AmPm::Pm), Priority(3)), "pm.", Dist(0)] |
     [(Token::AmPm(AmPm::Pm), Priority(3)), "pm", Dist(0)] |
     [(Token::AmPm(AmPm::Pm), Priority(3)), "p.", Dist(0)] |
     [(Token::AmPm(AmPm::Pm), Priority(3)), "p", Dist(0)]
 );
 
-combine!(am_pm => am | pm);
+// a part of the day after an hour also tells the meridiem, e.g. "5 in the afternoon"
+
+define!(in_word: (Token::When(When::In), Priority(3)), "in", Dist(0));
+define!(the: (Token::Articles(Articles::The), Priority(3)), "the", Dist(0));
+define!(
+    day_part:
+    [(Token::AmPm(AmPm::Am), Priority(3)), "morning", Dist(2)] |
+    [(Token::AmPm(AmPm::Pm), Priority(3)), "afternoon", Dist(2)] |
+    [(Token::AmPm(AmPm::Pm), Priority(3)), "evening", Dist(2)] |
+    [(Token::AmPm(AmPm::Pm), Priority(3)), "night", Dist(1)]
+);
+
+named_args!(day_part_suffix<'a>(dist_limit: Dist)<CompleteStr<'a>, TokenDesc>,
+    preceded!(apply!(in_word, Dist(0)),
+              preceded!(apply!(the, Dist(0)), apply!(day_part, dist_limit)))
+);
+
+combine!(am_pm => am | pm | day_part_suffix);
+
+define!(
+    fraction:
+    [(Token::Adverbs(Adverbs::Quarter), Priority(4)), "quarter", Dist(2)] |
+    [(Token::Adverbs(Adverbs::Half), Priority(4)), "half", Dist(1)]
+);
+
+// minutes before or after an hour, e.g. "ten past", "20 to"
+define_num!(minutes_count: (Token::Number, Priority(4)), 1, 59);
+
+define!(
+    minutes_word:
+    [(Token::Number(5), Priority(4)), "five", Dist(1)] |
+    [(Token::Number(10), Priority(4)), "ten", Dist(0)] |
+    [(Token::Number(20), Priority(4)), "twenty", Dist(1)]
+);
+
+// minutes in words, they go before ranges, so "ten to six" is 5:50 rather than 10 to 6
+named_args!(clock_fraction<'a>(dist_limit: Dist)<CompleteStr<'a>, TokenDesc>,
+    alt!(apply!(fraction, dist_limit) | apply!(minutes_word, dist_limit))
+);
+
+define!(past: (Token::Prepositions(Prepositions::Past), Priority(5)), "past", Dist(1));
+define!(to: (Token::Prepositions(Prepositions::To), Priority(5)), "to", Dist(0));
+
+combine!(relation => past | to);
+
+// hours after "past" or "to" are also given on the 24-hour clock or as noon and midnight, the
+// latter are the casual rule words
+named_args!(relation_hour<'a>(dist_limit: Dist)<CompleteStr<'a>, TokenDesc>,
+    alt!(apply!(hour, dist_limit) | apply!(noon, dist_limit) | apply!(midnight, dist_limit))
+);
+
+// numbers are minutes only before an hour which can't start a range of numbers, otherwise
+// "pages 10 to 20" would be a time: "25 past 19" and "5 to midnight" are times, "10 to 20" is not
+define_num!(afternoon_hour: (Token::Number, Priority(0)), 13, 23);
+
+named_args!(past_hour<'a>(dist_limit: Dist)<CompleteStr<'a>, TokenDesc>,
+    alt!(afternoon_hour | apply!(noon, dist_limit) | apply!(midnight, dist_limit))
+);
+
+named_args!(to_hour<'a>(dist_limit: Dist)<CompleteStr<'a>, TokenDesc>,
+    alt!(apply!(noon, dist_limit) | apply!(midnight, dist_limit))
+);
+
+// "o'clock" suffix, apostrophes are ignored by words recognition, so it is the same as "oclock"
+
+define!(oclock: (Token::OClock, Priority(9)), "oclock", Dist(1));
+define!(o_letter: (Token::OClock, Priority(9)), "o", Dist(0));
+define!(clock: (Token::OClock, Priority(9)), "clock", Dist(1));
+
+named_args!(oclock_suffix<'a>(dist_limit: Dist)<CompleteStr<'a>, TokenDesc>,
+    alt!(apply!(oclock, dist_limit) |
+         preceded!(apply!(o_letter, Dist(0)), apply!(clock, dist_limit)))
+);
+
+// a bare hour after "at", e.g. "at 5", it is the nearest 5:00 or 17:00 from now on
+
+define!(at: (Token::Prepositions(Prepositions::At), Priority(10)), "at", Dist(0));
+
+// only numbers of a day's hours, so that "at 100 km" is not taken for a time
+define_num!(day_hours: (Token::Number, Priority(0)), 0, 23);
+
+named_args!(day_hour<'a>(dist_limit: Dist)<CompleteStr<'a>, TokenDesc>,
+    alt!(day_hours | apply!(int_word, dist_limit))
+);
+
+// hours followed by minutes, meridiem or "o'clock" are recognized by other patterns
+fn bare_hour_prefix(input: CompleteStr, dist_limit: Dist) -> MyResult {
+    let (tail, desc) = at(input, dist_limit)?;
+    if let Ok((rest, _)) = day_hour(tail, dist_limit) {
+        let has_suffix = rest.trim_start().starts_with([':', '-'])
+            || dot_minutes(rest).is_ok()
+            || am_pm(rest, dist_limit).is_ok()
+            || oclock_suffix(rest, dist_limit).is_ok();
+        if !has_suffix {
+            return Ok((tail, desc));
+        }
+    }
+    wrap_error(input, UNKNOWN)
+}
+
+// hours and minutes with a dot and without a meridiem need "at" before them, e.g. "at 14.45",
+// otherwise any decimal number like "3.14" would be a time
+
+define!(dot_at: (Token::Prepositions(Prepositions::At), Priority(11)), "at", Dist(0));
+
+fn dot_time_prefix(input: CompleteStr, dist_limit: Dist) -> MyResult {
+    let (tail, desc) = dot_at(input, dist_limit)?;
+    if let Ok((rest, _)) = day_hours(tail) {
+        if let Ok((rest, _)) = dot_minutes(rest) {
+            // a meridiem is recognized by another pattern, "at" is not a part of the match then
+            if am_pm(rest, dist_limit).is_err() {
+                return Ok((tail, desc));
+            }
+        }
+    }
+    wrap_error(input, UNKNOWN)
+}
+
+// timezone abbreviations, e.g. "5pm EST", offsets are in seconds east of UTC
 
-named_args!(parse<'a>(exact_match: bool)<CompleteStr<'a>, (Vec<usize>,
-                             ( TokenDesc, TokenDesc, TokenDesc, TokenDesc ) )>,
+define!(
+    timezone:
+    [(Token::TimeZone(0), Priority(8)), "utc", Dist(0)] |
+    [(Token::TimeZone(0), Priority(8)), "gmt", Dist(0)] |
+    [(Token::TimeZone(-5 * 3600), Priority(8)), "est", Dist(0)] |
+    [(Token::TimeZone(-4 * 3600), Priority(8)), "edt", Dist(0)] |
+    [(Token::TimeZone(-8 * 3600), Priority(8)), "pst", Dist(0)] |
+    [(Token::TimeZone(-7 * 3600), Priority(8)), "pdt", Dist(0)] |
+    [(Token::TimeZone(3600), Priority(8)), "cet", Dist(0)] |
+    [(Token::TimeZone(2 * 3600), Priority(8)), "cest", Dist(0)]
+);
+
+// unknown abbreviations are not a part of the match
+named_args!(timezone_suffix<'a>(dist_limit: Dist)<CompleteStr<'a>, TokenDesc>,
+    alt!(apply!(timezone, dist_limit) | stub)
+);
+
+// ranges, e.g. "from 9am to 5pm", "9-5", "between 2 and 4pm"
+
+define!(from: (Token::Prepositions(Prepositions::From), Priority(6)), "from", Dist(1));
+define!(between: (Token::Prepositions(Prepositions::Between), Priority(6)), "between", Dist(2));
+
+named_args!(from_prefix<'a>(dist_limit: Dist)<CompleteStr<'a>, TokenDesc>,
+    alt!(apply!(from, dist_limit) | stub)
+);
+
+define!(
+    range_word:
+    [(Token::Range, Priority(7)), "to", Dist(0)] |
+    [(Token::Range, Priority(7)), "until", Dist(1)]
+);
+
+define_char!(range_dash: Priority(7), '-');
+
+// a unit after the end of a range makes it an amount, e.g. "5pm - 5 minutes" is not until 5
+fn unit_suffix(input: CompleteStr) -> MyResult {
+    if let Ok((tail, word)) = tokenize_word(input) {
+        if unit_size(&word).is_some() {
+            return Ok((tail, TokenDesc::new(PToken::Stub, Dist(0))));
+        }
+    }
+    wrap_error(input, UNKNOWN)
+}
+
+// "and" is a range connector after "between" only, otherwise it joins list items
+define!(range_and: (Token::Range, Priority(7)), "and", Dist(0));
+
+// hours of a range can't be bigger than 12, minutes are always written with two digits, so it is
+// required to have one digit after a dash to distinguish "9-5" range from 11-10 which is 11:10
+fn range_hours(input: CompleteStr, single_digit: bool) -> MyResult {
+    if let Ok((tail, n)) = recognize_int(input) {
+        let digits =
+            input[..input.len() - tail.len()].trim_start_matches(|c: char| !c.is_digit(10));
+        if n >= 0 && n <= 12 && (!single_digit || digits.len() == 1) {
+            return Ok((
+                tail,
+                TokenDesc::new(PToken::PToken(Token::Number(n), Priority(0)), Dist(0)),
+            ));
+        }
+    }
+    wrap_error(input, UNKNOWN)
+}
+
+named_args!(range_hour<'a>(dist_limit: Dist, single_digit: bool)<CompleteStr<'a>, TokenDesc>,
+    alt!(call!(range_hours, single_digit) | apply!(int_word, dist_limit))
+);
+
+named_args!(range_am_pm<'a>(dist_limit: Dist)<CompleteStr<'a>, TokenDesc>,
+    alt!(apply!(am_pm, dist_limit) | stub)
+);
+
+named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
+                             ( TokenDesc, TokenDesc, TokenDesc, TokenDesc, TokenDesc, TokenDesc ) )>,
     many_till!(tokenize_count_symbols,
         alt!(
+            // quarter/half past/to hours am/pm, for example quarter past five pm
+            tuple!(apply!(clock_fraction, dist_limit), apply!(relation, dist_limit),
+                   apply!(hour, dist_limit), apply!(am_pm, dist_limit), stub, stub) |
+            // quarter/half past/to hours, for example half past nine, ten to 16, five to midnight
+            tuple!(apply!(clock_fraction, dist_limit), apply!(relation, dist_limit),
+                   apply!(relation_hour, dist_limit), stub, stub, stub) |
+            // minutes past hours, for example 25 past 19, 10 past noon
+            tuple!(minutes_count, apply!(past, dist_limit), apply!(past_hour, dist_limit), stub,
+                   stub, stub) |
+            // minutes to noon or midnight, for example 5 to midnight
+            tuple!(minutes_count, apply!(to, dist_limit), apply!(to_hour, dist_limit), stub, stub,
+                   stub) |
+            // range of hours, for example from 9am to 5pm, nine to five
+            tuple!(apply!(from_prefix, dist_limit), apply!(range_hour, dist_limit, false),
+                   apply!(range_am_pm, dist_limit), apply!(range_word, dist_limit),
+                   apply!(range_hour, dist_limit, false), apply!(range_am_pm, dist_limit)) |
+            // range of hours, for example between 2 and 4pm
+            tuple!(apply!(between, dist_limit), apply!(range_hour, dist_limit, false),
+                   apply!(range_am_pm, dist_limit), apply!(range_and, dist_limit),
+                   apply!(range_hour, dist_limit, false), apply!(range_am_pm, dist_limit)) |
+            // range of hours, for example 9-5, 10am-2pm
+            tuple!(apply!(from_prefix, dist_limit), apply!(range_hour, dist_limit, false),
+                   apply!(range_am_pm, dist_limit), range_dash,
+                   terminated!(apply!(range_hour, dist_limit, true), not!(unit_suffix)),
+                   apply!(range_am_pm, dist_limit)) |
             // hours:minutes am/pm, for example 5:30am, 4:44pm, etc.
-            tuple!(hours, colon, minutes, apply!(am_pm, exact_match)) |
-            // hours:minutes am/pm, for example 5-30am, 4-44pm, etc.
-            tuple!(hours, dash, minutes, apply!(am_pm, exact_match)) |
+            tuple!(hours, colon, minutes, apply!(am_pm, dist_limit),
+                   apply!(timezone_suffix, dist_limit), stub) |
+            // hours-minutes am/pm, for example 5-30am, 4-44pm, etc.
+            tuple!(hours, dash, minutes, apply!(am_pm, dist_limit),
+                   apply!(timezone_suffix, dist_limit), stub) |
+            // hours.minutes am/pm, for example 5.30pm
+            tuple!(hours, dot_minutes, apply!(am_pm, dist_limit),
+                   apply!(timezone_suffix, dist_limit), stub, stub) |
             // hours:minutes, for example 5:30, 4:44, etc.
-            tuple!(hours, colon, minutes, stub) |
+            tuple!(hours, colon, minutes, apply!(timezone_suffix, dist_limit), stub, stub) |
+            // at hours.minutes, for example at 14.45
+            tuple!(apply!(dot_time_prefix, dist_limit), hours, dot_minutes,
+                   apply!(timezone_suffix, dist_limit), stub, stub) |
             // hours-minutes, for example 5-30, 4-44, etc.
-            tuple!(hours, dash, minutes, stub) |
-            // hours, for example 5am, 6p.m., 4a., 3 p.m.
-            tuple!(hours, apply!(am_pm, exact_match), stub, stub)
+            tuple!(hours, dash, minutes, apply!(timezone_suffix, dist_limit), stub, stub) |
+            // hours, for example 5am, 6p.m., 4a., 3 p.m., five pm, optionally with a timezone
+            tuple!(apply!(hour, dist_limit), apply!(am_pm, dist_limit),
+                   apply!(timezone_suffix, dist_limit), stub, stub, stub) |
+            // whole hours, for example five o'clock, 8 oclock
+            tuple!(apply!(hour, dist_limit), apply!(oclock_suffix, dist_limit),
+                   apply!(timezone_suffix, dist_limit), stub, stub, stub) |
+            // bare hours, for example at 5, at nine
+            tuple!(apply!(bare_hour_prefix, dist_limit), apply!(hour, dist_limit),
+                   apply!(timezone_suffix, dist_limit), stub, stub, stub)
         )
     )
 );
 
-make_interpreter!(positions = 4);
+make_interpreter!(positions = 6);
+
+fn opposite_meridiem(token: Token) -> Token {
+    match token {
+        Token::AmPm(AmPm::Am) => Token::AmPm(AmPm::Pm),
+        _ => Token::AmPm(AmPm::Am),
+    }
+}
 
 fn make_time<'a, 'b, Tz: TimeZone>(
     res: &'a RuleResult,
-    _tz_aware: DateTime<Tz>,
+    tz_aware: DateTime<Tz>,
     input: &'b str,
+    config: &ParseConfig,
 ) -> Result<Context, SemanticError<'b>> {
+    if let Some((mut start, mut end)) = res.split_range(Priority(7)) {
+        // meridiem given for one end only is inferred for another one: a range goes forward, so
+        // it is the same for "9-11pm", but "9 to 5pm" starts in the morning and "10pm to 2"
+        // ends in the night, with no meridiem at all "9 to 5" ends in the afternoon
+        let start_num = match_num(start.token_by_priority(Priority(0))).unwrap_or(0);
+        let end_num = match_num(end.token_by_priority(Priority(0))).unwrap_or(0);
+        let (start_hour, end_hour) = (start_num % 12, end_num % 12);
+        let infer = |token| {
+            if start_hour < end_hour {
+                token
+            } else {
+                opposite_meridiem(token)
+            }
+        };
+        match (
+            start.token_by_priority(Priority(3)),
+            end.token_by_priority(Priority(3)),
+        ) {
+            (None, Some(token)) => {
+                let token = infer(token);
+                start.set_token(&TokenDesc::new(PToken::PToken(token, Priority(3)), Dist(0)));
+            }
+            (Some(token), None) => {
+                let token = infer(token);
+                end.set_token(&TokenDesc::new(PToken::PToken(token, Priority(3)), Dist(0)));
+            }
+            (None, None) if end_num < start_num && start_num <= 12 => {
+                let token = Token::AmPm(AmPm::Pm);
+                end.set_token(&TokenDesc::new(PToken::PToken(token, Priority(3)), Dist(0)));
+            }
+            _ => (),
+        }
+
+        let mut ctx = make_time(&start, tz_aware.clone(), input, config)?;
+        ctx.end = Some(Box::new(make_time(&end, tz_aware, input, config)?));
+        return Ok(ctx);
+    }
+
     let mut ctx = Context::default();
     let mut hrs: i32 = 0;
 
-    let token = res.token_by_priority(Priority(0));
-    if let Some(Token::Number(n)) = token {
-        hrs = n as i32;
+    if let Some(n) = match_num(res.token_by_priority(Priority(0))) {
+        hrs = n;
+    }
+    // noon and midnight words come from the casual rule along with its priority
+    match res.token_by_priority(Priority(2)) {
+        Some(Token::TimeOfDay(TimeOfDay::Noon)) => hrs = 12,
+        Some(Token::TimeOfDay(TimeOfDay::Midnight)) => hrs = 0,
+        _ => (),
     }
 
     let token = res.token_by_priority(Priority(2));
@@ -82,15 +415,90 @@ fn make_time<'a, 'b, Tz: TimeZone>(
         ctx.minute = Some(0);
     }
 
-    let token = res.token_by_priority(Priority(3));
-    if let Some(Token::AmPm(AmPm::Pm)) = token {
-        if hrs <= 12 {
-            // TODO: Ensure correctness
-            hrs = (hrs + 12) % 24;
+    // the minutes are before the hour, e.g. "quarter to six"
+    let mut to_hour = false;
+    let minutes = match res.token_by_priority(Priority(4)) {
+        Some(Token::Adverbs(Adverbs::Quarter)) => Some(15),
+        Some(Token::Adverbs(_)) => Some(30),
+        token => match_num(token),
+    };
+    if let Some(minutes) = minutes {
+        if hrs < 0 || hrs > 23 {
+            return Err(invalid_time_error(input, "hours", hrs));
+        }
+
+        let token = res.token_by_priority(Priority(5));
+        if let Some(Token::Prepositions(Prepositions::To)) = token {
+            to_hour = true;
+            ctx.minute = Some(60 - minutes);
+        } else {
+            ctx.minute = Some(minutes);
         }
     }
 
+    let mut token = res.token_by_priority(Priority(3));
+    if token.is_some() && hrs > 12 {
+        // 12-hour clock has no hours like "13 pm", the meridiem of "18:00 pm" is ignored unless
+        // it is strict
+        if config.strict_meridiem {
+            return Err(invalid_time_error(input, "hours", hrs));
+        }
+        token = None;
+    }
+    match token {
+        // 12pm is noon and 12am is midnight
+        Some(Token::AmPm(AmPm::Pm)) if hrs < 12 => hrs += 12,
+        Some(Token::AmPm(AmPm::Am)) if hrs == 12 => hrs = 0,
+        _ => (),
+    }
+
+    if to_hour {
+        // the meridiem is the one of the hour, so "quarter to 12 pm" is 11:45, without a meridiem
+        // "quarter to six" is 5:45, "quarter to one" wraps to 12:45 and "ten to midnight" is 23:50
+        hrs = match hrs {
+            0 => 23,
+            1 if token.is_none() => 12,
+            _ => hrs - 1,
+        };
+    }
+
+    let oclock = res.token_by_priority(Priority(9)).is_some();
+    let bare = res.token_by_priority(Priority(10)).is_some();
+    if token.is_none() && (oclock || bare) {
+        // "five o'clock" has no meridiem, it is the nearest 5:00 or 17:00 from now on
+        if oclock && hrs > 12 {
+            return Err(invalid_time_error(input, "hours", hrs));
+        }
+        let now = (tz_aware.hour() as i32, tz_aware.minute() as i32);
+        // "at 17" is unambiguous, but it still may refer to the next day
+        let (morning, evening) = if hrs > 12 {
+            (hrs, hrs)
+        } else {
+            (hrs % 12, hrs % 12 + 12)
+        };
+        if (morning, 0) > now {
+            hrs = morning;
+        } else if (evening, 0) > now {
+            hrs = evening;
+        } else if config.prefer_future {
+            hrs = morning;
+            ctx.roll_day();
+        } else {
+            // the latest occurrence of the current day
+            hrs = evening;
+        }
+    }
+
+    if let Some(Token::TimeZone(offset)) = res.token_by_priority(Priority(8)) {
+        ctx.utc_offset = Some(offset);
+    }
+
+    if hrs > 23 {
+        return Err(invalid_time_error(input, "hours", hrs));
+    }
+
     ctx.hour = Some(hrs);
+    ctx.second = Some(0);
 
     Ok(ctx)
 }
@@ -98,6 +506,7 @@ fn make_time<'a, 'b, Tz: TimeZone>(
 #[cfg(test)]
 mod tests {
     use super::interpret;
+    use crate::rules::config::ParseConfig;
     use crate::rules::errors::invalid_time_error;
     use crate::rules::rules::MatchBounds;
     use chrono::prelude::*;
@@ -108,79 +517,607 @@ mod tests {
 
     #[test]
     fn test_hours_pm() {
-        let result = interpret("5pm", false, fixed_time()).unwrap();
+        let result = interpret("5pm", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.bounds, Some(MatchBounds::new(0, 3)));
         assert_eq!(result.get_hours(), 17);
 
-        let result = interpret("at 5 pm", false, fixed_time()).unwrap();
+        let result = interpret("at 5 pm", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.bounds, Some(MatchBounds::new(3, 7)));
         assert_eq!(result.get_hours(), 17);
 
-        let result = interpret("at 12 p.", false, fixed_time()).unwrap();
+        let result = interpret("at 12 p.", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.bounds, Some(MatchBounds::new(3, 8)));
+        assert_eq!(result.get_hours(), 12);
 
-        assert_eq!(result.get_hours(), 0);
-        let result = interpret("at 11p.m.", false, fixed_time()).unwrap();
+        let result = interpret("12pm", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 12);
+        assert_eq!(result.get_minutes(), 0);
+
+        let result = interpret("at 11p.m.", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.bounds, Some(MatchBounds::new(3, 9)));
         assert_eq!(result.get_hours(), 23);
     }
 
     #[test]
     fn test_hours_am() {
-        let result = interpret("5am", false, fixed_time()).unwrap();
+        let result = interpret("5am", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.bounds, Some(MatchBounds::new(0, 3)));
         assert_eq!(result.get_hours(), 5);
 
-        let result = interpret("at 5 a.m.", false, fixed_time()).unwrap();
+        let result = interpret("at 5 a.m.", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.bounds, Some(MatchBounds::new(3, 9)));
         assert_eq!(result.get_hours(), 5);
 
-        let result = interpret("at 12 a.", false, fixed_time()).unwrap();
+        let result = interpret("at 12 a.", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.bounds, Some(MatchBounds::new(3, 8)));
-        assert_eq!(result.get_hours(), 12);
+        assert_eq!(result.get_hours(), 0);
+
+        let result = interpret("12am", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 0);
+        assert_eq!(result.get_minutes(), 0);
+
+        let result = interpret("12:30am", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 0);
+        assert_eq!(result.get_minutes(), 30);
+    }
+
+    #[test]
+    fn test_hours_words() {
+        let result =
+            interpret("see you at five pm", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(11, 18)));
+        assert_eq!(result.get_hours(), 17);
+
+        let result = interpret("twelve am", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 9)));
+        assert_eq!(result.get_hours(), 0);
+
+        let result = interpret("tweleve a.m.", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 0);
+
+        let result = interpret(
+            "fiev pm",
+            &ParseConfig {
+                exact_match: true,
+                ..Default::default()
+            },
+            fixed_time(),
+        );
+        assert!(result.unwrap().bounds.is_none());
     }
 
     #[test]
     fn test_with_minutes() {
-        let result = interpret("5:30am", false, fixed_time()).unwrap();
+        let result = interpret("5:30am", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.bounds, Some(MatchBounds::new(0, 6)));
         assert_eq!(result.get_hours(), 5);
         assert_eq!(result.get_minutes(), 30);
 
-        let result = interpret("5:59 pm", false, fixed_time()).unwrap();
+        let result = interpret("5:59 pm", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.bounds, Some(MatchBounds::new(0, 7)));
         assert_eq!(result.get_hours(), 17);
         assert_eq!(result.get_minutes(), 59);
+        assert_eq!(result.get_seconds(), 0);
 
-        let result = interpret("17-59 pm", false, fixed_time()).unwrap();
-        assert_eq!(result.bounds, Some(MatchBounds::new(0, 8)));
+        let result = interpret("5-59 pm", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 7)));
         assert_eq!(result.get_hours(), 17);
         assert_eq!(result.get_minutes(), 59);
 
-        let result = interpret("up to 11-10 pm", false, fixed_time()).unwrap();
+        let result = interpret("up to 11-10 pm", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.bounds, Some(MatchBounds::new(6, 14)));
         assert_eq!(result.get_hours(), 23);
         assert_eq!(result.get_minutes(), 10);
 
-        let result = interpret("it is 10:30 o'clock", false, fixed_time()).unwrap();
+        let result = interpret("5:30pm", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 6)));
+        assert_eq!(result.get_hours(), 17);
+        assert_eq!(result.get_minutes(), 30);
+
+        let result = interpret("at 11:45 a.m.", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(3, 13)));
+        assert_eq!(result.get_hours(), 11);
+        assert_eq!(result.get_minutes(), 45);
+
+        let result =
+            interpret("it is 10:30 o'clock", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.bounds, Some(MatchBounds::new(6, 11)));
         assert_eq!(result.get_hours(), 10);
         assert_eq!(result.get_minutes(), 30);
     }
 
+    #[test]
+    fn test_quarter_half() {
+        let result = interpret("quarter past five", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 17)));
+        assert_eq!(result.get_hours(), 5);
+        assert_eq!(result.get_minutes(), 15);
+
+        let result = interpret(
+            "see you at half past nine",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(11, 25)));
+        assert_eq!(result.get_hours(), 9);
+        assert_eq!(result.get_minutes(), 30);
+
+        let result = interpret("quarter to six", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 5);
+        assert_eq!(result.get_minutes(), 45);
+
+        let result = interpret("quarter to one", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 12);
+        assert_eq!(result.get_minutes(), 45);
+
+        let result = interpret("qarter past 5 pm", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 17);
+        assert_eq!(result.get_minutes(), 15);
+
+        // the meridiem is the one of the hour the minutes are counted to
+        let result = interpret("quarter to 12 pm", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 11);
+        assert_eq!(result.get_minutes(), 45);
+
+        let result = interpret("ten to 12 am", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 23);
+        assert_eq!(result.get_minutes(), 50);
+
+        let result = interpret("quarter to 1 am", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 0);
+        assert_eq!(result.get_minutes(), 45);
+
+        let result = interpret("half past 24", &ParseConfig::default(), fixed_time());
+        assert_eq!(
+            result.unwrap_err().extract_error(),
+            invalid_time_error("half past 24", "hours", 24).extract_error()
+        );
+
+        // 12-hour clock has no hours like "13 pm"
+        let strict = ParseConfig {
+            strict_meridiem: true,
+            ..Default::default()
+        };
+        let result = interpret("half past 13 pm", &strict, fixed_time());
+        assert_eq!(
+            result.unwrap_err().extract_error(),
+            invalid_time_error("half past 13 pm", "hours", 13).extract_error()
+        );
+    }
+
+    #[test]
+    fn test_minutes_relation_24_hours() {
+        let result = interpret("half past 14", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 12)));
+        assert_eq!(result.get_hours(), 14);
+        assert_eq!(result.get_minutes(), 30);
+
+        let result = interpret("ten to 16", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 9)));
+        assert_eq!(result.get_hours(), 15);
+        assert_eq!(result.get_minutes(), 50);
+
+        let result = interpret("25 past 19", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 19);
+        assert_eq!(result.get_minutes(), 25);
+
+        let result = interpret("ten to midnight", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 15)));
+        assert_eq!(result.get_hours(), 23);
+        assert_eq!(result.get_minutes(), 50);
+
+        let result = interpret(
+            "quarter past midnight",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
+        assert_eq!(result.get_hours(), 0);
+        assert_eq!(result.get_minutes(), 15);
+
+        let result = interpret("twenty to noon", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 11);
+        assert_eq!(result.get_minutes(), 40);
+
+        let result = interpret("5 to midnight", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 23);
+        assert_eq!(result.get_minutes(), 55);
+
+        // minutes in words win over a range of hours
+        let result = interpret("five to 1", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 9)));
+        assert_eq!(result.get_hours(), 12);
+        assert_eq!(result.get_minutes(), 55);
+        assert!(result.context.end.is_none());
+
+        // numbers before a morning hour or after "to" are not minutes
+        for input in &["pages 10 to 20", "from 10 to 20", "10 past 5"] {
+            let result = interpret(input, &ParseConfig::default(), fixed_time()).unwrap();
+            assert_eq!(result.bounds, None);
+        }
+    }
+
+    #[test]
+    fn test_dot_separator() {
+        let result = interpret("5.30pm", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 6)));
+        assert_eq!(result.get_hours(), 17);
+        assert_eq!(result.get_minutes(), 30);
+
+        let result = interpret("at 14.45.", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 8)));
+        assert_eq!(result.get_hours(), 14);
+        assert_eq!(result.get_minutes(), 45);
+
+        let result = interpret("at 5.30 a.m.", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(3, 12)));
+        assert_eq!(result.get_hours(), 5);
+        assert_eq!(result.get_minutes(), 30);
+
+        let result = interpret("at 5 a.m.", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(3, 9)));
+        assert_eq!(result.get_hours(), 5);
+        assert_eq!(result.get_minutes(), 0);
+
+        let result = interpret("at 5.30", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 5);
+        assert_eq!(result.get_minutes(), 30);
+
+        // fractions, decimal numbers, prices and dates are not times
+        for input in &[
+            "1.5 liters",
+            "on 03.08.2018",
+            "5.50 dollars",
+            "pi is 3.14",
+            "costs 9.99",
+            "14.75",
+        ] {
+            let result = interpret(input, &ParseConfig::default(), fixed_time()).unwrap();
+            assert_eq!(result.bounds, None);
+        }
+    }
+
+    #[test]
+    fn test_24_hours() {
+        let result = interpret("meeting at 18:00", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(11, 16)));
+        assert_eq!(result.get_hours(), 18);
+        assert_eq!(result.get_minutes(), 0);
+
+        let result = interpret("at 0:15", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 0);
+        assert_eq!(result.get_minutes(), 15);
+
+        let result = interpret("23-59", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 23);
+        assert_eq!(result.get_minutes(), 59);
+
+        // meridiem path is still used when am/pm is present
+        let result = interpret("5pm", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 17);
+    }
+
     #[test]
     fn test_wrong_times() {
-        let result = interpret("24:10", false, fixed_time());
+        let result = interpret("24:10", &ParseConfig::default(), fixed_time());
         assert_eq!(
             result.unwrap_err().extract_error(),
             invalid_time_error("24:10", "hours", 24).extract_error()
         );
 
-        let result = interpret("12:60", false, fixed_time());
+        let result = interpret("12:60", &ParseConfig::default(), fixed_time());
         assert_eq!(
             result.unwrap_err().extract_error(),
             invalid_time_error("12:60", "minutes", 60).extract_error()
         );
+
+        let result = interpret("5:75pm", &ParseConfig::default(), fixed_time());
+        assert_eq!(
+            result.unwrap_err().extract_error(),
+            invalid_time_error("5:75pm", "minutes", 75).extract_error()
+        );
+
+        let strict = ParseConfig {
+            strict_meridiem: true,
+            ..Default::default()
+        };
+        let result = interpret("13 pm", &strict, fixed_time());
+        assert_eq!(
+            result.unwrap_err().extract_error(),
+            invalid_time_error("13 pm", "hours", 13).extract_error()
+        );
+
+        let result = interpret("17-59 pm", &strict, fixed_time());
+        assert_eq!(
+            result.unwrap_err().extract_error(),
+            invalid_time_error("17-59 pm", "hours", 17).extract_error()
+        );
     }
 
+    #[test]
+    fn test_redundant_meridiem() {
+        // the hour is on the 24-hour clock already
+        let result = interpret("at 18:00 pm", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(3, 11)));
+        assert_eq!(result.get_hours(), 18);
+        assert_eq!(result.get_minutes(), 0);
+
+        let result = interpret("13 pm", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 13);
+
+        let result = interpret("17:30 am", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 17);
+        assert_eq!(result.get_minutes(), 30);
+
+        let strict = ParseConfig {
+            strict_meridiem: true,
+            ..Default::default()
+        };
+        let result = interpret("at 18:00 pm", &strict, fixed_time());
+        assert_eq!(
+            result.unwrap_err().extract_error(),
+            invalid_time_error("18:00 pm", "hours", 18).extract_error()
+        );
+
+        // meridiem of the 12-hour clock is fine anyway
+        let result = interpret("6:00 pm", &strict, fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 18);
+    }
+
+    #[test]
+    fn test_ranges() {
+        let result = interpret("from 9am to 5pm", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 15)));
+        assert_eq!(result.get_hours(), 9);
+        assert_eq!(result.context.end.unwrap().hour, Some(17));
+
+        // a range with no meridiem ending before its start ends in the afternoon
+        let result = interpret("9 to 5", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 6)));
+        assert_eq!(result.get_hours(), 9);
+        assert_eq!(result.context.end.unwrap().hour, Some(17));
+
+        let result = interpret("nine until eleven", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 9);
+        assert_eq!(result.context.end.unwrap().hour, Some(11));
+
+        // meridiem of one end applies to another one
+        let result = interpret("from 9 to 11pm", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 14)));
+        assert_eq!(result.get_hours(), 21);
+        assert_eq!(result.context.end.unwrap().hour, Some(23));
+
+        // a range goes forward, so its start may be before noon
+        let result = interpret("9 to 5pm", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 8)));
+        assert_eq!(result.get_hours(), 9);
+        assert_eq!(result.context.end.unwrap().hour, Some(17));
+
+        let result = interpret("11 to 2pm", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 11);
+        assert_eq!(result.context.end.unwrap().hour, Some(14));
+
+        let result = interpret("12-5pm", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 12);
+        assert_eq!(result.context.end.unwrap().hour, Some(17));
+
+        let result = interpret("from 9am to 12", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 9);
+        assert_eq!(result.context.end.unwrap().hour, Some(12));
+
+        let result = interpret("10pm to 2", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 22);
+        assert_eq!(result.context.end.unwrap().hour, Some(2));
+
+        let result = interpret("call me 10am-2pm", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(8, 16)));
+        assert_eq!(result.get_hours(), 10);
+        assert_eq!(result.context.end.unwrap().hour, Some(14));
+
+        let result = interpret("9-5", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 9);
+        assert_eq!(result.context.end.unwrap().hour, Some(17));
+
+        let result = interpret("12 to 5", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 12);
+        assert_eq!(result.context.end.unwrap().hour, Some(17));
+
+        // a number with a unit after a dash is an amount rather than the end of a range
+        let result = interpret("5pm - 5 minutes", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 3)));
+        assert_eq!(result.get_hours(), 17);
+        assert!(result.context.end.is_none());
+
+        // two digits after dash are minutes
+        let result = interpret("9-05", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 9);
+        assert_eq!(result.get_minutes(), 5);
+        assert!(result.context.end.is_none());
+    }
+
+    #[test]
+    fn test_between_range() {
+        let result = interpret(
+            "call between 2 and 4pm",
+            &ParseConfig::default(),
+            fixed_time(),
+        );
+        let result = result.unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(5, 22)));
+        assert_eq!(result.get_hours(), 14);
+        assert_eq!(result.context.end.unwrap().hour, Some(16));
+
+        let result = interpret("between 9am and 5pm", &ParseConfig::default(), fixed_time());
+        let result = result.unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 19)));
+        assert_eq!(result.get_hours(), 9);
+        assert_eq!(result.context.end.unwrap().hour, Some(17));
+
+        // "and" alone doesn't make a range
+        let result = interpret("2 and 4pm", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(6, 9)));
+        assert!(result.context.end.is_none());
+    }
+
+    #[test]
+    fn test_timezone() {
+        let result = interpret("5pm est", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 7)));
+        assert_eq!(result.get_hours(), 17);
+        assert_eq!(result.context.utc_offset, Some(-5 * 3600));
+
+        let result = interpret("at 9 am utc", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(3, 11)));
+        assert_eq!(result.get_hours(), 9);
+        assert_eq!(result.context.utc_offset, Some(0));
+
+        let result = interpret("10:30 cet", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_minutes(), 30);
+        assert_eq!(result.context.utc_offset, Some(3600));
+
+        let result = interpret("5-30 gmt", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 8)));
+        assert_eq!(result.get_hours(), 5);
+        assert_eq!(result.get_minutes(), 30);
+        assert_eq!(result.context.utc_offset, Some(0));
+
+        // unknown abbreviation is not a part of the match
+        let result = interpret("at 5pm xyz", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(3, 6)));
+        assert_eq!(result.get_hours(), 17);
+        assert_eq!(result.context.utc_offset, None);
+    }
+
+    #[test]
+    fn test_oclock() {
+        let result = interpret("five o'clock", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 12)));
+        assert_eq!(result.get_hours(), 5);
+        assert_eq!(result.get_minutes(), 0);
+
+        let result = interpret("at 8 oclock", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(3, 11)));
+        assert_eq!(result.get_hours(), 8);
+
+        let result = interpret("at 8 o clock", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(3, 12)));
+        assert_eq!(result.get_hours(), 8);
+
+        // the nearest occurrence from now on
+        let afternoon = Local.ymd(2019, 1, 1).and_hms(15, 30, 0);
+        let result = interpret("five o'clock", &ParseConfig::default(), afternoon).unwrap();
+        assert_eq!(result.get_hours(), 17);
+        assert_eq!(result.get_duration_sec(), 0);
+
+        let result = interpret("two o'clock", &ParseConfig::default(), afternoon).unwrap();
+        assert_eq!(result.get_hours(), 2);
+        assert_eq!(result.get_duration_sec(), 24 * 3600);
+
+        let result = interpret("twelve o'clock", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_hours(), 12);
+    }
+
+    #[test]
+    fn test_bare_hour() {
+        let afternoon = Local.ymd(2019, 1, 1).and_hms(15, 0, 0);
+        let result = interpret("at 5", &ParseConfig::default(), afternoon).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 4)));
+        assert_eq!(result.get_hours(), 17);
+        assert_eq!(result.get_minutes(), 0);
+        assert_eq!(result.get_duration_sec(), 0);
+
+        let result = interpret("see you at nine", &ParseConfig::default(), afternoon).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(8, 15)));
+        assert_eq!(result.get_hours(), 21);
+
+        let evening = Local.ymd(2019, 1, 1).and_hms(19, 0, 0);
+        let result = interpret("at 5", &ParseConfig::default(), evening).unwrap();
+        assert_eq!(result.get_hours(), 5);
+        assert_eq!(result.get_duration_sec(), 24 * 3600);
+
+        let result = interpret("at 17", &ParseConfig::default(), evening).unwrap();
+        assert_eq!(result.get_hours(), 17);
+        assert_eq!(result.get_duration_sec(), 24 * 3600);
+
+        // explicit meridiem and minutes are recognized as usual
+        let result = interpret("at 5 am", &ParseConfig::default(), afternoon).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(3, 7)));
+        assert_eq!(result.get_hours(), 5);
+        assert_eq!(result.get_duration_sec(), 0);
+
+        let result = interpret("at 5:30", &ParseConfig::default(), afternoon).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(3, 7)));
+        assert_eq!(result.get_hours(), 5);
+        assert_eq!(result.get_minutes(), 30);
+
+        // numbers out of a day's hours are not times
+        for input in &["at 25", "at 100 km", "at 1999 prices"] {
+            let result = interpret(input, &ParseConfig::default(), afternoon).unwrap();
+            assert_eq!(result.bounds, None);
+        }
+    }
+
+    #[test]
+    fn test_bare_hour_today() {
+        let config = ParseConfig {
+            prefer_future: false,
+            ..Default::default()
+        };
+
+        // the upcoming occurrence is still preferred within the day
+        let afternoon = Local.ymd(2019, 1, 1).and_hms(15, 0, 0);
+        let result = interpret("at 5", &config, afternoon).unwrap();
+        assert_eq!(result.get_hours(), 17);
+        assert_eq!(result.get_duration_sec(), 0);
+
+        let evening = Local.ymd(2019, 1, 1).and_hms(19, 0, 0);
+        let result = interpret("at 5", &config, evening).unwrap();
+        assert_eq!(result.get_hours(), 17);
+        assert_eq!(result.get_duration_sec(), 0);
+
+        let result = interpret("two o'clock", &config, evening).unwrap();
+        assert_eq!(result.get_hours(), 14);
+        assert_eq!(result.get_duration_sec(), 0);
+
+        let result = interpret("at 17", &config, evening).unwrap();
+        assert_eq!(result.get_hours(), 17);
+        assert_eq!(result.get_duration_sec(), 0);
+    }
+
+    #[test]
+    fn test_part_of_day_meridiem() {
+        let result = interpret("5 in the afternoon", &ParseConfig::default(), fixed_time());
+        let result = result.unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 18)));
+        assert_eq!(result.get_hours(), 17);
+        assert_eq!(result.get_minutes(), 0);
+
+        let result = interpret("3 in the morning", &ParseConfig::default(), fixed_time());
+        let result = result.unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 16)));
+        assert_eq!(result.get_hours(), 3);
+
+        // the part of the day is not a bare hour, it happens on the current day
+        let evening = Local.ymd(2019, 1, 1).and_hms(19, 0, 0);
+        let result = interpret("at 3 in the morning", &ParseConfig::default(), evening);
+        let result = result.unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(3, 19)));
+        assert_eq!(result.get_hours(), 3);
+        assert_eq!(result.get_duration_sec(), 0);
+
+        let result = interpret("7:30 in the evening", &ParseConfig::default(), fixed_time());
+        let result = result.unwrap();
+        assert_eq!(result.get_hours(), 19);
+        assert_eq!(result.get_minutes(), 30);
+
+        let result = interpret(
+            "half past ten in the night",
+            &ParseConfig::default(),
+            fixed_time(),
+        );
+        let result = result.unwrap();
+        assert_eq!(result.get_hours(), 22);
+        assert_eq!(result.get_minutes(), 30);
+    }
 }
@@ -0,0 +1,184 @@
+use super::combinators::{stub, tokenize_count_symbols, tokenize_word, wrap_error, Dist};
+use super::config::ParseConfig;
+use super::errors::{invalid_date_error, SemanticError, UNKNOWN};
+use super::rules::{Context, MyResult, RuleResult, TokenDesc};
+use super::tokens::{PToken, Priority, TimeInterval, Token};
+use chrono::prelude::*;
+
+use nom::{
+    alt, apply, call, many_till, named_args, not, terminated, tuple, types::CompleteStr, IResult,
+};
+
+// no typos, otherwise "weeks 3" or "next wek 5pm" become week numbers
+define!(week: (Token::TimeInterval(TimeInterval::Week), Priority(0)), "week", Dist(0));
+
+define_num!(number: (Token::Number, Priority(1)));
+
+/// Recognizes ISO notation of a week, e.g. "W05", the number is glued to the letter
+fn iso_week(input: CompleteStr) -> MyResult {
+    if let Ok((tail, word)) = tokenize_word(input) {
+        let word = word.to_lowercase();
+        if word.starts_with('w') && word.len() > 1 && word.len() <= 3 {
+            if let Ok(n) = word[1..].parse::<i32>() {
+                return Ok((
+                    tail,
+                    TokenDesc::new(PToken::PToken(Token::Number(n), Priority(1)), Dist(0)),
+                ));
+            }
+        }
+    }
+    wrap_error(input, UNKNOWN)
+}
+
+// words which make "week" a duration or a relative week, e.g. "next week 5pm", "in 2 weeks 3 days"
+const WEEK_QUALIFIERS: &[&str] = &[
+    "next", "this", "last", "past", "previous", "coming", "every", "each", "per", "a", "an", "one",
+    "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "few", "couple",
+    "several",
+];
+
+// words which make the number after "week" something else, e.g. "week 3 days", "week 5pm"
+const NUMBER_SUFFIXES: &[&str] = &[
+    "am", "pm", "h", "hr", "hrs", "hour", "hours", "min", "mins", "minute", "minutes", "sec",
+    "secs", "second", "seconds", "day", "days", "week", "weeks", "month", "months", "year",
+    "years", "time", "times",
+];
+
+/// Skips a word like tokenize_count_symbols, a qualifier of "week" is skipped along with it, so
+/// that the week is not taken for a week number.
+fn skip_word(input: CompleteStr) -> IResult<CompleteStr, usize> {
+    if let Ok((tail, word)) = tokenize_word(input) {
+        let word = word.to_lowercase();
+        let is_qualifier = WEEK_QUALIFIERS.contains(&word.as_str())
+            || (!word.is_empty() && word.chars().all(|c| c.is_ascii_digit()));
+        if is_qualifier {
+            if let Ok((tail, next)) = tokenize_word(tail) {
+                let next = next.to_lowercase();
+                if next == "week" || next == "weeks" {
+                    return Ok((tail, input.len() - tail.len()));
+                }
+            }
+        }
+    }
+    tokenize_count_symbols(input)
+}
+
+/// Recognizes what turns a number into a time or an amount, e.g. "5pm", "10:00", "3 days".
+fn number_suffix(input: CompleteStr) -> MyResult {
+    if input.trim_start().starts_with(':') {
+        return Ok((input, TokenDesc::new(PToken::Stub, Dist(0))));
+    }
+    if let Ok((tail, word)) = tokenize_word(input) {
+        if NUMBER_SUFFIXES.contains(&word.to_lowercase().as_str()) {
+            return Ok((tail, TokenDesc::new(PToken::Stub, Dist(0))));
+        }
+    }
+    wrap_error(input, UNKNOWN)
+}
+
+named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
+                             ( TokenDesc, TokenDesc ) )>,
+    many_till!(skip_word,
+        alt!(
+            // e.g. "week 5"
+            tuple!(apply!(week, dist_limit), terminated!(number, not!(number_suffix))) |
+            // e.g. "W05"
+            tuple!(iso_week, stub)
+        )
+    )
+);
+
+make_interpreter!(positions = 2);
+
+fn make_time<'a, 'b, Tz: TimeZone>(
+    res: &'a RuleResult,
+    tz_aware: DateTime<Tz>,
+    input: &'b str,
+    _config: &ParseConfig,
+) -> Result<Context, SemanticError<'b>> {
+    let mut ctx = Context::default();
+
+    let mut week = 0;
+    if let Some(Token::Number(n)) = res.token_by_priority(Priority(1)) {
+        week = n;
+    }
+
+    if week < 1 || week > 53 {
+        return Err(invalid_date_error(input, "week", week));
+    }
+
+    // monday of the ISO week, it may belong to the previous year, some years have no 53rd week
+    let monday = NaiveDate::from_isoywd_opt(tz_aware.year(), week as u32, Weekday::Mon)
+        .ok_or_else(|| invalid_date_error(input, "week", week))?;
+
+    ctx.year = Some(monday.year());
+    ctx.month = Some(monday.month() as i32);
+    ctx.day = Some(monday.day() as i32);
+
+    Ok(ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::interpret;
+    use crate::rules::config::ParseConfig;
+    use crate::rules::errors::invalid_date_error;
+    use crate::rules::rules::MatchBounds;
+    use chrono::prelude::*;
+
+    fn fixed_time() -> DateTime<Local> {
+        Local.ymd(2019, 1, 1).and_hms(0, 0, 0)
+    }
+
+    #[test]
+    fn test_week_number() {
+        let result = interpret("report for week 5", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(11, 17)));
+        assert_eq!(result.get_year(), 2019);
+        assert_eq!(result.get_month(), 1);
+        assert_eq!(result.get_day(), 28);
+
+        let result = interpret("w05", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 3)));
+        assert_eq!(result.get_day(), 28);
+
+        // the first ISO week of 2019 starts in 2018
+        let result = interpret("week 1", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_year(), 2018);
+        assert_eq!(result.get_month(), 12);
+        assert_eq!(result.get_day(), 31);
+    }
+
+    #[test]
+    fn test_not_week_number() {
+        for input in &[
+            "next week 10:00",
+            "next week 5pm",
+            "this week 3pm",
+            "in 1 week 2 days",
+            "in 2 weeks 3 days",
+            "last week 3 times",
+            "weeks 3",
+            "week 3 days",
+        ] {
+            let result = interpret(input, &ParseConfig::default(), fixed_time()).unwrap();
+            assert_eq!(result.bounds, None, "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_invalid_week() {
+        let result = interpret("week 99", &ParseConfig::default(), fixed_time());
+        assert_eq!(
+            result.unwrap_err().extract_error(),
+            invalid_date_error("week 99", "week", 99).extract_error()
+        );
+
+        // 2019 has 52 ISO weeks only
+        let result = interpret("week 53", &ParseConfig::default(), fixed_time());
+        assert_eq!(
+            result.unwrap_err().extract_error(),
+            invalid_date_error("week 53", "week", 53).extract_error()
+        );
+    }
+}
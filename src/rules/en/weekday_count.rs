@@ -0,0 +1,123 @@
+use super::combinators::{stub, tokenize_count_symbols, Dist};
+use super::common_matchers::match_num;
+use super::config::ParseConfig;
+use super::errors::{invalid_time_error, SemanticError};
+use super::rules::{Context, RuleResult, TokenDesc};
+use super::tokens::{IntWord, Prepositions, Priority, Token, When};
+use super::weekdays::{day_number, day_of_week};
+use chrono::prelude::*;
+use time::Duration;
+
+use nom::{alt, apply, call, many_till, named_args, tuple, types::CompleteStr};
+
+define!(one: (Token::IntWord(IntWord::One), Priority(1)), "one", Dist(0));
+define!(two: (Token::IntWord(IntWord::Two), Priority(1)), "two", Dist(0));
+define!(three: (Token::IntWord(IntWord::Three), Priority(1)), "three", Dist(1));
+define!(four: (Token::IntWord(IntWord::Four), Priority(1)), "four", Dist(1));
+define!(five: (Token::IntWord(IntWord::Five), Priority(1)), "five", Dist(1));
+define!(six: (Token::IntWord(IntWord::Six), Priority(1)), "six", Dist(0));
+define!(seven: (Token::IntWord(IntWord::Seven), Priority(1)), "seven", Dist(1));
+define!(eight: (Token::IntWord(IntWord::Eight), Priority(1)), "eight", Dist(1));
+define!(nine: (Token::IntWord(IntWord::Nine), Priority(1)), "nine", Dist(1));
+define!(ten: (Token::IntWord(IntWord::Ten), Priority(1)), "ten", Dist(0));
+
+combine!(int_word => one | two | three | four | five | six | seven | eight | nine | ten);
+
+define_num!(number: (Token::Number, Priority(1)));
+
+named_args!(count<'a>(dist_limit: Dist)<CompleteStr<'a>, TokenDesc>,
+    alt!(number | apply!(int_word, dist_limit))
+);
+
+define!(from: (Token::Prepositions(Prepositions::From), Priority(2)), "from", Dist(1));
+define!(now: (Token::When(When::Now), Priority(3)), "now", Dist(0));
+define!(
+    ahead:
+    [(Token::When(When::Next), Priority(2)), "ahead", Dist(1)] |
+    [(Token::When(When::Next), Priority(2)), "later", Dist(1)]
+);
+
+// weekdays go in plural, e.g. "fridays", which fits into the distances of the weekday words
+
+named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
+                             ( TokenDesc, TokenDesc, TokenDesc, TokenDesc ) )>,
+    many_till!(tokenize_count_symbols,
+        alt!(
+            // e.g. "two fridays from now"
+            tuple!(apply!(count, dist_limit), apply!(day_of_week, dist_limit),
+                   apply!(from, dist_limit), apply!(now, dist_limit)) |
+            // e.g. "3 mondays ahead", "two sundays later"
+            tuple!(apply!(count, dist_limit), apply!(day_of_week, dist_limit),
+                   apply!(ahead, dist_limit), stub)
+        )
+    )
+);
+
+make_interpreter!(positions = 4);
+
+fn make_time<'a, 'b, Tz: TimeZone>(
+    res: &'a RuleResult,
+    tz_aware: DateTime<Tz>,
+    input: &'b str,
+    _config: &ParseConfig,
+) -> Result<Context, SemanticError<'b>> {
+    let mut ctx = Context::default();
+
+    let count = match_num(res.token_by_priority(Priority(1))).unwrap_or(0);
+    if count < 1 {
+        return Err(invalid_time_error(input, "count", count));
+    }
+
+    let day = match res.token_by_priority(Priority(0)) {
+        Some(token) => day_number(&token),
+        None => return Ok(ctx),
+    };
+
+    // the first one is the same as "next friday", all the others are a week apart
+    let mut delta = day - i64::from(tz_aware.weekday().num_days_from_monday());
+    if delta <= 0 {
+        delta += 7;
+    }
+    ctx.set_duration(Duration::days(delta + 7 * i64::from(count - 1)).num_seconds());
+
+    Ok(ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::interpret;
+    use crate::rules::config::ParseConfig;
+    use crate::rules::consts;
+    use crate::rules::rules::MatchBounds;
+    use chrono::prelude::*;
+
+    fn fixed_time() -> DateTime<Local> {
+        // 2019 1st January, Tuesday
+        Local.ymd(2019, 1, 1).and_hms(0, 0, 0)
+    }
+
+    #[test]
+    fn test_weekday_count() {
+        let result =
+            interpret("one friday from now", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 19)));
+        assert_eq!(result.get_duration_sec(), 3 * consts::DAY as i64);
+
+        let result = interpret(
+            "see you two fridays from now",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(8, 28)));
+        assert_eq!(result.get_duration_sec(), 10 * consts::DAY as i64);
+
+        let result = interpret("3 mondays ahead", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 15)));
+        assert_eq!(result.get_duration_sec(), 20 * consts::DAY as i64);
+
+        // the same weekday as today is a week away
+        let result = interpret("one tuesday later", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 7 * consts::DAY as i64);
+    }
+}
@@ -0,0 +1,128 @@
+use super::combinators::{stub, tokenize_count_symbols, tokenize_word, wrap_error, Dist};
+use super::config::ParseConfig;
+use super::consts;
+use super::errors::{SemanticError, UNKNOWN};
+use super::rules::{Context, MyResult, RuleResult, TokenDesc};
+use super::tokens::Priority;
+use super::weekdays::{day_number, day_of_week};
+use chrono::prelude::*;
+
+use nom::{alt, apply, call, many_till, named_args, tuple, types::CompleteStr};
+
+/// Recognizes a day of week which continues a list, it must follow a comma or "and", e.g.
+/// ", wednesday" or " and friday".
+fn next_item(input: CompleteStr, dist_limit: Dist) -> MyResult {
+    let mut rest = input.trim_start();
+    let has_comma = rest.starts_with(',');
+    if has_comma {
+        rest = &rest[1..];
+    }
+
+    let mut has_and = false;
+    if let Ok((tail, word)) = tokenize_word(CompleteStr(rest)) {
+        if word.to_lowercase() == "and" {
+            has_and = true;
+            rest = &tail;
+        }
+    }
+
+    if !has_comma && !has_and {
+        return wrap_error(input, UNKNOWN);
+    }
+
+    day_of_week(CompleteStr(rest), dist_limit)
+}
+
+named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
+                             ( TokenDesc, TokenDesc, TokenDesc, TokenDesc, TokenDesc, TokenDesc,
+                               TokenDesc ) )>,
+    many_till!(tokenize_count_symbols,
+        alt!(
+            // e.g. "monday and friday", "monday, wednesday and friday"
+            tuple!(apply!(day_of_week, dist_limit), call!(next_item, dist_limit),
+                   alt!(call!(next_item, dist_limit) | stub),
+                   alt!(call!(next_item, dist_limit) | stub),
+                   alt!(call!(next_item, dist_limit) | stub),
+                   alt!(call!(next_item, dist_limit) | stub),
+                   alt!(call!(next_item, dist_limit) | stub))
+        )
+    )
+);
+
+make_interpreter!(positions = 7);
+
+/// Every day of a list is the nearest one, today included, just like the start of a weekdays
+/// range. The first day is described by the context itself and the rest by its list.
+fn make_time<'a, 'b, Tz: TimeZone>(
+    res: &'a RuleResult,
+    tz_aware: DateTime<Tz>,
+    _input: &'b str,
+    _config: &ParseConfig,
+) -> Result<Context, SemanticError<'b>> {
+    let weekday = i64::from(tz_aware.weekday().num_days_from_monday());
+
+    let mut items = res
+        .tokens_by_priority(Priority(0))
+        .iter()
+        .map(|token| {
+            let days = (7 + day_number(token) - weekday) % 7;
+            let mut item = Context::default();
+            item.set_duration(days * i64::from(consts::DAY));
+            item
+        })
+        .collect::<Vec<_>>();
+
+    let mut ctx = items.remove(0);
+    ctx.list = items;
+
+    Ok(ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::interpret;
+    use crate::rules::config::ParseConfig;
+    use crate::rules::consts;
+    use crate::rules::rules::MatchBounds;
+    use chrono::prelude::*;
+
+    fn fixed_time() -> DateTime<Local> {
+        // tuesday
+        Local.ymd(2019, 1, 1).and_hms(0, 0, 0)
+    }
+
+    fn days(n: i32) -> i64 {
+        i64::from(n * consts::DAY)
+    }
+
+    #[test]
+    fn test_weekday_list() {
+        let result = interpret(
+            "gym on monday, wednesday and friday",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(7, 35)));
+        assert_eq!(result.get_duration_sec(), days(6));
+        let list: Vec<i64> = result
+            .context
+            .list
+            .iter()
+            .map(|item| item.duration.num_seconds())
+            .collect();
+        assert_eq!(list, vec![days(1), days(3)]);
+
+        let result = interpret("tue and sat", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 11)));
+        assert_eq!(result.get_duration_sec(), 0);
+        assert_eq!(result.context.list.len(), 1);
+
+        // a single day or days without a connector are not a list
+        let result = interpret("monday", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, None);
+
+        let result = interpret("monday friday", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, None);
+    }
+}
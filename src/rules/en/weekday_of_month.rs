@@ -0,0 +1,182 @@
+use super::combinators::{stub, tokenize_count_symbols, Dist};
+use super::common::days_in_month;
+use super::common_matchers::match_ordinal;
+use super::config::ParseConfig;
+use super::errors::{invalid_date_error, SemanticError};
+use super::exact_month_date::{month, month_number};
+use super::rules::{Context, RuleResult, TokenDesc};
+use super::tokens::{Articles, Ordinals, Prepositions, Priority, TimeInterval, Token, When};
+use super::weekdays::{day_number, day_of_week};
+use chrono::prelude::*;
+
+use nom::{alt, apply, call, many_till, named_args, tuple, types::CompleteStr};
+
+define!(
+    position:
+    [(Token::Ordinals(Ordinals::First), Priority(1)), "first", Dist(1)] |
+    [(Token::Ordinals(Ordinals::First), Priority(1)), "1st", Dist(0)] |
+    [(Token::Ordinals(Ordinals::Second), Priority(1)), "second", Dist(1)] |
+    [(Token::Ordinals(Ordinals::Second), Priority(1)), "2nd", Dist(0)] |
+    [(Token::Ordinals(Ordinals::Third), Priority(1)), "third", Dist(1)] |
+    [(Token::Ordinals(Ordinals::Third), Priority(1)), "3rd", Dist(0)] |
+    [(Token::Ordinals(Ordinals::Fourth), Priority(1)), "fourth", Dist(1)] |
+    [(Token::Ordinals(Ordinals::Fourth), Priority(1)), "4th", Dist(0)] |
+    [(Token::Ordinals(Ordinals::Fifth), Priority(1)), "fifth", Dist(1)] |
+    [(Token::Ordinals(Ordinals::Fifth), Priority(1)), "5th", Dist(0)] |
+    [(Token::When(When::Last), Priority(1)), "last", Dist(1)]
+);
+
+define!(of: (Token::Prepositions(Prepositions::Of), Priority(2)), "of", Dist(0));
+define!(the: (Token::Articles(Articles::The), Priority(3)), "the", Dist(0));
+define!(month_word: (Token::TimeInterval(TimeInterval::Month), Priority(4)), "month", Dist(1));
+define!(leading_the: (Token::Articles(Articles::The), Priority(6)), "the", Dist(0));
+
+named_args!(the_prefix<'a>(dist_limit: Dist)<CompleteStr<'a>, TokenDesc>,
+    alt!(apply!(leading_the, dist_limit) | stub)
+);
+
+// month names go with Priority(5)
+named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
+                             ( TokenDesc, TokenDesc, TokenDesc, TokenDesc, TokenDesc, TokenDesc ) )>,
+    many_till!(tokenize_count_symbols,
+        alt!(
+            // e.g. "the first monday of the month"
+            tuple!(apply!(the_prefix, dist_limit), apply!(position, dist_limit),
+                   apply!(day_of_week, dist_limit), apply!(of, dist_limit),
+                   apply!(the, dist_limit), apply!(month_word, dist_limit)) |
+            // e.g. "last friday of march"
+            tuple!(apply!(the_prefix, dist_limit), apply!(position, dist_limit),
+                   apply!(day_of_week, dist_limit), apply!(of, dist_limit),
+                   apply!(month, dist_limit), stub)
+        )
+    )
+);
+
+make_interpreter!(positions = 6);
+
+/// "The month" is the current one, a month name is the one of the current year just like in
+/// "march 4". The fifth weekday doesn't exist in every month, it is an error then.
+fn make_time<'a, 'b, Tz: TimeZone>(
+    res: &'a RuleResult,
+    tz_aware: DateTime<Tz>,
+    input: &'b str,
+    _config: &ParseConfig,
+) -> Result<Context, SemanticError<'b>> {
+    let mut ctx = Context::default();
+
+    let year = tz_aware.year();
+    let month = match res.token_by_priority(Priority(5)) {
+        Some(token) => month_number(&token).unwrap_or(1) as u32,
+        None => tz_aware.month(),
+    };
+    let weekday = match res.token_by_priority(Priority(0)) {
+        Some(token) => day_number(&token) as u32,
+        None => return Ok(ctx),
+    };
+
+    let last_day = days_in_month(year, month);
+    let first = match NaiveDate::from_ymd_opt(year, month, 1) {
+        Some(date) => date.weekday().num_days_from_monday(),
+        None => return Err(invalid_date_error(input, "month", month as i32)),
+    };
+    let day = match res.token_by_priority(Priority(1)) {
+        Some(Token::When(When::Last)) => {
+            // scan backward from the end of the month
+            let last = (first + last_day - 1) % 7;
+            last_day - (7 + last - weekday) % 7
+        }
+        token => {
+            let n = match_ordinal(token).unwrap_or(1) as u32;
+            let day = 1 + (7 + weekday - first) % 7 + 7 * (n - 1);
+            // the month has no such occurrence of the weekday, e.g. "fifth monday of february"
+            if day > last_day {
+                return Err(invalid_date_error(input, "occurrence", n as i32));
+            }
+            day
+        }
+    };
+
+    ctx.month = Some(month as i32);
+    ctx.day = Some(day as i32);
+
+    Ok(ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::interpret;
+    use crate::rules::config::ParseConfig;
+    use crate::rules::errors::invalid_date_error;
+    use crate::rules::rules::MatchBounds;
+    use chrono::prelude::*;
+
+    fn fixed_time() -> DateTime<Local> {
+        // 2019 1st January, Tuesday
+        Local.ymd(2019, 1, 1).and_hms(0, 0, 0)
+    }
+
+    #[test]
+    fn test_weekday_of_month() {
+        let result = interpret(
+            "the first monday of the month",
+            &ParseConfig::default(),
+            fixed_time(),
+        );
+        let result = result.unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 29)));
+        assert_eq!(result.get_month(), 1);
+        assert_eq!(result.get_day(), 7);
+
+        let result = interpret(
+            "last friday of march",
+            &ParseConfig::default(),
+            fixed_time(),
+        );
+        let result = result.unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 20)));
+        assert_eq!(result.get_month(), 3);
+        assert_eq!(result.get_day(), 29);
+
+        // the month begins with the weekday
+        let result = interpret(
+            "1st tuesday of the month",
+            &ParseConfig::default(),
+            fixed_time(),
+        );
+        assert_eq!(result.unwrap().get_day(), 1);
+
+        let result = interpret(
+            "last sunday of february",
+            &ParseConfig::default(),
+            fixed_time(),
+        );
+        assert_eq!(result.unwrap().get_day(), 24);
+
+        let result = interpret(
+            "third wed of the month",
+            &ParseConfig::default(),
+            fixed_time(),
+        );
+        assert_eq!(result.unwrap().get_day(), 16);
+    }
+
+    #[test]
+    fn test_fifth_weekday() {
+        let result = interpret(
+            "fifth thursday of january",
+            &ParseConfig::default(),
+            fixed_time(),
+        );
+        assert_eq!(result.unwrap().get_day(), 31);
+
+        let result = interpret(
+            "fifth monday of february",
+            &ParseConfig::default(),
+            fixed_time(),
+        );
+        assert_eq!(
+            result.unwrap_err().extract_error(),
+            invalid_date_error("fifth monday of february", "occurrence", 5).extract_error()
+        );
+    }
+}
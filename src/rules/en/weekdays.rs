@@ -1,7 +1,8 @@
 use super::combinators::{stub, tokenize_count_symbols, Dist};
-use super::errors::{ambiguous_time_error, SemanticError};
-use super::rules::{Context, RuleResult, TokenDesc};
-use super::tokens::{Priority, Token, Weekday as Day, When};
+use super::config::ParseConfig;
+use super::errors::{ambiguous_time_error, invalid_time_error, SemanticError};
+use super::rules::{Context, Recurrence, RuleResult, TokenDesc};
+use super::tokens::{Prepositions, Priority, TimeInterval, Token, Weekday as Day, When};
 use chrono::prelude::*;
 use time::Duration;
 
@@ -59,63 +60,128 @@ define!(this: (Token::When(When::This), Priority(1)), "this", Dist(1));
 define!(last: (Token::When(When::Last), Priority(1)), "last", Dist(1));
 define!(past: (Token::When(When::Past), Priority(1)), "past", Dist(1));
 define!(next: (Token::When(When::Next), Priority(1)), "next", Dist(1));
+// "coming" is the same as "next", one typo at most, so that it doesn't take other words for it
+define!(coming: (Token::When(When::Next), Priority(1)), "coming", Dist(1));
 
-combine!(when => this | last | past | next);
+combine!(when => this | last | past | next | coming);
 
-define!(week_noun: (Token::Week, Priority(2)), "week", Dist(1));
+// "this" before "coming" changes nothing, e.g. "this coming friday"
+define!(this_coming: (Token::When(When::This), Priority(7)), "this", Dist(1));
 
-named_args!(parse<'a>(exact_match: bool)<CompleteStr<'a>, (Vec<usize>,
-                             ( TokenDesc, TokenDesc, TokenDesc ) )>,
+define!(week_noun: (Token::TimeInterval(TimeInterval::Week), Priority(2)), "week", Dist(1));
+
+// range connectors, e.g. "monday to friday", "mon-fri", "between monday and thursday"
+
+define!(
+    range_word:
+    [(Token::Range, Priority(3)), "to", Dist(0)] |
+    [(Token::Range, Priority(3)), "through", Dist(2)] |
+    [(Token::Range, Priority(3)), "until", Dist(1)]
+);
+
+define_char!(dash: Priority(3), '-');
+
+define!(between: (Token::Prepositions(Prepositions::Between), Priority(6)), "between", Dist(2));
+// "and" is a range connector after "between" only, otherwise it joins list items
+define!(range_and: (Token::Range, Priority(3)), "and", Dist(0));
+
+// repetitions, e.g. "every monday", "every other friday"
+
+define!(
+    every:
+    [(Token::Every, Priority(4)), "every", Dist(1)] |
+    [(Token::Every, Priority(4)), "each", Dist(0)]
+);
+define!(other: (Token::Other, Priority(5)), "other", Dist(1));
+
+named_args!(range<'a>(dist_limit: Dist)<CompleteStr<'a>, TokenDesc>,
+    alt!(apply!(range_word, dist_limit) | dash)
+);
+
+named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
+                             ( TokenDesc, TokenDesc, TokenDesc, TokenDesc ) )>,
     many_till!(tokenize_count_symbols,
         alt!(
+            // every, optional other, day of week, e.g. "every other monday"
+            tuple!(apply!(every, dist_limit), alt!(apply!(other, dist_limit) | stub),
+                   apply!(day_of_week, dist_limit), stub) |
+            // between, day of week, "and", day of week, e.g. "between monday and thursday"
+            tuple!(apply!(between, dist_limit), apply!(day_of_week, dist_limit),
+                   apply!(range_and, dist_limit), apply!(day_of_week, dist_limit)) |
+            // day of week, connector, day of week, e.g. "monday to friday"
+            tuple!(apply!(day_of_week, dist_limit), apply!(range, dist_limit),
+                   apply!(day_of_week, dist_limit), stub) |
             // day of week, when, "week", e.g. "tuesday next week"
-            tuple!(apply!(day_of_week, exact_match), apply!(when, exact_match),
-                   apply!(week_noun, exact_match)) |
+            tuple!(apply!(day_of_week, dist_limit), apply!(when, dist_limit),
+                   apply!(week_noun, dist_limit), stub) |
+            // "this coming", day of week or "week", e.g. "this coming friday"
+            tuple!(apply!(this_coming, dist_limit), apply!(coming, dist_limit),
+                   alt!(apply!(day_of_week, dist_limit) | apply!(week_noun, dist_limit)), stub) |
+            // when and "week", e.g. "next week"
+            tuple!(apply!(when, dist_limit), apply!(week_noun, dist_limit), stub, stub) |
             // when and then any day of week, e.g. "last friday"
-            tuple!(apply!(when, exact_match), apply!(day_of_week, exact_match), stub) |
+            tuple!(apply!(when, dist_limit), apply!(day_of_week, dist_limit), stub, stub) |
+            // day of week and "coming", e.g. "friday coming"
+            tuple!(apply!(day_of_week, dist_limit), apply!(coming, dist_limit), stub, stub) |
             // day of week
-            tuple!(apply!(day_of_week, exact_match), stub, stub)
+            tuple!(apply!(day_of_week, dist_limit), stub, stub, stub)
         )
     )
 );
 
-make_interpreter!(positions = 3);
+make_interpreter!(positions = 4);
 
-fn make_time<'a, 'b, Tz: TimeZone>(
+pub(crate) fn make_time<'a, 'b, Tz: TimeZone>(
     res: &'a RuleResult,
     tz_aware: DateTime<Tz>,
     input: &'b str,
+    _config: &ParseConfig,
 ) -> Result<Context, SemanticError<'b>> {
     let mut ctx = Context::default();
 
-    let mut day = 0;
+    let days: Vec<i64> = res
+        .tokens_by_priority(Priority(0))
+        .iter()
+        .map(day_number)
+        .collect();
 
-    let token = res.token_by_priority(Priority(0));
+    if res.token_by_priority(Priority(3)).is_some() {
+        return make_range(days[0], days[1], tz_aware, input);
+    }
 
-    token.map_or((), |t| match t {
-        Token::Weekday(Day::Monday) => {
-            day = 0;
-        }
-        Token::Weekday(Day::Tuesday) => {
-            day = 1;
-        }
-        Token::Weekday(Day::Wednesday) => {
-            day = 2;
-        }
-        Token::Weekday(Day::Thursday) => {
-            day = 3;
-        }
-        Token::Weekday(Day::Friday) => {
-            day = 4;
-        }
-        Token::Weekday(Day::Saturday) => {
-            day = 5;
-        }
-        Token::Weekday(Day::Sunday) => {
-            day = 6;
+    if res.token_by_priority(Priority(4)).is_some() {
+        // repetition has no particular date, only its period and day of week
+        ctx.recurrence = Some(Recurrence {
+            unit: TimeInterval::Week,
+            step: if res.token_by_priority(Priority(5)).is_some() {
+                2
+            } else {
+                1
+            },
+            weekday: res
+                .tokens_by_priority(Priority(0))
+                .first()
+                .and_then(|t| match t {
+                    Token::Weekday(day) => Some(day.clone()),
+                    _ => None,
+                }),
+        });
+        return Ok(ctx);
+    }
+
+    if days.is_empty() {
+        // a whole week shift without a particular day, e.g. "last week"
+        match res.token_by_priority(Priority(1)) {
+            Some(Token::When(When::Next)) => ctx.set_duration(Duration::weeks(1).num_seconds()),
+            Some(Token::When(When::Last)) | Some(Token::When(When::Past)) => {
+                ctx.set_duration(-Duration::weeks(1).num_seconds())
+            }
+            _ => (),
         }
-        _ => (),
-    });
+        return Ok(ctx);
+    }
+
+    let day = days[0];
 
     let mut token = res.token_by_priority(Priority(1));
 
@@ -158,11 +224,51 @@ fn make_time<'a, 'b, Tz: TimeZone>(
     Ok(ctx)
 }
 
+pub(crate) fn day_number(token: &Token) -> i64 {
+    match token {
+        Token::Weekday(Day::Monday) => 0,
+        Token::Weekday(Day::Tuesday) => 1,
+        Token::Weekday(Day::Wednesday) => 2,
+        Token::Weekday(Day::Thursday) => 3,
+        Token::Weekday(Day::Friday) => 4,
+        Token::Weekday(Day::Saturday) => 5,
+        Token::Weekday(Day::Sunday) => 6,
+        _ => unreachable!(),
+    }
+}
+
+/// Range starts at the nearest day (today included) and lasts until the nearest end day after
+/// the start, so "friday to monday" wraps over the weekend.
+fn make_range<'b, Tz: TimeZone>(
+    start_day: i64,
+    end_day: i64,
+    tz_aware: DateTime<Tz>,
+    input: &'b str,
+) -> Result<Context, SemanticError<'b>> {
+    let length = (7 + end_day - start_day) % 7;
+    if length == 0 {
+        return Err(invalid_time_error(input, "range length", 0));
+    }
+
+    let start = (7 + start_day - tz_aware.weekday() as i64) % 7;
+
+    let mut ctx = Context::default();
+    ctx.set_duration(Duration::days(start).num_seconds());
+
+    let mut end = Context::default();
+    end.set_duration(Duration::days(start + length).num_seconds());
+    ctx.end = Some(Box::new(end));
+
+    Ok(ctx)
+}
+
 #[cfg(test)]
 mod tests {
     use super::interpret;
-    use crate::rules::errors::ambiguous_time_error;
-    use crate::rules::rules::MatchBounds;
+    use crate::rules::config::ParseConfig;
+    use crate::rules::errors::{ambiguous_time_error, ambiguous_token_error, invalid_time_error};
+    use crate::rules::rules::{MatchBounds, Recurrence};
+    use crate::rules::tokens::{TimeInterval, Token, Weekday, When};
     use chrono::prelude::*;
 
     fn fixed_time() -> DateTime<Local> {
@@ -172,56 +278,276 @@ mod tests {
 
     #[test]
     fn test_past_last() {
-        let result = interpret("do it for the past Monday", false, fixed_time()).unwrap();
+        let result = interpret(
+            "do it for the past Monday",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
         assert_eq!(result.bounds, Some(MatchBounds::new(14, 25)));
         assert_eq!(result.get_duration_sec(), -86400);
 
-        let result = interpret("past saturday", false, fixed_time()).unwrap();
+        let result = interpret("past saturday", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.bounds, Some(MatchBounds::new(0, 13)));
         assert_eq!(result.get_duration_sec(), -259200);
 
-        let result = interpret("pst frday", false, fixed_time()).unwrap();
+        let result = interpret("pst frday", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.bounds, Some(MatchBounds::new(0, 9)));
         assert_eq!(result.get_duration_sec(), -345600);
 
-        let result = interpret("pat thrday", false, fixed_time()).unwrap();
+        let result = interpret("pat thrday", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.bounds, Some(MatchBounds::new(0, 10)));
         assert_eq!(result.get_duration_sec(), -432000);
 
-        let result = interpret("past wednesday", true, fixed_time()).unwrap();
+        let result = interpret(
+            "past wednesday",
+            &ParseConfig {
+                exact_match: true,
+                ..Default::default()
+            },
+            fixed_time(),
+        )
+        .unwrap();
         assert_eq!(result.bounds, Some(MatchBounds::new(0, 14)));
         assert_eq!(result.get_duration_sec(), -518400);
 
-        let result = interpret("past tuesday", true, fixed_time()).unwrap();
+        let result = interpret(
+            "past tuesday",
+            &ParseConfig {
+                exact_match: true,
+                ..Default::default()
+            },
+            fixed_time(),
+        )
+        .unwrap();
         assert_eq!(result.bounds, Some(MatchBounds::new(0, 12)));
         assert_eq!(result.get_duration_sec(), -604800);
 
-        let result = interpret("lst monday", false, fixed_time()).unwrap();
+        let result = interpret("lst monday", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.bounds, Some(MatchBounds::new(0, 10)));
         assert_eq!(result.get_duration_sec(), -86400);
     }
 
     #[test]
     fn test_next() {
-        let result = interpret("next monday", false, fixed_time()).unwrap();
+        let result = interpret("next monday", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.bounds, Some(MatchBounds::new(0, 11)));
         assert_eq!(result.get_duration_sec(), 518400);
 
-        let result = interpret("drop me a line at next wednesday", false, fixed_time()).unwrap();
+        let result = interpret(
+            "drop me a line at next wednesday",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
         assert_eq!(result.bounds, Some(MatchBounds::new(18, 32)));
         assert_eq!(result.get_duration_sec(), 86400);
     }
 
+    #[test]
+    fn test_unicode_separators() {
+        let result =
+            interpret("next\u{00A0}monday", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 12)));
+        assert_eq!(result.get_duration_sec(), 518400);
+
+        let result = interpret(
+            "\u{201C}next monday\u{201D} \u{2014} ok?",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(3, 14)));
+        assert_eq!(result.get_duration_sec(), 518400);
+    }
+
+    #[test]
+    fn test_next_week() {
+        let result = interpret("tuesday next week", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 17)));
+        assert_eq!(result.get_duration_sec(), 604800);
+
+        let result = interpret("see you next week", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(8, 17)));
+        assert_eq!(result.get_duration_sec(), 7 * 86400);
+
+        let result = interpret("last week", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), -7 * 86400);
+    }
+
     #[test]
     fn test_this() {
-        let result = interpret("drop me a line at this monday", false, fixed_time());
+        let result = interpret(
+            "drop me a line at this monday",
+            &ParseConfig::default(),
+            fixed_time(),
+        );
         assert_eq!(
             result.unwrap_err().extract_error(),
             ambiguous_time_error("this monday").extract_error()
         );
 
-        let result = interpret("this friday", false, fixed_time()).unwrap();
+        let result = interpret("this friday", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 11)));
+        assert_eq!(result.get_duration_sec(), 259200);
+    }
+
+    #[test]
+    fn test_max_edit_distance() {
+        let config = ParseConfig {
+            max_edit_distance: Some(1),
+            ..Default::default()
+        };
+        let result = interpret("pst frday", &config, fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 9)));
+        assert_eq!(result.get_duration_sec(), -345600);
+
+        let config = ParseConfig {
+            max_edit_distance: Some(0),
+            ..Default::default()
+        };
+        let result = interpret("pst frday", &config, fixed_time()).unwrap();
+        assert_eq!(result.bounds, None);
+
+        let result = interpret("past friday", &config, fixed_time()).unwrap();
         assert_eq!(result.bounds, Some(MatchBounds::new(0, 11)));
+    }
+
+    #[test]
+    fn test_ambiguous_token() {
+        // "hast" is one typo away from both "last" and "past"
+        let result = interpret("hast friday", &ParseConfig::default(), fixed_time());
+        assert_eq!(
+            result.unwrap_err().extract_error(),
+            ambiguous_token_error(
+                "hast friday",
+                vec![Token::When(When::Last), Token::When(When::Past)]
+            )
+            .extract_error()
+        );
+    }
+
+    #[test]
+    fn test_range() {
+        let result = interpret("monday to friday", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 16)));
+        assert_eq!(result.get_duration_sec(), 518400);
+        assert_eq!(result.context.end.unwrap().duration.num_seconds(), 864000);
+
+        let result = interpret("mon-fri", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 7)));
+        assert_eq!(result.get_duration_sec(), 518400);
+        assert_eq!(result.context.end.unwrap().duration.num_seconds(), 864000);
+
+        let result = interpret(
+            "tuesday until thursday",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
+        assert_eq!(result.get_duration_sec(), 0);
+        assert_eq!(result.context.end.unwrap().duration.num_seconds(), 172800);
+
+        // wraps over the weekend
+        let result = interpret("friday to monday", &ParseConfig::default(), fixed_time()).unwrap();
         assert_eq!(result.get_duration_sec(), 259200);
+        assert_eq!(result.context.end.unwrap().duration.num_seconds(), 518400);
+
+        let result = interpret("monday to monday", &ParseConfig::default(), fixed_time());
+        assert_eq!(
+            result.unwrap_err().extract_error(),
+            invalid_time_error("monday to monday", "range length", 0).extract_error()
+        );
+    }
+
+    #[test]
+    fn test_between_range() {
+        let result = interpret(
+            "free between monday and thursday",
+            &ParseConfig::default(),
+            fixed_time(),
+        );
+        let result = result.unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(5, 32)));
+        assert_eq!(result.get_duration_sec(), 518400);
+        assert_eq!(result.context.end.unwrap().duration.num_seconds(), 777600);
+
+        let result = interpret("betwen tue and wed", &ParseConfig::default(), fixed_time());
+        let result = result.unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 18)));
+        assert_eq!(result.get_duration_sec(), 0);
+        assert_eq!(result.context.end.unwrap().duration.num_seconds(), 86400);
+
+        // "and" alone doesn't make a range
+        let result = interpret("thursday and friday", &ParseConfig::default(), fixed_time());
+        let result = result.unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 8)));
+        assert!(result.context.end.is_none());
+    }
+
+    #[test]
+    fn test_coming() {
+        // "coming" is the same as "next" before and after a day of week
+        let next = interpret("next friday", &ParseConfig::default(), fixed_time()).unwrap();
+        for input in &["this coming friday", "coming friday", "friday coming"] {
+            let result = interpret(input, &ParseConfig::default(), fixed_time()).unwrap();
+            assert_eq!(result.bounds, Some(MatchBounds::new(0, input.len())));
+            assert_eq!(result.get_duration_sec(), next.get_duration_sec());
+        }
+
+        // the day itself is a week ahead
+        let result = interpret("tuesday coming", &ParseConfig::default(), fixed_time());
+        assert_eq!(result.unwrap().get_duration_sec(), 7 * 86400);
+
+        let result = interpret("this coming week", &ParseConfig::default(), fixed_time());
+        let result = result.unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 16)));
+        assert_eq!(result.get_duration_sec(), 7 * 86400);
+
+        let config = ParseConfig::default();
+        let result = interpret("thursday coming week", &config, fixed_time()).unwrap();
+        let next = interpret("thursday next week", &config, fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 20)));
+        assert_eq!(result.get_duration_sec(), next.get_duration_sec());
+
+        // one typo at most
+        let result = interpret("friday comming", &ParseConfig::default(), fixed_time());
+        assert_eq!(result.unwrap().bounds, Some(MatchBounds::new(0, 14)));
+        let result = interpret("friday camping", &ParseConfig::default(), fixed_time());
+        assert_eq!(result.unwrap().bounds, Some(MatchBounds::new(0, 6)));
+    }
+
+    #[test]
+    fn test_recurrence() {
+        let result = interpret("every monday", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 12)));
+        assert_eq!(result.get_duration_sec(), 0);
+        assert_eq!(
+            result.context.recurrence,
+            Some(Recurrence {
+                unit: TimeInterval::Week,
+                step: 1,
+                weekday: Some(Weekday::Monday),
+            })
+        );
+
+        let result = interpret(
+            "we meet every other monday",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(8, 26)));
+        assert_eq!(
+            result.context.recurrence,
+            Some(Recurrence {
+                unit: TimeInterval::Week,
+                step: 2,
+                weekday: Some(Weekday::Monday),
+            })
+        );
+
+        let result = interpret("next monday", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.context.recurrence, None);
     }
 }
@@ -0,0 +1,127 @@
+use super::combinators::{stub, tokenize_count_symbols, Dist};
+use super::config::ParseConfig;
+use super::consts;
+use super::errors::SemanticError;
+use super::rules::{Context, RuleResult, TokenDesc};
+use super::tokens::{Priority, Token, When};
+use chrono::prelude::*;
+
+use nom::{alt, apply, call, many_till, named_args, tuple, types::CompleteStr};
+
+define!(weekend: (Token::Weekend, Priority(0)), "weekend", Dist(1));
+
+define!(this: (Token::When(When::This), Priority(1)), "this", Dist(1));
+define!(last: (Token::When(When::Last), Priority(1)), "last", Dist(1));
+define!(past: (Token::When(When::Past), Priority(1)), "past", Dist(1));
+define!(next: (Token::When(When::Next), Priority(1)), "next", Dist(1));
+
+combine!(when => this | last | past | next);
+
+named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>, ( TokenDesc, TokenDesc ) )>,
+    many_till!(tokenize_count_symbols,
+        alt!(
+            // e.g. "next weekend", "last weekend"
+            tuple!(apply!(when, dist_limit), apply!(weekend, dist_limit)) |
+            // e.g. "on the weekend"
+            tuple!(apply!(weekend, dist_limit), stub)
+        )
+    )
+);
+
+make_interpreter!(positions = 2);
+
+/// The weekend starts on saturday, "this weekend" is the nearest one, on sunday it is the one
+/// going on. "Next weekend" is the one after it, so it differs from "this weekend" even on
+/// weekdays.
+fn make_time<'a, 'b, Tz: TimeZone>(
+    res: &'a RuleResult,
+    tz_aware: DateTime<Tz>,
+    _input: &'b str,
+    config: &ParseConfig,
+) -> Result<Context, SemanticError<'b>> {
+    let mut ctx = Context::default();
+
+    let weekday = tz_aware.weekday().num_days_from_monday() as i32;
+    let saturday = Weekday::Sat.num_days_from_monday() as i32;
+    let mut days = if weekday <= saturday {
+        saturday - weekday
+    } else {
+        -1
+    };
+
+    match res.token_by_priority(Priority(1)) {
+        Some(Token::When(When::Next)) => days += 7,
+        Some(Token::When(When::Last)) | Some(Token::When(When::Past)) => days -= 7,
+        _ => (),
+    }
+
+    ctx.set_duration(days * consts::DAY);
+
+    if config.weekend_range {
+        let mut end = Context::default();
+        end.set_duration((days + 1) * consts::DAY);
+        ctx.end = Some(Box::new(end));
+    }
+
+    Ok(ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::interpret;
+    use crate::rules::config::ParseConfig;
+    use crate::rules::consts;
+    use crate::rules::rules::MatchBounds;
+    use chrono::prelude::*;
+
+    fn fixed_time() -> DateTime<Local> {
+        // wednesday
+        Local.ymd(2019, 7, 10).and_hms(12, 0, 0)
+    }
+
+    fn days(n: i32) -> i64 {
+        i64::from(n * consts::DAY)
+    }
+
+    #[test]
+    fn test_weekend() {
+        let result = interpret("this weekend", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 12)));
+        assert_eq!(result.get_duration_sec(), days(3));
+        assert!(result.context.end.is_none());
+
+        let result = interpret(
+            "see you next weekend",
+            &ParseConfig::default(),
+            fixed_time(),
+        );
+        let result = result.unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(8, 20)));
+        assert_eq!(result.get_duration_sec(), days(10));
+
+        let result = interpret("last weekend", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), days(-4));
+
+        let result = interpret("on the weekend", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(7, 14)));
+        assert_eq!(result.get_duration_sec(), days(3));
+
+        // on sunday the weekend is still going on
+        let sunday = Local.ymd(2019, 7, 14).and_hms(12, 0, 0);
+        let result = interpret("this weekend", &ParseConfig::default(), sunday).unwrap();
+        assert_eq!(result.get_duration_sec(), days(-1));
+    }
+
+    #[test]
+    fn test_weekend_range() {
+        let config = ParseConfig {
+            weekend_range: true,
+            ..Default::default()
+        };
+
+        let result = interpret("next weekend", &config, fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), days(10));
+        let end = result.context.end.unwrap();
+        assert_eq!(end.duration.num_seconds(), days(11));
+    }
+}
@@ -1,26 +1,79 @@
+//! Errors returned by the parsers.
+//!
+//! Input without any date/time in it is not an error, parsers just return no results for it.
+
 use super::rules::MatchBounds;
-use failure::Fail;
+use super::tokens::Token;
 use nom::types::CompleteStr;
-use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
 
+// nom error code used by the rules internally, never returned to users
 pub(crate) const UNKNOWN: u32 = 1; // couldn't recognize token
 
-#[derive(Debug, Fail, PartialEq, Clone, Serialize)]
+/// Reason why a recognized date/time couldn't be turned into a result.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum DateTimeError {
-    #[fail(display = "can't parse time unambiguously in: {}", msg)]
+    /// Phrase has several meanings, e.g. "this monday" on wednesday may be the past or the next
+    /// monday.
     AmbiguousTime { msg: String },
-    #[fail(display = "invalid time in: {}, {} can't be {}", text, what, value)]
+    /// Phrase is recognized but makes no sense as a time, e.g. "24:10" or "in -3 minutes".
     InvalidTime {
         text: String,
         what: String,
         value: i32,
     },
-    #[fail(display = "can't parse, rules intersection detected in {}", text)]
+    /// Phrase is recognized but names a date which doesn't exist, e.g. "February 30" or "week 53"
+    /// of a year with 52 weeks.
+    InvalidDate {
+        text: String,
+        what: String,
+        value: i32,
+    },
+    /// Matches of several rules intersect and it is unclear which one is right.
     IntersectionError { text: String },
+    /// Word is equally close to several known words, e.g. "munday" may be monday or sunday.
+    Ambiguous {
+        text: String,
+        candidates: Vec<Token>,
+    },
+    /// Resulting date/time can't be represented, e.g. "in 300000 years".
+    OutOfRange { what: String, value: i64 },
+}
+
+impl fmt::Display for DateTimeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DateTimeError::AmbiguousTime { msg } => {
+                write!(f, "can't parse time unambiguously in: {}", msg)
+            }
+            DateTimeError::InvalidTime { text, what, value } => {
+                write!(f, "invalid time in: {}, {} can't be {}", text, what, value)
+            }
+            DateTimeError::InvalidDate { text, what, value } => {
+                write!(f, "invalid date in: {}, {} can't be {}", text, what, value)
+            }
+            DateTimeError::IntersectionError { text } => {
+                write!(f, "can't parse, rules intersection detected in {}", text)
+            }
+            DateTimeError::Ambiguous { text, candidates } => {
+                write!(f, "can't choose between {:?} in: {}", candidates, text)
+            }
+            DateTimeError::OutOfRange { what, value } => {
+                write!(f, "date/time is out of range, {} can't be {}", what, value)
+            }
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize)]
-pub struct SemanticError<'a> {
+impl Error for DateTimeError {}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub(crate) struct SemanticError<'a> {
     // meta info for parser
     bounds: MatchBounds,
     tail: &'a str,
@@ -55,7 +108,7 @@ impl<'a> SemanticError<'a> {
     }
 }
 
-pub fn ambiguous_time_error(msg: &str) -> SemanticError {
+pub(crate) fn ambiguous_time_error(msg: &str) -> SemanticError {
     SemanticError {
         bounds: MatchBounds::new(0, 0),
         tail: "",
@@ -65,7 +118,7 @@ pub fn ambiguous_time_error(msg: &str) -> SemanticError {
     }
 }
 
-pub fn invalid_time_error<'a>(msg: &'a str, what: &'a str, value: i32) -> SemanticError<'a> {
+pub(crate) fn invalid_time_error<'a>(msg: &'a str, what: &'a str, value: i32) -> SemanticError<'a> {
     SemanticError {
         bounds: MatchBounds::new(0, 0),
         tail: "",
@@ -77,7 +130,30 @@ pub fn invalid_time_error<'a>(msg: &'a str, what: &'a str, value: i32) -> Semant
     }
 }
 
-pub fn intersection_error(text: &str) -> SemanticError {
+pub(crate) fn invalid_date_error<'a>(msg: &'a str, what: &'a str, value: i32) -> SemanticError<'a> {
+    SemanticError {
+        bounds: MatchBounds::new(0, 0),
+        tail: "",
+        error: DateTimeError::InvalidDate {
+            text: msg.to_owned(),
+            what: what.to_owned(),
+            value,
+        },
+    }
+}
+
+pub(crate) fn ambiguous_token_error(text: &str, candidates: Vec<Token>) -> SemanticError<'_> {
+    SemanticError {
+        bounds: MatchBounds::new(0, 0),
+        tail: "",
+        error: DateTimeError::Ambiguous {
+            text: text.to_owned(),
+            candidates,
+        },
+    }
+}
+
+pub(crate) fn intersection_error(text: &str) -> SemanticError {
     SemanticError {
         bounds: MatchBounds::new(0, 0),
         tail: "",
@@ -86,3 +162,10 @@ pub fn intersection_error(text: &str) -> SemanticError {
         },
     }
 }
+
+pub(crate) fn out_of_range_error(what: &str, value: i64) -> DateTimeError {
+    DateTimeError::OutOfRange {
+        what: what.to_owned(),
+        value,
+    }
+}
@@ -0,0 +1,118 @@
+use super::combinators::{stub, tokenize_count_symbols, Dist};
+use super::en::casual_date_time::make_time;
+use super::rules::{RuleResult, TokenDesc};
+use super::tokens::{Articles, Prepositions, Priority, Pronouns, TimeOfDay, Token, When};
+use chrono::prelude::*;
+
+use nom::{alt, apply, call, many_till, named_args, tuple, types::CompleteStr};
+
+define!(now: (Token::When(When::Now), Priority(0)), "ahora", Dist(1));
+
+define!(
+    this:
+    [(Token::Pronouns(Pronouns::This), Priority(1)), "esta", Dist(0)] |
+    [(Token::Pronouns(Pronouns::This), Priority(1)), "este", Dist(0)]
+);
+
+define!(today: (Token::When(When::Today), Priority(1)), "hoy", Dist(0));
+define!(tomorrow: (Token::When(When::Tomorrow), Priority(1)), "mañana", Dist(1));
+define!(yesterday: (Token::When(When::Yesterday), Priority(1)), "ayer", Dist(0));
+
+combine!(when => today | yesterday | tomorrow);
+
+define!(morning: (Token::TimeOfDay(TimeOfDay::Morning), Priority(2)), "mañana", Dist(1));
+define!(afternoon: (Token::TimeOfDay(TimeOfDay::Afternoon), Priority(2)), "tarde", Dist(1));
+define!(night: (Token::TimeOfDay(TimeOfDay::Night), Priority(2)), "noche", Dist(1));
+define!(noon: (Token::TimeOfDay(TimeOfDay::Noon), Priority(2)), "mediodía", Dist(1));
+define!(midnight: (Token::TimeOfDay(TimeOfDay::Midnight), Priority(2)), "medianoche", Dist(2));
+
+combine!(time_of_day => morning | afternoon | night | noon | midnight);
+
+// "por la tarde", "de la noche"
+define!(
+    prep:
+    [(Token::When(When::In), Priority(3)), "por", Dist(0)] |
+    [(Token::Prepositions(Prepositions::Of), Priority(3)), "de", Dist(0)]
+);
+define!(the: (Token::Articles(Articles::The), Priority(3)), "la", Dist(0));
+
+// "mañana" is both "tomorrow" and "morning", it is a part of the day only after a day or an
+// article, that's why alternatives with parts of the day go first
+named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
+                             ( TokenDesc, TokenDesc, TokenDesc, TokenDesc, ) )>,
+    many_till!(tokenize_count_symbols,
+        alt!(
+            // mañana por la mañana, ayer por la noche, etc.
+            tuple!(apply!(when, dist_limit), apply!(prep, dist_limit), apply!(the, dist_limit),
+                   apply!(time_of_day, dist_limit)) |
+            // esta mañana, esta noche
+            tuple!(apply!(this, dist_limit), apply!(time_of_day, dist_limit), stub, stub) |
+            // por la mañana, de la tarde
+            tuple!(apply!(prep, dist_limit), apply!(the, dist_limit),
+                   apply!(time_of_day, dist_limit), stub) |
+            // hoy, mañana, ayer
+            tuple!(apply!(when, dist_limit), stub, stub, stub) |
+            // ahora
+            tuple!(apply!(now, dist_limit), stub, stub, stub)
+        )
+    )
+);
+
+make_interpreter!(positions = 4);
+
+#[cfg(test)]
+mod tests {
+    use super::interpret;
+    use crate::rules::config::ParseConfig;
+    use crate::rules::rules::MatchBounds;
+    use chrono::prelude::*;
+
+    fn fixed_time() -> DateTime<Local> {
+        Local.ymd(2019, 1, 1).and_hms(0, 0, 0)
+    }
+
+    #[test]
+    fn test_casual_date() {
+        let result = interpret("hasta mañana", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(6, 13)));
+        assert_eq!(result.get_duration_sec(), 86400);
+        assert_eq!(result.get_hours(), 0);
+
+        // missing tilde
+        let result = interpret("hasta manana", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(6, 12)));
+        assert_eq!(result.get_duration_sec(), 86400);
+
+        let result = interpret("hoy", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 3)));
+        assert_eq!(result.get_duration_sec(), 0);
+
+        let result = interpret("ayer", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), -86400);
+    }
+
+    #[test]
+    fn test_time_of_day() {
+        let result = interpret(
+            "mañana por la mañana",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 22)));
+        assert_eq!(result.get_duration_sec(), 86400);
+        assert_eq!(result.get_hours(), 9);
+
+        let result = interpret("esta manana", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 0);
+        assert_eq!(result.get_hours(), 9);
+
+        let result = interpret("ayer por la tarde", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), -86400);
+        assert_eq!(result.get_hours(), 15);
+
+        let result = interpret("de la noche", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 0);
+        assert_eq!(result.get_hours(), 23);
+    }
+}
@@ -0,0 +1,95 @@
+use super::combinators::{stub, tokenize_count_symbols, Dist};
+use super::en::weekdays::make_time;
+use super::rules::{RuleResult, TokenDesc};
+use super::tokens::{Priority, Token, Weekday as Day, When};
+use chrono::prelude::*;
+
+use nom::{alt, apply, call, many_till, named_args, tuple, types::CompleteStr};
+
+define!(lunes: (Token::Weekday(Day::Monday), Priority(0)), "lunes", Dist(1));
+define!(martes: (Token::Weekday(Day::Tuesday), Priority(0)), "martes", Dist(1));
+define!(miercoles: (Token::Weekday(Day::Wednesday), Priority(0)), "miércoles", Dist(2));
+define!(jueves: (Token::Weekday(Day::Thursday), Priority(0)), "jueves", Dist(1));
+define!(viernes: (Token::Weekday(Day::Friday), Priority(0)), "viernes", Dist(2));
+define!(sabado: (Token::Weekday(Day::Saturday), Priority(0)), "sábado", Dist(1));
+define!(domingo: (Token::Weekday(Day::Sunday), Priority(0)), "domingo", Dist(2));
+
+combine!(day_of_week => lunes | martes | miercoles | jueves | viernes | sabado | domingo);
+
+define!(this: (Token::When(When::This), Priority(1)), "este", Dist(0));
+define!(last: (Token::When(When::Last), Priority(1)), "pasado", Dist(1));
+define!(next: (Token::When(When::Next), Priority(1)), "próximo", Dist(2));
+
+combine!(when => last | next);
+
+named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
+                             ( TokenDesc, TokenDesc ) )>,
+    many_till!(tokenize_count_symbols,
+        alt!(
+            // day of week and then when, e.g. "lunes pasado"
+            tuple!(apply!(day_of_week, dist_limit), apply!(when, dist_limit)) |
+            // when and then day of week, e.g. "próximo viernes"
+            tuple!(apply!(next, dist_limit), apply!(day_of_week, dist_limit)) |
+            // "este" and then any day of week, e.g. "este jueves"
+            tuple!(apply!(this, dist_limit), apply!(day_of_week, dist_limit)) |
+            // day of week
+            tuple!(apply!(day_of_week, dist_limit), stub)
+        )
+    )
+);
+
+make_interpreter!(positions = 2);
+
+#[cfg(test)]
+mod tests {
+    use super::interpret;
+    use crate::rules::config::ParseConfig;
+    use crate::rules::errors::ambiguous_time_error;
+    use crate::rules::rules::MatchBounds;
+    use chrono::prelude::*;
+
+    fn fixed_time() -> DateTime<Local> {
+        // 2019 1st January, Tuesday
+        Local.ymd(2019, 1, 1).and_hms(0, 0, 1)
+    }
+
+    #[test]
+    fn test_past_last() {
+        let result = interpret("el lunes pasado", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(3, 15)));
+        assert_eq!(result.get_duration_sec(), -86400);
+
+        // missing accent
+        let result = interpret("sabado pasado", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 13)));
+        assert_eq!(result.get_duration_sec(), -259200);
+    }
+
+    #[test]
+    fn test_next() {
+        let result = interpret("el próximo lunes", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(3, 17)));
+        assert_eq!(result.get_duration_sec(), 518400);
+
+        let result = interpret("domingo proximo", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 432000);
+    }
+
+    #[test]
+    fn test_this() {
+        let result = interpret("este jueves", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 11)));
+        assert_eq!(result.get_duration_sec(), 172800);
+
+        // missing accent
+        let result = interpret("el miercoles", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(3, 12)));
+        assert_eq!(result.get_duration_sec(), 86400);
+
+        let result = interpret("este lunes", &ParseConfig::default(), fixed_time());
+        assert_eq!(
+            result.unwrap_err().extract_error(),
+            ambiguous_time_error("este lunes").extract_error()
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use super::combinators::{stub, tokenize_count_symbols, Dist};
+use super::en::casual_date_time::make_time;
+use super::rules::{RuleResult, TokenDesc};
+use super::tokens::{Priority, Pronouns, TimeOfDay, Token, When};
+use chrono::prelude::*;
+
+use nom::{alt, apply, call, many_till, named_args, tuple, types::CompleteStr};
+
+define!(now: (Token::When(When::Now), Priority(0)), "maintenant", Dist(2));
+
+define!(this: (Token::Pronouns(Pronouns::This), Priority(1)), "ce", Dist(0));
+
+// apostrophe is dropped before comparison, see recognize_word
+define!(today: (Token::When(When::Today), Priority(1)), "aujourdhui", Dist(2));
+define!(tomorrow: (Token::When(When::Tomorrow), Priority(1)), "demain", Dist(1));
+define!(yesterday: (Token::When(When::Yesterday), Priority(1)), "hier", Dist(0));
+
+combine!(when => today | yesterday | tomorrow);
+
+define!(night: (Token::TimeOfDay(TimeOfDay::Night), Priority(2)), "nuit", Dist(0));
+define!(morning: (Token::TimeOfDay(TimeOfDay::Morning), Priority(2)), "matin", Dist(1));
+define!(evening: (Token::TimeOfDay(TimeOfDay::Evening), Priority(2)), "soir", Dist(0));
+define!(noon: (Token::TimeOfDay(TimeOfDay::Noon), Priority(2)), "midi", Dist(0));
+define!(midnight: (Token::TimeOfDay(TimeOfDay::Midnight), Priority(2)), "minuit", Dist(1));
+
+combine!(time_of_day => night | morning | evening | noon | midnight);
+
+named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
+                             ( TokenDesc, TokenDesc, ) )>,
+    many_till!(tokenize_count_symbols,
+        alt!(
+            // ce soir, ce matin
+            tuple!(apply!(this, dist_limit), apply!(time_of_day, dist_limit)) |
+            // demain matin, hier soir, etc.
+            tuple!(apply!(when, dist_limit), apply!(time_of_day, dist_limit)) |
+            // aujourd'hui, demain, hier
+            tuple!(apply!(when, dist_limit), stub) |
+            // maintenant
+            tuple!(apply!(now, dist_limit), stub)
+        )
+    )
+);
+
+make_interpreter!(positions = 2);
+
+#[cfg(test)]
+mod tests {
+    use super::interpret;
+    use crate::rules::config::ParseConfig;
+    use crate::rules::rules::MatchBounds;
+    use chrono::prelude::*;
+
+    fn fixed_time() -> DateTime<Local> {
+        Local.ymd(2019, 1, 1).and_hms(0, 0, 0)
+    }
+
+    #[test]
+    fn test_casual_date() {
+        let result = interpret("c'est aujourd'hui", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(6, 17)));
+        assert_eq!(result.get_duration_sec(), 0);
+
+        let result =
+            interpret("aujourd\u{2019}hui", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 13)));
+
+        // missing apostrophe
+        let result = interpret("aujourdhui", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 10)));
+        assert_eq!(result.get_duration_sec(), 0);
+
+        let result = interpret("à demain", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(3, 9)));
+        assert_eq!(result.get_duration_sec(), 86400);
+
+        let result = interpret("hier", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), -86400);
+    }
+
+    #[test]
+    fn test_time_of_day() {
+        let result = interpret("demain matin", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 12)));
+        assert_eq!(result.get_duration_sec(), 86400);
+        assert_eq!(result.get_hours(), 9);
+
+        let result = interpret("ce soir", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 0);
+        assert_eq!(result.get_hours(), 19);
+
+        let result = interpret("hier soir", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), -86400);
+        assert_eq!(result.get_hours(), 19);
+    }
+}
@@ -0,0 +1,105 @@
+use super::combinators::{stub, tokenize_count_symbols, Dist};
+use super::en::weekdays::make_time;
+use super::rules::{RuleResult, TokenDesc};
+use super::tokens::{Priority, Token, Weekday as Day, When};
+use chrono::prelude::*;
+
+use nom::{alt, apply, call, many_till, named_args, tuple, types::CompleteStr};
+
+define!(lundi: (Token::Weekday(Day::Monday), Priority(0)), "lundi", Dist(1));
+define!(mardi: (Token::Weekday(Day::Tuesday), Priority(0)), "mardi", Dist(1));
+define!(mercredi: (Token::Weekday(Day::Wednesday), Priority(0)), "mercredi", Dist(2));
+define!(jeudi: (Token::Weekday(Day::Thursday), Priority(0)), "jeudi", Dist(1));
+define!(vendredi: (Token::Weekday(Day::Friday), Priority(0)), "vendredi", Dist(2));
+define!(samedi: (Token::Weekday(Day::Saturday), Priority(0)), "samedi", Dist(1));
+define!(dimanche: (Token::Weekday(Day::Sunday), Priority(0)), "dimanche", Dist(2));
+
+combine!(day_of_week => lundi | mardi | mercredi | jeudi | vendredi | samedi | dimanche);
+
+define!(this: (Token::When(When::This), Priority(1)), "ce", Dist(0));
+define!(
+    last:
+    [(Token::When(When::Last), Priority(1)), "dernier", Dist(1)] |
+    [(Token::When(When::Last), Priority(1)), "passé", Dist(1)]
+);
+define!(next: (Token::When(When::Next), Priority(1)), "prochain", Dist(2));
+
+combine!(when => last | next);
+
+named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
+                             ( TokenDesc, TokenDesc ) )>,
+    many_till!(tokenize_count_symbols,
+        alt!(
+            // day of week and then when, e.g. "vendredi dernier"
+            tuple!(apply!(day_of_week, dist_limit), apply!(when, dist_limit)) |
+            // "ce" and then any day of week, e.g. "ce jeudi"
+            tuple!(apply!(this, dist_limit), apply!(day_of_week, dist_limit)) |
+            // day of week
+            tuple!(apply!(day_of_week, dist_limit), stub)
+        )
+    )
+);
+
+make_interpreter!(positions = 2);
+
+#[cfg(test)]
+mod tests {
+    use super::interpret;
+    use crate::rules::config::ParseConfig;
+    use crate::rules::errors::ambiguous_time_error;
+    use crate::rules::rules::MatchBounds;
+    use chrono::prelude::*;
+
+    fn fixed_time() -> DateTime<Local> {
+        // 2019 1st January, Tuesday
+        Local.ymd(2019, 1, 1).and_hms(0, 0, 1)
+    }
+
+    #[test]
+    fn test_past_last() {
+        let result = interpret("lundi dernier", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 13)));
+        assert_eq!(result.get_duration_sec(), -86400);
+
+        let result = interpret("samedi passé", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 13)));
+        assert_eq!(result.get_duration_sec(), -259200);
+
+        // missing accent
+        let result = interpret("samedi passe", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 12)));
+        assert_eq!(result.get_duration_sec(), -259200);
+    }
+
+    #[test]
+    fn test_next() {
+        let result = interpret(
+            "réunion lundi prochain",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(9, 23)));
+        assert_eq!(result.get_duration_sec(), 518400);
+
+        let result = interpret("dimanche prochain", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 432000);
+    }
+
+    #[test]
+    fn test_this() {
+        let result = interpret("ce jeudi", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 8)));
+        assert_eq!(result.get_duration_sec(), 172800);
+
+        let result = interpret("à vendredi", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, Some(MatchBounds::new(3, 11)));
+        assert_eq!(result.get_duration_sec(), 259200);
+
+        let result = interpret("ce lundi", &ParseConfig::default(), fixed_time());
+        assert_eq!(
+            result.unwrap_err().extract_error(),
+            ambiguous_time_error("ce lundi").extract_error()
+        );
+    }
+}
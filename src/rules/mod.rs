@@ -2,10 +2,17 @@
 pub(crate) mod combinators;
 
 pub(crate) mod common_matchers;
+pub(crate) mod config;
 pub(crate) mod consts;
-pub(crate) mod errors;
+pub mod errors;
+pub(crate) mod registry;
 pub(crate) mod rules;
-pub(crate) mod tokens;
+pub(crate) mod stats;
+pub mod tokens;
 
 pub(crate) mod common;
-pub(crate) mod en;
+pub mod de;
+pub mod en;
+pub mod es;
+pub mod fr;
+pub mod ru;
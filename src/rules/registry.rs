@@ -0,0 +1,91 @@
+use crate::language::Language;
+use crate::rules::combinators;
+use crate::rules::config::{ParseConfig, RuleKind};
+use crate::rules::en;
+use crate::rules::errors::DateTimeError;
+use crate::rules::rules::{CustomRule, FnRule, MatchResult};
+use chrono::{offset::TimeZone, DateTime};
+
+/// Rules of a parser which can be extended at runtime, e.g. with application specific phrases.
+/// Custom rules get the input prepared according to the config, i.e. lowercased unless
+/// `preserve_case` is set, and their matches go through the same merging and overlap checks as
+/// matches of the built-in rules.
+///
+/// ```
+/// use chrono::offset::{TimeZone, Utc};
+/// use when::{Context, MatchResult, ParseConfig, RuleRegistry};
+///
+/// let registry = RuleRegistry::default_english().with_rule(|input: &str, _: &ParseConfig, _| {
+///     let start = input.find("payday")?;
+///     let mut ctx = Context::default();
+///     ctx.day = Some(25);
+///     Some(MatchResult::new(ctx, start, start + "payday".len()))
+/// });
+///
+/// let base = Utc.ymd(2019, 1, 10).and_hms(12, 0, 0);
+/// let res = registry.parse(base, "see you on payday", &ParseConfig::default());
+/// assert_eq!(res[0].as_ref().unwrap().resolve(base), Ok(Utc.ymd(2019, 1, 25).and_hms(12, 0, 0)));
+/// ```
+pub struct RuleRegistry<Tz: TimeZone> {
+    rules: Vec<(RuleKind, FnRule<Tz>)>,
+    custom: Vec<Box<CustomRule<Tz>>>,
+    modifiers: combinators::Modifiers,
+}
+
+impl<Tz: TimeZone> RuleRegistry<Tz> {
+    /// Registry without any rules, words around matches like "around" are not recognized either.
+    pub fn empty() -> Self {
+        RuleRegistry {
+            rules: Vec::new(),
+            custom: Vec::new(),
+            modifiers: combinators::Modifiers::default(),
+        }
+    }
+
+    /// Built-in English rules, the same ones en::parse applies.
+    pub fn default_english() -> Self {
+        RuleRegistry {
+            rules: en::rules::<Tz>(),
+            custom: Vec::new(),
+            modifiers: en::MODIFIERS,
+        }
+    }
+
+    /// Adds a rule which is applied after the rules added before it.
+    pub fn with_rule<F>(mut self, rule: F) -> Self
+    where
+        F: Fn(&str, &ParseConfig, DateTime<Tz>) -> Option<MatchResult> + 'static,
+    {
+        self.custom.push(Box::new(rule));
+        self
+    }
+
+    pub fn parse(
+        &self,
+        tz_aware: DateTime<Tz>,
+        input: &str,
+        config: &ParseConfig,
+    ) -> Vec<Result<MatchResult, DateTimeError>> {
+        let input_prepared = config.prepare_input(input);
+        let matches = combinators::apply_rules(
+            tz_aware,
+            &input_prepared.text,
+            &self.rules,
+            &self.custom,
+            &self.modifiers,
+            config,
+        );
+        input_prepared.restore_all(matches)
+    }
+}
+
+impl<Tz: TimeZone> Language<Tz> for RuleRegistry<Tz> {
+    fn parse(
+        &self,
+        tz_aware: DateTime<Tz>,
+        input: &str,
+        config: &ParseConfig,
+    ) -> Vec<Result<MatchResult, DateTimeError>> {
+        RuleRegistry::parse(self, tz_aware, input, config)
+    }
+}
@@ -0,0 +1,67 @@
+use super::combinators::{stub, tokenize_count_symbols, Dist};
+use super::en::casual_date_time::make_time;
+use super::rules::{RuleResult, TokenDesc};
+use super::tokens::{Priority, TimeOfDay, Token, When};
+use chrono::prelude::*;
+
+use nom::{alt, apply, call, many_till, named_args, tuple, types::CompleteStr};
+
+define!(now: (Token::When(When::Now), Priority(0)), "сейчас", Dist(1));
+
+define!(today: (Token::When(When::Today), Priority(1)), "сегодня", Dist(2));
+define!(tomorrow: (Token::When(When::Tomorrow), Priority(1)), "завтра", Dist(1));
+define!(yesterday: (Token::When(When::Yesterday), Priority(1)), "вчера", Dist(1));
+
+combine!(when => today | yesterday | tomorrow);
+
+define!(morning: (Token::TimeOfDay(TimeOfDay::Morning), Priority(2)), "утром", Dist(1));
+define!(afternoon: (Token::TimeOfDay(TimeOfDay::Afternoon), Priority(2)), "днём", Dist(1));
+define!(evening: (Token::TimeOfDay(TimeOfDay::Evening), Priority(2)), "вечером", Dist(2));
+define!(night: (Token::TimeOfDay(TimeOfDay::Night), Priority(2)), "ночью", Dist(1));
+
+combine!(time_of_day => morning | afternoon | evening | night);
+
+named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
+                             ( TokenDesc, TokenDesc, ) )>,
+    many_till!(tokenize_count_symbols,
+        alt!(
+            // завтра утром, вчера вечером, etc.
+            tuple!(apply!(when, dist_limit), apply!(time_of_day, dist_limit)) |
+            // сегодня, завтра, вчера
+            tuple!(apply!(when, dist_limit), stub) |
+            // сейчас
+            tuple!(apply!(now, dist_limit), stub)
+        )
+    )
+);
+
+make_interpreter!(positions = 2);
+
+#[cfg(test)]
+mod tests {
+    use super::interpret;
+    use crate::rules::config::ParseConfig;
+    use chrono::prelude::*;
+
+    fn fixed_time() -> DateTime<Local> {
+        Local.ymd(2019, 1, 1).and_hms(0, 0, 0)
+    }
+
+    #[test]
+    fn test_casual_date() {
+        let result = interpret("до завтра", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 86400);
+
+        let result = interpret("сегодня", &ParseConfig::default(), fixed_time()).unwrap();
+        assert!(result.bounds.is_some());
+        assert_eq!(result.get_duration_sec(), 0);
+
+        // typo
+        let result = interpret("седодня", &ParseConfig::default(), fixed_time()).unwrap();
+        assert!(result.bounds.is_some());
+
+        let result = interpret("вчера вечером", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), -86400);
+        assert_eq!(result.get_hours(), 19);
+    }
+}
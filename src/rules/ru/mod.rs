@@ -0,0 +1,33 @@
+mod casual_date_time;
+mod weekdays;
+
+use crate::rules::combinators;
+use crate::rules::common;
+use crate::rules::config::ParseConfig;
+use crate::rules::en;
+use crate::rules::errors;
+use crate::rules::rules;
+use crate::rules::tokens;
+use chrono::{offset::TimeZone, DateTime};
+
+/// Words around matches of the Russian rules, see combinators::Modifiers.
+const MODIFIERS: combinators::Modifiers = combinators::Modifiers {
+    approximation: &["около", "примерно"],
+    negation: &["не", "кроме"],
+    adjustment: None,
+};
+
+pub fn parse<'a, Tz: TimeZone + 'a>(
+    tz_aware: DateTime<Tz>,
+    input: &'a str,
+    config: &ParseConfig,
+) -> Vec<Result<rules::MatchResult, errors::DateTimeError>> {
+    common::parse_translated(
+        tz_aware,
+        input,
+        config,
+        weekdays::interpret::<Tz>,
+        casual_date_time::interpret::<Tz>,
+        &MODIFIERS,
+    )
+}
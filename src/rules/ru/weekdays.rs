@@ -0,0 +1,112 @@
+use super::combinators::{stub, tokenize_count_symbols, Dist};
+use super::en::weekdays::make_time;
+use super::rules::{RuleResult, TokenDesc};
+use super::tokens::{Priority, Token, Weekday as Day, When};
+use chrono::prelude::*;
+
+use nom::{alt, apply, call, many_till, named_args, tuple, types::CompleteStr};
+
+// distances also cover word endings, e.g. "в пятницу", "в следующую субботу", except for
+// wednesday, one letter away from it is "среди" ("among"), so its forms are listed instead
+
+define!(monday: (Token::Weekday(Day::Monday), Priority(0)), "понедельник", Dist(2));
+define!(tuesday: (Token::Weekday(Day::Tuesday), Priority(0)), "вторник", Dist(1));
+define!(
+    wednesday:
+    [(Token::Weekday(Day::Wednesday), Priority(0)), "среда", Dist(0)] |
+    [(Token::Weekday(Day::Wednesday), Priority(0)), "среду", Dist(0)] |
+    [(Token::Weekday(Day::Wednesday), Priority(0)), "среды", Dist(0)]
+);
+define!(thursday: (Token::Weekday(Day::Thursday), Priority(0)), "четверг", Dist(1));
+define!(friday: (Token::Weekday(Day::Friday), Priority(0)), "пятница", Dist(1));
+define!(saturday: (Token::Weekday(Day::Saturday), Priority(0)), "суббота", Dist(1));
+define!(sunday: (Token::Weekday(Day::Sunday), Priority(0)), "воскресенье", Dist(2));
+
+combine!(day_of_week => monday | tuesday | wednesday | thursday | friday | saturday | sunday);
+
+define!(
+    this:
+    [(Token::When(When::This), Priority(1)), "этот", Dist(0)] |
+    [(Token::When(When::This), Priority(1)), "эту", Dist(0)]
+);
+define!(last: (Token::When(When::Last), Priority(1)), "прошлый", Dist(2));
+define!(next: (Token::When(When::Next), Priority(1)), "следующий", Dist(2));
+
+combine!(when => this | last | next);
+
+named_args!(parse<'a>(dist_limit: Dist)<CompleteStr<'a>, (Vec<usize>,
+                             ( TokenDesc, TokenDesc ) )>,
+    many_till!(tokenize_count_symbols,
+        alt!(
+            // when and then day of week, e.g. "следующий вторник"
+            tuple!(apply!(when, dist_limit), apply!(day_of_week, dist_limit)) |
+            // day of week
+            tuple!(apply!(day_of_week, dist_limit), stub)
+        )
+    )
+);
+
+make_interpreter!(positions = 2);
+
+#[cfg(test)]
+mod tests {
+    use super::interpret;
+    use crate::rules::config::ParseConfig;
+    use crate::rules::errors::ambiguous_time_error;
+    use crate::rules::rules::MatchBounds;
+    use chrono::prelude::*;
+
+    fn fixed_time() -> DateTime<Local> {
+        // 2019 1st January, Tuesday
+        Local.ymd(2019, 1, 1).and_hms(0, 0, 1)
+    }
+
+    #[test]
+    fn test_past_last() {
+        let result =
+            interpret("прошлый понедельник", &ParseConfig::default(), fixed_time()).unwrap();
+        // rules work with byte offsets, cyrillic letters take two bytes each
+        assert_eq!(result.bounds, Some(MatchBounds::new(0, 37)));
+        assert_eq!(result.get_duration_sec(), -86400);
+
+        let result = interpret("в прошлую субботу", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), -259200);
+    }
+
+    #[test]
+    fn test_next() {
+        let result =
+            interpret("в следующую пятницу", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 259200);
+
+        let result = interpret(
+            "следующее воскресенье",
+            &ParseConfig::default(),
+            fixed_time(),
+        )
+        .unwrap();
+        assert_eq!(result.get_duration_sec(), 432000);
+    }
+
+    #[test]
+    fn test_this() {
+        let result = interpret("в четверг", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 172800);
+
+        let result = interpret("в среду", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 86400);
+
+        let result = interpret("до среды", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.get_duration_sec(), 86400);
+
+        // "среди" is "among", not a wednesday
+        let result = interpret("среди недели", &ParseConfig::default(), fixed_time()).unwrap();
+        assert_eq!(result.bounds, None);
+
+        let result = interpret("этот понедельник", &ParseConfig::default(), fixed_time());
+        assert_eq!(
+            result.unwrap_err().extract_error(),
+            ambiguous_time_error("этот понедельник").extract_error()
+        );
+    }
+}
@@ -1,14 +1,21 @@
-use chrono::prelude::DateTime;
+use chrono::prelude::{DateTime, Datelike, TimeZone, Timelike};
+use chrono::{FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
 use nom::{types::CompleteStr, IResult};
-use serde::Serialize;
-use std::convert::From;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::convert::{From, TryFrom};
 
 use super::combinators::Dist;
-use super::errors::SemanticError;
-use super::tokens::{PToken, Priority, Token};
+use super::common::days_in_month;
+use super::config::ParseConfig;
+use super::consts;
+use super::errors::{out_of_range_error, DateTimeError, SemanticError};
+use super::tokens::{Adverbs, PToken, Priority, TimeInterval, Token, Weekday};
 
+/// Result of a word recognizer: the rest of the input and the recognized token.
 pub type MyResult<'a> = IResult<CompleteStr<'a>, TokenDesc>;
 
+/// Recognized token along with the distance between the word and the token pattern.
 #[derive(Debug, Clone)]
 pub struct TokenDesc {
     pub token: PToken,
@@ -24,7 +31,8 @@ impl TokenDesc {
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Copy, Serialize)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MatchBounds {
     pub start_idx: usize,
     pub end_idx: usize,
@@ -34,12 +42,84 @@ impl MatchBounds {
     pub fn new(start_idx: usize, end_idx: usize) -> Self {
         Self { start_idx, end_idx }
     }
+
+    /// Converts byte offsets in the input into character offsets, e.g. for use with char_indices.
+    /// The counter keeps the characters counted for the previous bounds.
+    pub(crate) fn to_char_bounds(&self, input: &str, counter: &mut CharCounter) -> Self {
+        let start_idx = counter.char_idx(input, self.start_idx);
+        Self {
+            start_idx,
+            end_idx: start_idx + input[self.start_idx..self.end_idx].chars().count(),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+/// Counts characters of the input up to the given byte offsets, offsets usually grow, so every
+/// character is counted once instead of counting from the start of the input for every match.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct CharCounter {
+    byte_idx: usize,
+    char_idx: usize,
+}
+
+impl CharCounter {
+    pub(crate) fn char_idx(&mut self, input: &str, byte_idx: usize) -> usize {
+        if byte_idx < self.byte_idx {
+            *self = CharCounter::default();
+        }
+        self.char_idx += input[self.byte_idx..byte_idx].chars().count();
+        self.byte_idx = byte_idx;
+        self.char_idx
+    }
+}
+
+// time::Duration has no serde support, so it is stored as a number of seconds
+#[cfg(feature = "serde")]
+mod duration_seconds {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        duration: &time::Duration,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(duration.num_seconds())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<time::Duration, D::Error> {
+        i64::deserialize(deserializer).map(time::Duration::seconds)
+    }
+}
+
+/// Repetition of an event, e.g. "every other monday" is every 2 weeks on monday.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Recurrence {
+    pub unit: TimeInterval,
+    /// number of units between repetitions
+    pub step: u32,
+    /// day of week for weekly repetitions
+    pub weekday: Option<Weekday>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Context {
-    // relative value
+    // relative values, months and years have no fixed length so they are kept apart from the
+    // duration and applied by the calendar, e.g. "in a month" on january 31st is february 28th
+    pub years: i32,
+    pub months: i32,
+    #[cfg_attr(feature = "serde", serde(with = "duration_seconds"))]
     pub duration: time::Duration,
+    // the time of day has passed, so one day of the duration moves it to the next day, a date
+    // given along with the time replaces the move, e.g. "at 5" in "friday at 5"
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) rolled: bool,
+    // the day starts at midnight unless a time of day is given, e.g. "tomorrow" with
+    // ParseConfig::zero_time_of_day set
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) zero_time: bool,
 
     // absolute values
     pub year: Option<i32>,
@@ -47,6 +127,24 @@ pub struct Context {
     pub day: Option<i32>,
     pub hour: Option<i32>,
     pub minute: Option<i32>,
+    pub second: Option<i32>,
+    // offset from UTC in seconds the absolute values are given in, e.g. "5pm EST"
+    pub utc_offset: Option<i32>,
+
+    // relative value applied after the absolute ones, e.g. "minus 30 minutes" in
+    // "noon minus 30 minutes"
+    #[cfg_attr(feature = "serde", serde(with = "duration_seconds"))]
+    pub adjustment: time::Duration,
+
+    // end of a range, relative to the same base time as the start
+    pub end: Option<Box<Context>>,
+
+    // repetition, the values above describe its first occurrence if any
+    pub recurrence: Option<Recurrence>,
+
+    // further items of a list, e.g. "wednesday and friday" in "monday, wednesday and friday",
+    // relative to the same base time as the first item described by the values above
+    pub list: Vec<Context>,
 }
 
 impl Context {
@@ -57,8 +155,102 @@ impl Context {
         self.duration = time::Duration::seconds(i64::from(duration));
     }
 
+    // moves a time of day which has passed to the next day, e.g. "at 5" said at 19:00
+    pub(crate) fn roll_day(&mut self) {
+        self.set_duration(consts::DAY);
+        self.rolled = true;
+    }
+
+    // tells whether a date is set, e.g. "next friday", "march 13"
+    pub(crate) fn has_date(&self) -> bool {
+        self.duration.num_seconds() != 0 && !self.rolled
+            || self.years != 0
+            || self.months != 0
+            || self.year.is_some()
+            || self.month.is_some()
+            || self.day.is_some()
+    }
+
+    // tells whether a time of day is set, e.g. "5pm", "tomorrow evening"
+    pub(crate) fn has_time(&self) -> bool {
+        self.hour.is_some() || self.minute.is_some()
+    }
+
+    /// Shifts the resulting date/time, range end and list items included, by the given number of
+    /// seconds after the absolute values are applied.
+    pub(crate) fn adjust(&mut self, seconds: i64) {
+        self.adjustment = self.adjustment + time::Duration::seconds(seconds);
+        if let Some(end) = &mut self.end {
+            end.adjust(seconds);
+        }
+        for item in &mut self.list {
+            item.adjust(seconds);
+        }
+    }
+
     pub fn update(&mut self, other: &Self) {
-        self.duration = self.duration + other.duration;
+        // values shared by both range ends, e.g. "friday" in "friday from 9am to 5pm", apply
+        // to the end as well
+        let end = match (&self.end, &other.end) {
+            (Some(end), None) => {
+                let mut end = end.clone();
+                end.update(other);
+                Some(end)
+            }
+            (None, Some(other_end)) => {
+                let mut end = Box::new(self.clone());
+                end.update(other_end);
+                Some(end)
+            }
+            (Some(end), Some(other_end)) => {
+                let mut end = end.clone();
+                end.update(other_end);
+                Some(end)
+            }
+            (None, None) => None,
+        };
+        self.end = end;
+
+        // the same goes for list items, e.g. "5pm" in "monday and friday at 5pm"
+        let list = if other.list.is_empty() {
+            self.list
+                .iter()
+                .map(|item| {
+                    let mut item = item.clone();
+                    item.update(other);
+                    item
+                })
+                .collect()
+        } else {
+            other
+                .list
+                .iter()
+                .map(|other_item| {
+                    let mut item = self.clone();
+                    item.list.clear();
+                    item.update(other_item);
+                    item
+                })
+                .collect()
+        };
+        self.list = list;
+
+        // a date decides the day of a rolled time on its own
+        let mut other_duration = other.duration;
+        if other.rolled && self.has_date() {
+            other_duration = other_duration - time::Duration::days(1);
+        } else if self.rolled && other.has_date() {
+            self.duration = self.duration - time::Duration::days(1);
+            self.rolled = false;
+        } else {
+            self.rolled = self.rolled || other.rolled;
+        }
+        self.zero_time = self.zero_time || other.zero_time;
+
+        self.years += other.years;
+        self.months += other.months;
+        self.duration = self.duration + other_duration;
+        self.adjustment = self.adjustment + other.adjustment;
         if other.year.is_some() {
             self.year = other.year;
         }
@@ -74,18 +266,150 @@ impl Context {
         if other.minute.is_some() {
             self.minute = other.minute;
         }
+        if other.second.is_some() {
+            self.second = other.second;
+        }
+        if other.utc_offset.is_some() {
+            self.utc_offset = other.utc_offset;
+        }
+        if other.recurrence.is_some() {
+            self.recurrence = other.recurrence.clone();
+        }
+    }
+
+    /// Applies relative and absolute values to the base date/time, relative shift goes first and
+    /// the adjustment goes last. Years and months are added before the duration.
+    pub fn apply<Tz: TimeZone>(&self, base: DateTime<Tz>) -> Result<DateTime<Tz>, DateTimeError> {
+        if !self.adjustment.is_zero() {
+            let anchor = Context {
+                adjustment: time::Duration::zero(),
+                ..self.clone()
+            };
+            return anchor
+                .apply(base)?
+                .checked_add_signed(self.adjustment)
+                .ok_or_else(|| out_of_range_error("adjustment", self.adjustment.num_seconds()));
+        }
+
+        let shifted = shift_months(base, i64::from(self.years) * 12 + i64::from(self.months))?
+            .checked_add_signed(self.duration)
+            .ok_or_else(|| out_of_range_error("duration", self.duration.num_seconds()))?;
+        let tz = shifted.timezone();
+        let offset = match self.utc_offset {
+            Some(seconds) => Some(
+                FixedOffset::east_opt(seconds)
+                    .ok_or_else(|| out_of_range_error("utc offset", i64::from(seconds)))?,
+            ),
+            None => None,
+        };
+        // absolute values are relative to the explicitly given timezone if any
+        let local = match offset {
+            Some(offset) => shifted.with_timezone(&offset).naive_local(),
+            None => shifted.naive_local(),
+        };
+
+        let mut year = self.year.unwrap_or_else(|| local.year());
+        let mut month = self.month.unwrap_or(local.month() as i32);
+        // relative values like "in 10 months" or "3 months ago" may go beyond the year
+        year += (month - 1).div_euclid(12);
+        month = (month - 1).rem_euclid(12) + 1;
+
+        if NaiveDate::from_ymd_opt(year, month as u32, 1).is_none() {
+            return Err(out_of_range_error("year", i64::from(year)));
+        }
+        // the day of the base date may not exist in the target month, e.g. february 29th in
+        // "in a year", the last day of the month is taken then
+        let day = self
+            .day
+            .unwrap_or_else(|| (local.day() as i32).min(days_in_month(year, month as u32) as i32));
+        let date = NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+            .ok_or_else(|| out_of_range_error("day", i64::from(day)))?;
+
+        let (hour, minute) = match (self.hour, self.minute) {
+            (None, None) if self.zero_time => (0, 0),
+            (hour, minute) => (
+                hour.unwrap_or(local.hour() as i32),
+                minute.unwrap_or(local.minute() as i32),
+            ),
+        };
+        // drop seconds unless they were set explicitly or the shift is not in whole minutes
+        let second = self
+            .second
+            .unwrap_or(if self.duration.num_seconds() % 60 == 0 {
+                0
+            } else {
+                local.second() as i32
+            });
+        let time = NaiveTime::from_hms_opt(hour as u32, minute as u32, second as u32)
+            .ok_or_else(|| out_of_range_error("hour", i64::from(hour)))?;
+
+        let naive = NaiveDateTime::new(date, time);
+        if naive == local.with_nanosecond(0).unwrap() {
+            // nothing has changed, keep the exact instant which might be ambiguous in local time
+            return Ok(shifted.with_nanosecond(0).unwrap());
+        }
+
+        if let Some(offset) = offset {
+            return offset
+                .from_local_datetime(&naive)
+                .single()
+                .map(|date_time| date_time.with_timezone(&tz))
+                .ok_or_else(|| out_of_range_error("hour", i64::from(hour)));
+        }
+
+        // local time may not exist at all or happen twice due to DST transitions
+        tz.from_local_datetime(&naive)
+            .earliest()
+            .ok_or_else(|| out_of_range_error("hour", i64::from(hour)))
     }
 }
 
+// the day is clamped to the length of the target month, the time of day stays the same
+fn shift_months<Tz: TimeZone>(
+    base: DateTime<Tz>,
+    months: i64,
+) -> Result<DateTime<Tz>, DateTimeError> {
+    if months == 0 {
+        return Ok(base);
+    }
+
+    let local = base.naive_local();
+    let total = i64::from(local.year()) * 12 + i64::from(local.month0()) + months;
+    let year = total.div_euclid(12);
+    let month = total.rem_euclid(12) as u32 + 1;
+    let date = i32::try_from(year)
+        .ok()
+        .and_then(|year| {
+            NaiveDate::from_ymd_opt(year, month, local.day().min(days_in_month(year, month)))
+        })
+        .ok_or_else(|| out_of_range_error("year", year))?;
+
+    // local time may not exist in the target day due to DST transitions
+    base.timezone()
+        .from_local_datetime(&date.and_time(local.time()))
+        .earliest()
+        .ok_or_else(|| out_of_range_error("months", months))
+}
+
 impl Default for Context {
     fn default() -> Self {
         Context {
+            years: 0,
+            months: 0,
             duration: time::Duration::zero(),
+            rolled: false,
+            zero_time: false,
             year: None,
             month: None,
             day: None,
             hour: None,
             minute: None,
+            second: None,
+            utc_offset: None,
+            adjustment: time::Duration::zero(),
+            end: None,
+            recurrence: None,
+            list: Vec::new(),
         }
     }
 }
@@ -95,12 +419,18 @@ pub(crate) struct RuleResult<'a> {
     pub tail: &'a str,
     pub tokens: Option<Vec<PToken>>,
     pub bounds: Option<MatchBounds>,
+    // sum of edit distances of the matched words
+    pub distance: usize,
 
     pub context: Context,
 }
 
 pub(crate) type FnRule<Tz> =
-    for<'r> fn(&'r str, bool, DateTime<Tz>) -> Result<RuleResult<'r>, SemanticError<'r>>;
+    for<'r> fn(&'r str, &ParseConfig, DateTime<Tz>) -> Result<RuleResult<'r>, SemanticError<'r>>;
+
+/// Rule added at runtime, see RuleRegistry. It gets the input left after its previous match and
+/// returns the nearest match in it if any, bounds of the match are byte offsets in that input.
+pub type CustomRule<Tz> = dyn Fn(&str, &ParseConfig, DateTime<Tz>) -> Option<MatchResult>;
 
 impl<'a> RuleResult<'a> {
     pub fn new() -> Self {
@@ -108,11 +438,34 @@ impl<'a> RuleResult<'a> {
             tail: "",
             tokens: None,
             bounds: None,
+            distance: 0,
             context: Default::default(),
         }
     }
 
+    /// Result of a custom rule, matches which are empty or don't fit the input are ignored, so
+    /// that a rule can't make the parser loop forever.
+    pub(crate) fn from_custom(input: &'a str, found: Option<MatchResult>) -> Self {
+        let mut res = Self::new();
+        res.set_tail(input);
+        if let Some(found) = found {
+            let bounds = found.get_bounds();
+            if bounds.start_idx < bounds.end_idx
+                && bounds.end_idx <= input.len()
+                && input.is_char_boundary(bounds.start_idx)
+                && input.is_char_boundary(bounds.end_idx)
+            {
+                res.set_bounds(Some(bounds));
+                res.set_tail(&input[bounds.end_idx..]);
+                res.distance = found.distance();
+                res.set_context(found.get_timeshift().clone());
+            }
+        }
+        res
+    }
+
     pub fn set_token(&mut self, token_descr: &TokenDesc) -> &mut Self {
+        self.distance += token_descr.dist.0;
         if token_descr.token != PToken::Stub {
             if let Some(ref mut tokens) = self.tokens {
                 tokens.push(token_descr.clone_content());
@@ -123,6 +476,32 @@ impl<'a> RuleResult<'a> {
         self
     }
 
+    /// Tells whether the match counts with a vague quantity, e.g. "a few hours"
+    pub(crate) fn is_approximate(&self) -> bool {
+        self.tokens.as_ref().map_or(false, |tokens| {
+            tokens.iter().any(|t| match t {
+                PToken::PToken(Token::Adverbs(Adverbs::Few), _)
+                | PToken::PToken(Token::Adverbs(Adverbs::Couple), _)
+                | PToken::PToken(Token::Adverbs(Adverbs::Several), _) => true,
+                _ => false,
+            })
+        })
+    }
+
+    /// Finds all tokens which satisfy required priority in order of their appearance
+    pub(crate) fn tokens_by_priority(&self, priority: Priority) -> Vec<Token> {
+        match &self.tokens {
+            Some(tokens) => tokens
+                .iter()
+                .filter_map(|t| match t {
+                    PToken::PToken(token, p) if *p == priority => Some(token.clone()),
+                    _ => None,
+                })
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
     /// Finds the first token which satisfies required priority
     pub(crate) fn token_by_priority(&self, priority: Priority) -> Option<Token> {
         match &self.tokens {
@@ -144,6 +523,26 @@ impl<'a> RuleResult<'a> {
         }
     }
 
+    /// Splits tokens of a range match into tokens of its start and tokens of its end, the range
+    /// connector is recognized by its priority and dropped. None if there is no connector.
+    pub(crate) fn split_range(&self, connector: Priority) -> Option<(Self, Self)> {
+        let tokens = self.tokens.as_ref()?;
+        let idx = tokens.iter().position(|t| match t {
+            PToken::PToken(_, p) => *p == connector,
+            _ => false,
+        })?;
+
+        let part = |tokens: &[PToken]| RuleResult {
+            tail: self.tail,
+            tokens: Some(tokens.to_vec()),
+            bounds: self.bounds,
+            distance: 0,
+            context: Context::default(),
+        };
+
+        Some((part(&tokens[..idx]), part(&tokens[idx + 1..])))
+    }
+
     pub fn set_tail(&mut self, tail: &'a str) {
         self.tail = tail;
     }
@@ -161,6 +560,11 @@ impl<'a> RuleResult<'a> {
         self.context.duration.num_seconds()
     }
 
+    #[cfg(test)]
+    pub fn get_seconds(&self) -> i32 {
+        self.context.second.unwrap_or(0)
+    }
+
     #[cfg(test)]
     pub fn get_minutes(&self) -> i32 {
         self.context.minute.unwrap_or(0)
@@ -185,12 +589,30 @@ impl<'a> RuleResult<'a> {
     pub fn get_year(&self) -> i32 {
         self.context.year.unwrap_or(0)
     }
+
+    #[cfg(test)]
+    pub fn get_months(&self) -> i32 {
+        self.context.months
+    }
+
+    #[cfg(test)]
+    pub fn get_years(&self) -> i32 {
+        self.context.years
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
+// maximum number of characters between matches MatchResult::try_merge accepts, e.g. " at "
+const MAX_MERGE_GAP: usize = 5;
+
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MatchResult {
     bounds: MatchBounds,
     time_shift: Context,
+    matched_text: String,
+    approximate: bool,
+    negated: bool,
+    distance: usize,
 }
 
 impl MatchResult {
@@ -198,18 +620,258 @@ impl MatchResult {
         Self {
             bounds: MatchBounds::new(start_idx, end_idx),
             time_shift,
+            matched_text: String::new(),
+            approximate: false,
+            negated: false,
+            distance: 0,
         }
     }
 
+    pub(crate) fn set_matched_text(&mut self, matched_text: &str) {
+        self.matched_text = matched_text.to_owned();
+    }
+
+    /// Part of the input string the match was found in, as it is in the input.
+    pub fn get_matched_text(&self) -> &str {
+        &self.matched_text
+    }
+
+    pub(crate) fn set_approximate(&mut self, approximate: bool) {
+        self.approximate = approximate;
+    }
+
+    /// Tells whether the date/time was marked as approximate, e.g. "around 5pm", the marker is
+    /// a part of the match, but doesn't change the resulting date/time.
+    pub fn is_approximate(&self) -> bool {
+        self.approximate
+    }
+
+    pub(crate) fn set_negated(&mut self, negated: bool) {
+        self.negated = negated;
+    }
+
+    /// Tells whether the date/time is negated, e.g. "not tomorrow", the negation word is not a part
+    /// of the match and doesn't change the resulting date/time.
+    pub fn is_negated(&self) -> bool {
+        self.negated
+    }
+
+    pub(crate) fn set_distance(&mut self, distance: usize) {
+        self.distance = distance;
+    }
+
+    /// Total number of typos in the matched words, i.e. the sum of their Damerau-Levenshtein
+    /// distances to the patterns, zero for exact matches. It allows to drop sloppy matches.
+    pub fn distance(&self) -> usize {
+        self.distance
+    }
+
+    pub(crate) fn get_bounds(&self) -> MatchBounds {
+        self.bounds
+    }
+
+    pub(crate) fn set_bounds(&mut self, bounds: MatchBounds) {
+        self.bounds = bounds;
+    }
+
     pub fn get_timeshift(&self) -> &Context {
         &self.time_shift
     }
 
+    /// Converts the match into an absolute date/time relative to the base one.
+    pub fn resolve<Tz: TimeZone>(&self, base: DateTime<Tz>) -> Result<DateTime<Tz>, DateTimeError> {
+        self.time_shift.apply(base)
+    }
+
+    /// Tells whether the match is a span of time, e.g. "monday to friday", rather than a point.
+    pub fn is_range(&self) -> bool {
+        self.time_shift.end.is_some()
+    }
+
+    /// Tells whether the match lists several dates, e.g. "monday, wednesday and friday".
+    pub fn is_list(&self) -> bool {
+        !self.time_shift.list.is_empty()
+    }
+
+    /// Converts every item of a list into an absolute date/time in order of their appearance, the
+    /// list of a match which is not a list consists of the match itself.
+    pub fn resolve_list<Tz: TimeZone>(
+        &self,
+        base: DateTime<Tz>,
+    ) -> Result<Vec<DateTime<Tz>>, DateTimeError> {
+        std::iter::once(&self.time_shift)
+            .chain(self.time_shift.list.iter())
+            .map(|item| item.apply(base.clone()))
+            .collect()
+    }
+
+    /// Repetition described by the match, e.g. "every monday", None for one-off events.
+    pub fn get_recurrence(&self) -> Option<&Recurrence> {
+        self.time_shift.recurrence.as_ref()
+    }
+
+    /// Converts the end of a range into an absolute date/time, None if the match is not a range.
+    /// A range of times which ends before its start goes over midnight, e.g. "from 9pm to 2am",
+    /// so its end is on the next day.
+    pub fn resolve_end<Tz: TimeZone>(
+        &self,
+        base: DateTime<Tz>,
+    ) -> Result<Option<DateTime<Tz>>, DateTimeError> {
+        let end = match &self.time_shift.end {
+            Some(end) => end.apply(base.clone())?,
+            None => return Ok(None),
+        };
+        let start = self.time_shift.apply(base)?;
+        let day = time::Duration::days(1);
+        if end <= start && start - end.clone() < day {
+            return Ok(Some(end + day));
+        }
+        Ok(Some(end))
+    }
+
+    /// Index of the first character of the match in the input, counted in characters.
     pub fn get_start_idx(&self) -> usize {
         self.bounds.start_idx
     }
 
+    /// Index of the character right after the match, counted in characters.
     pub fn get_end_idx(&self) -> usize {
         self.bounds.end_idx
     }
+
+    /// Merges the match with the other one right after it if one of them is a date and the other
+    /// one is a time, e.g. "5pm" and "tomorrow". Returns None for overlapping matches, matches
+    /// far apart from each other and matches which both set a date or a time. The text between the
+    /// matches is unknown, so matched texts are joined with a space.
+    pub fn try_merge(&self, other: &MatchResult) -> Option<MatchResult> {
+        if other.get_start_idx() < self.get_end_idx()
+            || other.get_start_idx() - self.get_end_idx() > MAX_MERGE_GAP
+        {
+            return None;
+        }
+
+        let (first, second) = (&self.time_shift, &other.time_shift);
+        let is_date_time = |date: &Context, time: &Context| {
+            date.has_date() && !date.has_time() && time.has_time() && !time.has_date()
+        };
+        if !(is_date_time(first, second) || is_date_time(second, first))
+            || first.end.is_some()
+            || second.end.is_some()
+        {
+            return None;
+        }
+
+        let mut time_shift = first.clone();
+        time_shift.update(second);
+
+        let mut merged = MatchResult::new(time_shift, self.get_start_idx(), other.get_end_idx());
+        if other.get_start_idx() == self.get_end_idx() {
+            merged.set_matched_text(&format!("{}{}", self.matched_text, other.matched_text));
+        } else {
+            merged.set_matched_text(&format!("{} {}", self.matched_text, other.matched_text));
+        }
+        merged.set_approximate(self.approximate || other.approximate);
+        merged.set_negated(self.negated);
+        merged.set_distance(self.distance + other.distance);
+        Some(merged)
+    }
+
+    /// Short human readable description for logs, e.g. "[3..14] next friday -> +7 days".
+    pub fn summary(&self) -> String {
+        format!(
+            "[{}..{}] {} -> {}",
+            self.bounds.start_idx,
+            self.bounds.end_idx,
+            self.matched_text,
+            describe_shift(&self.time_shift)
+        )
+    }
+}
+
+fn plural(n: i64, unit: &str) -> String {
+    if n == 1 {
+        format!("{} {}", n, unit)
+    } else {
+        format!("{} {}s", n, unit)
+    }
+}
+
+// e.g. "+2 months", "-1 year"
+fn describe_count(n: i32, unit: &str) -> String {
+    let sign = if n > 0 { "+" } else { "-" };
+    format!("{}{}", sign, plural(i64::from(n).abs(), unit))
+}
+
+// e.g. "+1 day 2 hours", "-30 minutes"
+fn describe_seconds(seconds: i64) -> String {
+    let mut rest = seconds.abs();
+    let mut units = Vec::new();
+    for &(size, unit) in &[
+        (86400, "day"),
+        (3600, "hour"),
+        (60, "minute"),
+        (1, "second"),
+    ] {
+        if rest >= size {
+            units.push(plural(rest / size, unit));
+            rest %= size;
+        }
+    }
+    let sign = if seconds > 0 { "+" } else { "-" };
+    format!("{}{}", sign, units.join(" "))
+}
+
+// relative part goes first, e.g. "+1 day 2 hours hour=17 minute=0"
+fn describe_shift(ctx: &Context) -> String {
+    let mut parts = Vec::new();
+
+    if ctx.years != 0 {
+        parts.push(describe_count(ctx.years, "year"));
+    }
+    if ctx.months != 0 {
+        parts.push(describe_count(ctx.months, "month"));
+    }
+
+    let seconds = ctx.duration.num_seconds();
+    if seconds != 0 {
+        parts.push(describe_seconds(seconds));
+    }
+
+    let fields = [
+        ("year", ctx.year),
+        ("month", ctx.month),
+        ("day", ctx.day),
+        ("hour", ctx.hour),
+        ("minute", ctx.minute),
+        ("second", ctx.second),
+        ("utc_offset", ctx.utc_offset),
+    ];
+    for (name, value) in fields.iter() {
+        if let Some(value) = value {
+            parts.push(format!("{}={}", name, value));
+        }
+    }
+
+    let adjustment = ctx.adjustment.num_seconds();
+    if adjustment != 0 {
+        parts.push(format!("then {}", describe_seconds(adjustment)));
+    }
+
+    if parts.is_empty() {
+        parts.push("now".to_owned());
+    }
+    parts.join(" ")
+}
+
+/// Part of the input, either a match or the text between matches, see en::parse_all.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Segment {
+    Matched(MatchResult),
+    /// text without date/time in it, offsets are in characters just like for matches
+    Unmatched {
+        start: usize,
+        end: usize,
+        text: String,
+    },
 }
@@ -0,0 +1,41 @@
+//! Counters of the work done by a parse, e.g. to tune `ParseConfig::max_edit_distance` for long
+//! inputs, see en::parse_with_stats.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+
+/// Counters of a single parse.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ParseStats {
+    /// Times a rule was applied, every rule is applied again after each of its matches.
+    pub rule_attempts: usize,
+    /// Matches found by the rules, including the ones dropped later as overlapping.
+    pub matches: usize,
+    /// Damerau-Levenshtein distances computed to recognize words, exact matching needs none.
+    pub distance_computations: usize,
+}
+
+thread_local! {
+    // counters of the parse in progress on this thread, none unless they are collected
+    static STATS: Cell<Option<ParseStats>> = Cell::new(None);
+}
+
+/// Updates the counters, it does nothing unless they are collected by `collect`.
+pub(crate) fn record<F: FnOnce(&mut ParseStats)>(update: F) {
+    STATS.with(|cell| {
+        if let Some(mut stats) = cell.get() {
+            update(&mut stats);
+            cell.set(Some(stats));
+        }
+    });
+}
+
+/// Runs the parse and returns its result along with the counters of everything it has done.
+pub(crate) fn collect<T, F: FnOnce() -> T>(parse: F) -> (T, ParseStats) {
+    let outer = STATS.with(|cell| cell.replace(Some(ParseStats::default())));
+    let result = parse();
+    let stats = STATS.with(|cell| cell.replace(outer)).unwrap_or_default();
+    (result, stats)
+}
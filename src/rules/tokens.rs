@@ -1,4 +1,10 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Month {
     January,
     February,
@@ -15,6 +21,7 @@ pub enum Month {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Weekday {
     Monday,
     Tuesday,
@@ -26,16 +33,26 @@ pub enum Weekday {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Pronouns {
     This,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Prepositions {
     Of,
+    Past,
+    To,
+    From,
+    After,
+    Before,
+    At,
+    Between,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum When {
     Within,
     In,
@@ -51,21 +68,25 @@ pub enum When {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum AmPm {
     Am,
     Pm,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TimeOfDay {
     Night,
     Morning,
     Evening,
     Noon,
+    Midnight,
     Afternoon,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum IntWord {
     One,
     Two,
@@ -82,6 +103,7 @@ pub enum IntWord {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Ordinals {
     First,
     Second,
@@ -117,23 +139,30 @@ pub enum Ordinals {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TimeInterval {
     Second,
     Minute,
     Hour,
     Day,
     Week,
+    Fortnight,
     Month,
     Year,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Adverbs {
     Half,
+    Quarter,
     Few,
+    Couple,
+    Several,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Articles {
     A,
     An,
@@ -141,10 +170,42 @@ pub enum Articles {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PeriodPart {
+    Beginning,
+    Middle,
+    End,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Holiday {
+    NewYear,
+    Easter,
+    Halloween,
+    Christmas,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Token {
     Char, // stands for any character
-    Week,
     Ago,
+    Range,         // connects start and end of a range: "to", "until", etc.
+    Every,         // repetition: "every monday"
+    Other,         // every second repetition: "every other week"
+    TimeZone(i32), // offset from UTC in seconds
+    OClock,
+    Weekend,
     Prepositions(Prepositions),
     Ordinals(Ordinals),
     Articles(Articles),
@@ -153,20 +214,350 @@ pub enum Token {
     When(When),
     AmPm(AmPm),
     Number(i32),
+    Year(usize),
     IntWord(IntWord),
     TimeInterval(TimeInterval),
     TimeOfDay(TimeOfDay),
     Adverbs(Adverbs),
     Pronouns(Pronouns),
+    PeriodPart(PeriodPart),
+    Holiday(Holiday),
+    Season(Season),
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Priority(pub isize);
 
 // This enum adds priority value to token, tokens with smaller priority numbers are
 // being parsed first
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PToken {
     Stub,
     PToken(Token, Priority),
+    // several tokens fit equally well
+    Ambiguous(Vec<Token>),
+}
+
+/// Implements Display and FromStr for a token enum using the given names, the names are lowercase
+/// and stable, so the string form may be used as a compact serialization.
+macro_rules! names {
+    ( $enum: ident { $($variant: ident => $name: expr),* } ) => {
+        impl fmt::Display for $enum {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(match self {
+                    $($enum::$variant => $name),*
+                })
+            }
+        }
+
+        impl FromStr for $enum {
+            type Err = ParseTokenError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $($name => Ok($enum::$variant),)*
+                    _ => Err(ParseTokenError(s.to_owned())),
+                }
+            }
+        }
+    };
+}
+
+/// Error returned when a string is not a valid token representation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseTokenError(pub String);
+
+impl fmt::Display for ParseTokenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown token: {}", self.0)
+    }
+}
+
+names!(Month {
+    January => "january",
+    February => "february",
+    March => "march",
+    April => "april",
+    May => "may",
+    June => "june",
+    July => "july",
+    August => "august",
+    September => "september",
+    October => "october",
+    November => "november",
+    December => "december"
+});
+
+names!(Weekday {
+    Monday => "monday",
+    Tuesday => "tuesday",
+    Wednesday => "wednesday",
+    Thursday => "thursday",
+    Friday => "friday",
+    Saturday => "saturday",
+    Sunday => "sunday"
+});
+
+names!(Pronouns {
+    This => "this"
+});
+
+names!(Prepositions {
+    Of => "of",
+    Past => "past",
+    To => "to",
+    From => "from",
+    After => "after",
+    Before => "before",
+    At => "at",
+    Between => "between"
+});
+
+names!(When {
+    Within => "within",
+    In => "in",
+    This => "this",
+    Last => "last",
+    Past => "past",
+    Next => "next",
+    Now => "now",
+    Today => "today",
+    Tonight => "tonight",
+    Tomorrow => "tomorrow",
+    Yesterday => "yesterday"
+});
+
+names!(AmPm {
+    Am => "am",
+    Pm => "pm"
+});
+
+names!(TimeOfDay {
+    Night => "night",
+    Morning => "morning",
+    Evening => "evening",
+    Noon => "noon",
+    Midnight => "midnight",
+    Afternoon => "afternoon"
+});
+
+names!(IntWord {
+    One => "one",
+    Two => "two",
+    Three => "three",
+    Four => "four",
+    Five => "five",
+    Six => "six",
+    Seven => "seven",
+    Eight => "eight",
+    Nine => "nine",
+    Ten => "ten",
+    Eleven => "eleven",
+    Twelve => "twelve"
+});
+
+names!(Ordinals {
+    First => "first",
+    Second => "second",
+    Third => "third",
+    Fourth => "fourth",
+    Fifth => "fifth",
+    Sixth => "sixth",
+    Seventh => "seventh",
+    Eighth => "eighth",
+    Ninth => "ninth",
+    Tenth => "tenth",
+    Eleventh => "eleventh",
+    Twelfth => "twelfth",
+    Thirteenth => "thirteenth",
+    Fourteenth => "fourteenth",
+    Fifteenth => "fifteenth",
+    Sixteenth => "sixteenth",
+    Seventeenth => "seventeenth",
+    Eighteenth => "eighteenth",
+    Nineteenth => "nineteenth",
+    Twentieth => "twentieth",
+    TwentyFirst => "twenty_first",
+    TwentySecond => "twenty_second",
+    TwentyThird => "twenty_third",
+    TwentyFourth => "twenty_fourth",
+    TwentyFifth => "twenty_fifth",
+    TwentySixth => "twenty_sixth",
+    TwentySeventh => "twenty_seventh",
+    TwentyEighth => "twenty_eighth",
+    TwentyNinth => "twenty_ninth",
+    Thirtieth => "thirtieth",
+    ThirtiethFirst => "thirty_first"
+});
+
+names!(TimeInterval {
+    Second => "second",
+    Minute => "minute",
+    Hour => "hour",
+    Day => "day",
+    Week => "week",
+    Fortnight => "fortnight",
+    Month => "month",
+    Year => "year"
+});
+
+names!(Adverbs {
+    Half => "half",
+    Quarter => "quarter",
+    Few => "few",
+    Couple => "couple",
+    Several => "several"
+});
+
+names!(Articles {
+    A => "a",
+    An => "an",
+    The => "the"
+});
+
+names!(PeriodPart {
+    Beginning => "beginning",
+    Middle => "middle",
+    End => "end"
+});
+
+names!(Holiday {
+    NewYear => "new_year",
+    Easter => "easter",
+    Halloween => "halloween",
+    Christmas => "christmas"
+});
+
+names!(Season {
+    Spring => "spring",
+    Summer => "summer",
+    Autumn => "autumn",
+    Winter => "winter"
+});
+
+// tokens are written as "kind:value", e.g. "weekday:friday", "number:5", or just "kind" for tokens
+// without a value, e.g. "ago"
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Char => write!(f, "char"),
+            Token::Ago => write!(f, "ago"),
+            Token::Range => write!(f, "range"),
+            Token::Every => write!(f, "every"),
+            Token::Other => write!(f, "other"),
+            Token::TimeZone(x) => write!(f, "timezone:{}", x),
+            Token::OClock => write!(f, "oclock"),
+            Token::Weekend => write!(f, "weekend"),
+            Token::Prepositions(x) => write!(f, "preposition:{}", x),
+            Token::Ordinals(x) => write!(f, "ordinal:{}", x),
+            Token::Articles(x) => write!(f, "article:{}", x),
+            Token::Month(x) => write!(f, "month:{}", x),
+            Token::Weekday(x) => write!(f, "weekday:{}", x),
+            Token::When(x) => write!(f, "when:{}", x),
+            Token::AmPm(x) => write!(f, "ampm:{}", x),
+            Token::Number(x) => write!(f, "number:{}", x),
+            Token::Year(x) => write!(f, "year:{}", x),
+            Token::IntWord(x) => write!(f, "int_word:{}", x),
+            Token::TimeInterval(x) => write!(f, "interval:{}", x),
+            Token::TimeOfDay(x) => write!(f, "time_of_day:{}", x),
+            Token::Adverbs(x) => write!(f, "adverb:{}", x),
+            Token::Pronouns(x) => write!(f, "pronoun:{}", x),
+            Token::PeriodPart(x) => write!(f, "period_part:{}", x),
+            Token::Holiday(x) => write!(f, "holiday:{}", x),
+            Token::Season(x) => write!(f, "season:{}", x),
+        }
+    }
+}
+
+impl FromStr for Token {
+    type Err = ParseTokenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(2, ':');
+        let kind = parts.next().unwrap_or("");
+        let value = parts.next();
+        let err = || ParseTokenError(s.to_owned());
+
+        match (kind, value) {
+            ("char", None) => Ok(Token::Char),
+            ("ago", None) => Ok(Token::Ago),
+            ("range", None) => Ok(Token::Range),
+            ("every", None) => Ok(Token::Every),
+            ("other", None) => Ok(Token::Other),
+            ("oclock", None) => Ok(Token::OClock),
+            ("weekend", None) => Ok(Token::Weekend),
+            ("timezone", Some(x)) => x.parse().map(Token::TimeZone).map_err(|_| err()),
+            ("preposition", Some(x)) => x.parse().map(Token::Prepositions).map_err(|_| err()),
+            ("ordinal", Some(x)) => x.parse().map(Token::Ordinals).map_err(|_| err()),
+            ("article", Some(x)) => x.parse().map(Token::Articles).map_err(|_| err()),
+            ("month", Some(x)) => x.parse().map(Token::Month).map_err(|_| err()),
+            ("weekday", Some(x)) => x.parse().map(Token::Weekday).map_err(|_| err()),
+            ("when", Some(x)) => x.parse().map(Token::When).map_err(|_| err()),
+            ("ampm", Some(x)) => x.parse().map(Token::AmPm).map_err(|_| err()),
+            ("number", Some(x)) => x.parse().map(Token::Number).map_err(|_| err()),
+            ("year", Some(x)) => x.parse().map(Token::Year).map_err(|_| err()),
+            ("int_word", Some(x)) => x.parse().map(Token::IntWord).map_err(|_| err()),
+            ("interval", Some(x)) => x.parse().map(Token::TimeInterval).map_err(|_| err()),
+            ("time_of_day", Some(x)) => x.parse().map(Token::TimeOfDay).map_err(|_| err()),
+            ("adverb", Some(x)) => x.parse().map(Token::Adverbs).map_err(|_| err()),
+            ("pronoun", Some(x)) => x.parse().map(Token::Pronouns).map_err(|_| err()),
+            ("period_part", Some(x)) => x.parse().map(Token::PeriodPart).map_err(|_| err()),
+            ("holiday", Some(x)) => x.parse().map(Token::Holiday).map_err(|_| err()),
+            ("season", Some(x)) => x.parse().map(Token::Season).map_err(|_| err()),
+            _ => Err(err()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Token, Weekday, When};
+
+    #[test]
+    fn test_display() {
+        let weekdays = [
+            (Weekday::Monday, "weekday:monday"),
+            (Weekday::Tuesday, "weekday:tuesday"),
+            (Weekday::Wednesday, "weekday:wednesday"),
+            (Weekday::Thursday, "weekday:thursday"),
+            (Weekday::Friday, "weekday:friday"),
+            (Weekday::Saturday, "weekday:saturday"),
+            (Weekday::Sunday, "weekday:sunday"),
+        ];
+        for (day, repr) in weekdays.iter() {
+            let token = Token::Weekday(day.clone());
+            assert_eq!(token.to_string(), *repr);
+            assert_eq!(repr.parse::<Token>(), Ok(token));
+        }
+
+        let whens = [
+            (When::Within, "when:within"),
+            (When::In, "when:in"),
+            (When::This, "when:this"),
+            (When::Last, "when:last"),
+            (When::Past, "when:past"),
+            (When::Next, "when:next"),
+            (When::Now, "when:now"),
+            (When::Today, "when:today"),
+            (When::Tonight, "when:tonight"),
+            (When::Tomorrow, "when:tomorrow"),
+            (When::Yesterday, "when:yesterday"),
+        ];
+        for (when, repr) in whens.iter() {
+            let token = Token::When(when.clone());
+            assert_eq!(token.to_string(), *repr);
+            assert_eq!(repr.parse::<Token>(), Ok(token));
+        }
+
+        assert_eq!(Token::Number(5).to_string(), "number:5");
+        assert_eq!("number:5".parse::<Token>(), Ok(Token::Number(5)));
+        assert_eq!(Token::Year(2020).to_string(), "year:2020");
+        assert_eq!("year:2020".parse::<Token>(), Ok(Token::Year(2020)));
+        assert_eq!(Token::Ago.to_string(), "ago");
+        assert_eq!("ago".parse::<Token>(), Ok(Token::Ago));
+        assert!("weekday:funday".parse::<Token>().is_err());
+        assert!("ago:5".parse::<Token>().is_err());
+    }
 }
@@ -1,4 +1,4 @@
-use chrono::{offset::TimeZone, NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{offset::TimeZone, Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Weekday};
 use std::str::FromStr;
 use when;
 use when::Parser;
@@ -93,6 +93,109 @@ fn test_overlap_error() {
     );
 }
 
+#[test]
+fn test_errors() {
+    let tz_aware = chrono_tz::Europe::Moscow.from_utc_datetime(&fixed_time());
+
+    // it is friday, so it may be both past and next monday
+    let res = when::en(tz_aware, "this monday", &when::ParseConfig::default());
+    assert_eq!(
+        res[0],
+        Err(when::errors::DateTimeError::AmbiguousTime {
+            msg: "this monday".to_owned()
+        })
+    );
+
+    let res = when::en(tz_aware, "at 24:10", &when::ParseConfig::default());
+    let err = res[0].as_ref().unwrap_err();
+    assert_eq!(
+        *err,
+        when::errors::DateTimeError::InvalidTime {
+            text: "24:10".to_owned(),
+            what: "hours".to_owned(),
+            value: 24,
+        }
+    );
+
+    // errors work with the standard error handling
+    let err: &dyn std::error::Error = err;
+    assert_eq!(err.to_string(), "invalid time in: 24:10, hours can't be 24");
+
+    // no date/time in the input is not an error
+    let res = when::en(tz_aware, "nothing here", &when::ParseConfig::default());
+    assert!(res.is_empty());
+}
+
+#[test]
+fn test_error_variants() {
+    let tz_aware = chrono_tz::Europe::Moscow.from_utc_datetime(&fixed_time());
+    let config = when::ParseConfig::default();
+
+    let res = when::en(tz_aware, "this monday", &config);
+    assert_eq!(
+        res,
+        vec![Err(when::DateTimeError::AmbiguousTime {
+            msg: "this monday".to_owned()
+        })]
+    );
+
+    let res = when::en(tz_aware, "at 24:10", &config);
+    assert_eq!(
+        res,
+        vec![Err(when::DateTimeError::InvalidTime {
+            text: "24:10".to_owned(),
+            what: "hours".to_owned(),
+            value: 24,
+        })]
+    );
+
+    let res = when::en(tz_aware, "on february 30", &config);
+    assert_eq!(
+        res,
+        vec![Err(when::DateTimeError::InvalidDate {
+            text: "february 30".to_owned(),
+            what: "day".to_owned(),
+            value: 30,
+        })]
+    );
+    let err: &dyn std::error::Error = res[0].as_ref().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid date in: february 30, day can't be 30"
+    );
+
+    let res = when::en(tz_aware, "next march 13 seconds ago", &config);
+    assert_eq!(
+        res,
+        vec![Err(when::DateTimeError::IntersectionError {
+            text: "march 13 seconds ago".to_owned()
+        })]
+    );
+
+    // "munday" is one typo away from both monday and sunday
+    let res = when::en(tz_aware, "next munday", &config);
+    assert_eq!(
+        res,
+        vec![Err(when::DateTimeError::Ambiguous {
+            text: "next munday".to_owned(),
+            candidates: vec![
+                when::tokens::Token::Weekday(when::tokens::Weekday::Monday),
+                when::tokens::Token::Weekday(when::tokens::Weekday::Sunday),
+            ],
+        })]
+    );
+
+    // a match is fine, its date/time can't be resolved
+    let res = when::en(tz_aware, "in 300000 years", &config);
+    assert_eq!(
+        res[0].as_ref().unwrap().resolve(tz_aware),
+        Err(when::DateTimeError::OutOfRange {
+            what: "year".to_owned(),
+            value: 302018,
+        })
+    );
+}
+
 #[test]
 fn test_seconds_delta() {
     let parser = when::parser::Parser::new(chrono_tz::Europe::Moscow);
@@ -114,4 +217,1375 @@ fn test_various_requests() {
 
     let parser = when::parser::Parser::new(chrono_tz::Europe::Moscow);
     assert_date_time(parser, "in 1 hour", &["2018-08-03T16:34:00"], 1);
+
+    let parser = when::parser::Parser::new(chrono_tz::Europe::Moscow);
+    assert_date_time(parser, "due 2019-01-05", &["2019-01-05T15:34:00"], 1);
+
+    // "next week" also matches on its own, but the longer match covers it
+    let parser = when::parser::Parser::new(chrono_tz::Europe::Moscow);
+    assert_date_time(
+        parser,
+        "by the end of next week",
+        &["2018-08-12T15:34:00"],
+        1,
+    );
+
+    let parser = when::parser::Parser::new(chrono_tz::Europe::Moscow);
+    assert_date_time(
+        parser,
+        "beginning of next month",
+        &["2018-09-01T15:34:00"],
+        1,
+    );
+
+    let parser = when::parser::Parser::new(chrono_tz::Europe::Moscow);
+    assert_date_time(parser, "two fridays from now", &["2018-08-17T15:34:00"], 1);
+}
+
+#[test]
+fn test_exact_parse() {
+    let tz_aware = chrono_tz::Europe::Moscow.from_utc_datetime(&fixed_time());
+
+    let res = when::en(tz_aware, "satarday", &when::ParseConfig::default());
+    assert_eq!(res.len(), 1);
+    assert!(res[0].is_ok());
+
+    assert!(when::en::parse_exact(tz_aware, "satarday").is_empty());
+    assert_eq!(when::en::parse_exact(tz_aware, "saturday").len(), 1);
+
+    // possessive "'s" is not a part of the word
+    let res = when::en::parse_exact(tz_aware, "saturday's meeting");
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].as_ref().unwrap().get_end_idx(), 10);
+
+    // quotes are not a part of the word
+    let res = when::en::parse_exact(tz_aware, "on 'saturday'");
+    let match_result = res[0].as_ref().unwrap();
+    assert_eq!(match_result.get_start_idx(), 4);
+    assert_eq!(match_result.get_end_idx(), 12);
+}
+
+#[test]
+fn test_parser_config() {
+    let tz = chrono_tz::Europe::Moscow;
+    let tz_aware = tz.from_utc_datetime(&fixed_time());
+    let config = when::ParseConfig {
+        max_edit_distance: Some(1),
+        ..Default::default()
+    };
+
+    let parser = Parser::new(tz).config(config);
+    assert_eq!(parser.get_config(), &config);
+
+    for input in &["next friday", "satarday", "nxt friday", "in 3 dys"] {
+        let expected: Vec<_> = when::en(tz_aware, input, &config)
+            .into_iter()
+            .map(|item| item.and_then(|match_result| match_result.resolve(tz_aware)))
+            .collect();
+        assert_eq!(parser.parse_fixed_time(fixed_time(), input), expected);
+    }
+
+    // named settings are the same as the config fields
+    let parser = Parser::new(chrono_tz::UTC)
+        .timezone(tz)
+        .exact(false)
+        .max_distance(1);
+    assert_eq!(parser.get_tz(), &tz);
+    assert_eq!(parser.get_config(), &config);
+    assert_eq!(
+        parser.parse_fixed_time(fixed_time(), "nxt friday"),
+        Parser::new(tz)
+            .config(config)
+            .parse_fixed_time(fixed_time(), "nxt friday")
+    );
+
+    let parser = Parser::new(tz).exact(true);
+    assert!(parser.get_config().exact_match);
+    assert!(parser.parse_fixed_time(fixed_time(), "satarday").is_empty());
+}
+
+#[test]
+fn test_max_matches() {
+    let tz_aware = chrono_tz::Europe::Moscow.from_utc_datetime(&fixed_time());
+    let input = "next monday, next wednesday and next friday";
+
+    let config = when::ParseConfig {
+        max_matches: Some(2),
+        ..Default::default()
+    };
+    let res = when::en(tz_aware, input, &config);
+    assert_eq!(res.len(), 2);
+    assert_eq!(res[0].as_ref().unwrap().get_matched_text(), "next monday");
+    assert_eq!(
+        res[1].as_ref().unwrap().get_matched_text(),
+        "next wednesday"
+    );
+
+    assert_eq!(when::en(tz_aware, input, &Default::default()).len(), 3);
+
+    // the leftmost matches are kept regardless of the order of the rules
+    let config = when::ParseConfig {
+        max_matches: Some(1),
+        ..Default::default()
+    };
+    let res = when::en(tz_aware, "tomorrow then next monday", &config);
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].as_ref().unwrap().get_matched_text(), "tomorrow");
+
+    // matches dropped as parts of longer ones don't make longer matches disappear
+    let res = when::en(tz_aware, "end of next week and tomorrow", &config);
+    assert_eq!(res.len(), 1);
+    assert_eq!(
+        res[0].as_ref().unwrap().get_matched_text(),
+        "end of next week"
+    );
+}
+
+#[test]
+fn test_disabled_rules() {
+    let tz_aware = chrono_tz::Europe::Moscow.from_utc_datetime(&fixed_time());
+
+    let config = when::ParseConfig {
+        rules: when::RuleSet::all().without(when::RuleKind::Time),
+        ..Default::default()
+    };
+    let res = when::en(tz_aware, "5 next friday", &config);
+    assert_eq!(res.len(), 1);
+    let match_result = res[0].as_ref().unwrap();
+    assert_eq!(match_result.get_start_idx(), 2);
+    assert_eq!(match_result.get_end_idx(), 13);
+
+    let config = when::ParseConfig {
+        rules: when::RuleSet::only(&[when::RuleKind::Time]),
+        ..Default::default()
+    };
+    assert!(when::en(tz_aware, "next friday", &config).is_empty());
+}
+
+#[test]
+fn test_matched_text() {
+    let tz_aware = chrono_tz::Europe::Moscow.from_utc_datetime(&fixed_time());
+
+    let res = when::en(
+        tz_aware,
+        "Meet me next Friday or at 10:30",
+        &when::ParseConfig::default(),
+    );
+    assert_eq!(res.len(), 2);
+    assert_eq!(res[0].as_ref().unwrap().get_matched_text(), "next Friday");
+    assert_eq!(res[1].as_ref().unwrap().get_matched_text(), "10:30");
+
+    // bounds count characters, not bytes
+    let res = when::fr::parse(
+        tz_aware,
+        "réunion très importante lundi prochain",
+        &when::ParseConfig::default(),
+    );
+    let match_result = res[0].as_ref().unwrap();
+    assert_eq!(match_result.get_matched_text(), "lundi prochain");
+    assert_eq!(match_result.get_start_idx(), 24);
+    assert_eq!(match_result.get_end_idx(), 38);
+
+    let input = "café next monday";
+    let res = when::en(tz_aware, input, &when::ParseConfig::default());
+    let match_result = res[0].as_ref().unwrap();
+    assert_eq!(match_result.get_start_idx(), 5);
+    assert_eq!(match_result.get_end_idx(), 16);
+    let text: String = input
+        .chars()
+        .skip(match_result.get_start_idx())
+        .take(match_result.get_end_idx() - match_result.get_start_idx())
+        .collect();
+    assert_eq!(text, "next monday");
+}
+
+#[test]
+fn test_russian() {
+    let tz_aware = chrono_tz::Europe::Moscow.from_utc_datetime(&fixed_time());
+
+    let res = when::ru::parse(
+        tz_aware,
+        "встреча в следующую пятницу",
+        &when::ParseConfig::default(),
+    );
+    assert_eq!(res.len(), 1);
+    let match_result = res[0].as_ref().unwrap();
+    assert_eq!(match_result.get_matched_text(), "следующую пятницу");
+    assert_eq!(match_result.get_start_idx(), 10);
+    assert_eq!(match_result.get_end_idx(), 27);
+}
+
+#[test]
+fn test_date_with_time() {
+    let tz = chrono_tz::Europe::Moscow;
+    let tz_aware = tz.from_utc_datetime(&fixed_time());
+
+    let res = when::en(
+        tz_aware,
+        "let's meet next friday at 5pm",
+        &when::ParseConfig::default(),
+    );
+    assert_eq!(res.len(), 1);
+    let match_result = res[0].as_ref().unwrap();
+    assert_eq!(match_result.get_matched_text(), "next friday at 5pm");
+    assert_eq!(match_result.get_start_idx(), 11);
+    assert_eq!(match_result.get_end_idx(), 29);
+    assert_eq!(
+        match_result.resolve(tz_aware),
+        Ok(tz.ymd(2018, 8, 10).and_hms(17, 0, 0))
+    );
+
+    let res = when::en(
+        tz_aware,
+        "next monday at midnight",
+        &when::ParseConfig::default(),
+    );
+    assert_eq!(res.len(), 1);
+    assert_eq!(
+        res[0].as_ref().unwrap().resolve(tz_aware),
+        Ok(tz.ymd(2018, 8, 6).and_hms(0, 0, 0))
+    );
+
+    let res = when::en(tz_aware, "tomorrow at noon", &when::ParseConfig::default());
+    assert_eq!(res.len(), 1);
+    assert_eq!(
+        res[0].as_ref().unwrap().resolve(tz_aware),
+        Ok(tz.ymd(2018, 8, 4).and_hms(12, 0, 0))
+    );
+
+    // matches with other words between them stay apart
+    let res = when::en(
+        tz_aware,
+        "next friday or 5pm",
+        &when::ParseConfig::default(),
+    );
+    assert_eq!(res.len(), 2);
+}
+
+#[test]
+fn test_approximate() {
+    let tz_aware = chrono_tz::Europe::Moscow.from_utc_datetime(&fixed_time());
+
+    let exact = when::en(tz_aware, "5pm", &when::ParseConfig::default());
+    let exact = exact[0].as_ref().unwrap();
+    assert!(!exact.is_approximate());
+
+    let res = when::en(
+        tz_aware,
+        "see you around 5pm",
+        &when::ParseConfig::default(),
+    );
+    let match_result = res[0].as_ref().unwrap();
+    assert!(match_result.is_approximate());
+    assert_eq!(match_result.get_timeshift(), exact.get_timeshift());
+    assert_eq!(match_result.get_matched_text(), "around 5pm");
+    assert_eq!(match_result.get_start_idx(), 8);
+
+    let res = when::en(tz_aware, "at about noon", &when::ParseConfig::default());
+    let match_result = res[0].as_ref().unwrap();
+    assert!(match_result.is_approximate());
+    assert_eq!(match_result.get_matched_text(), "about noon");
+
+    let res = when::en(tz_aware, "~5pm", &when::ParseConfig::default());
+    assert!(res[0].as_ref().unwrap().is_approximate());
+    assert_eq!(res[0].as_ref().unwrap().get_start_idx(), 0);
+
+    // markers are recognized regardless of the case
+    let config = when::ParseConfig {
+        preserve_case: true,
+        ..Default::default()
+    };
+    let res = when::en(tz_aware, "Around 5pm", &config);
+    let match_result = res[0].as_ref().unwrap();
+    assert!(match_result.is_approximate());
+    assert_eq!(match_result.get_start_idx(), 0);
+    assert_eq!(match_result.get_end_idx(), 10);
+
+    // markers are specific to a language
+    let res = when::de::parse(tz_aware, "etwa morgen", &when::ParseConfig::default());
+    assert!(res[0].as_ref().unwrap().is_approximate());
+    assert_eq!(res[0].as_ref().unwrap().get_matched_text(), "etwa morgen");
+
+    let res = when::fr::parse(tz_aware, "around demain", &when::ParseConfig::default());
+    assert!(!res[0].as_ref().unwrap().is_approximate());
+    assert_eq!(res[0].as_ref().unwrap().get_matched_text(), "demain");
+}
+
+#[test]
+fn test_vague_quantities() {
+    let tz = chrono_tz::Europe::Moscow;
+    let base = tz.ymd(2019, 1, 10).and_hms(19, 0, 0);
+
+    let res = when::en(
+        base,
+        "let's talk in a couple of days",
+        &when::ParseConfig::default(),
+    );
+    let match_result = res[0].as_ref().unwrap();
+    assert!(match_result.is_approximate());
+    assert_eq!(match_result.get_matched_text(), "in a couple of days");
+    assert_eq!(
+        match_result.resolve(base),
+        Ok(tz.ymd(2019, 1, 12).and_hms(19, 0, 0))
+    );
+
+    let res = when::en(base, "in a few hours", &when::ParseConfig::default());
+    let match_result = res[0].as_ref().unwrap();
+    assert!(match_result.is_approximate());
+    assert_eq!(
+        match_result.resolve(base),
+        Ok(tz.ymd(2019, 1, 10).and_hms(22, 0, 0))
+    );
+
+    let config = when::ParseConfig {
+        few_count: 5,
+        ..Default::default()
+    };
+    let res = when::en(base, "in several days", &config);
+    assert_eq!(
+        res[0].as_ref().unwrap().resolve(base),
+        Ok(tz.ymd(2019, 1, 15).and_hms(19, 0, 0))
+    );
+}
+
+#[test]
+fn test_empty_input() {
+    let tz_aware = chrono_tz::Europe::Moscow.from_utc_datetime(&fixed_time());
+    for input in &["", "   ", ",,,", " \t\n"] {
+        assert!(when::en(tz_aware, input, &when::ParseConfig::default()).is_empty());
+        assert_eq!(
+            when::en::parse_iter(tz_aware, input, &when::ParseConfig::default()).count(),
+            0
+        );
+    }
+}
+
+#[test]
+fn test_parse_with_stats() {
+    let tz_aware = chrono_tz::Europe::Moscow.from_utc_datetime(&fixed_time());
+    let config = when::ParseConfig::default();
+
+    let (res, stats) = when::en::parse_with_stats(tz_aware, "see you next fridya at 5pm", &config);
+    assert_eq!(
+        res,
+        when::en(tz_aware, "see you next fridya at 5pm", &config)
+    );
+    assert!(stats.rule_attempts > 0);
+    assert!(stats.matches >= res.len());
+    assert!(stats.distance_computations > 0);
+
+    // exact matching compares words without computing distances
+    let config = when::ParseConfig {
+        exact_match: true,
+        ..Default::default()
+    };
+    let (res, stats) = when::en::parse_with_stats(tz_aware, "next friday", &config);
+    assert_eq!(res.len(), 1);
+    assert!(stats.matches > 0);
+    assert_eq!(stats.distance_computations, 0);
+
+    // nothing is counted outside of parse_with_stats
+    let (_, stats) = when::en::parse_with_stats(tz_aware, "", &config);
+    assert_eq!(stats, when::ParseStats::default());
+}
+
+#[test]
+fn test_negated() {
+    let tz_aware = chrono_tz::Europe::Moscow.from_utc_datetime(&fixed_time());
+
+    let res = when::en(
+        tz_aware,
+        "any day but not tomorrow",
+        &when::ParseConfig::default(),
+    );
+    let match_result = res[0].as_ref().unwrap();
+    assert!(match_result.is_negated());
+    assert_eq!(match_result.get_matched_text(), "tomorrow");
+
+    let res = when::en(
+        tz_aware,
+        "except next friday at 5pm",
+        &when::ParseConfig::default(),
+    );
+    assert!(res[0].as_ref().unwrap().is_negated());
+
+    // the negation must be a separate word
+    let res = when::en(tz_aware, "piano tomorrow", &when::ParseConfig::default());
+    assert!(!res[0].as_ref().unwrap().is_negated());
+
+    let res = when::en(tz_aware, "tomorrow", &when::ParseConfig::default());
+    assert!(!res[0].as_ref().unwrap().is_negated());
+
+    // markers are recognized regardless of the case
+    let config = when::ParseConfig {
+        preserve_case: true,
+        ..Default::default()
+    };
+    for input in &["Not tomorrow", "Except tomorrow"] {
+        let res = when::en(tz_aware, input, &config);
+        assert!(res[0].as_ref().unwrap().is_negated());
+    }
+
+    // markers are specific to a language
+    let res = when::fr::parse(tz_aware, "pas demain", &when::ParseConfig::default());
+    assert!(res[0].as_ref().unwrap().is_negated());
+
+    let res = when::es::parse(tz_aware, "no mañana", &when::ParseConfig::default());
+    assert!(res[0].as_ref().unwrap().is_negated());
+
+    let res = when::fr::parse(tz_aware, "not demain", &when::ParseConfig::default());
+    assert!(!res[0].as_ref().unwrap().is_negated());
+}
+
+#[test]
+fn test_distance() {
+    let tz_aware = chrono_tz::Europe::Moscow.from_utc_datetime(&fixed_time());
+
+    let res = when::en(tz_aware, "next friday", &when::ParseConfig::default());
+    assert_eq!(res[0].as_ref().unwrap().distance(), 0);
+
+    let res = when::en(tz_aware, "next fridey", &when::ParseConfig::default());
+    assert_eq!(res[0].as_ref().unwrap().distance(), 1);
+
+    // typos of a date and a time merged together add up
+    let res = when::en(
+        tz_aware,
+        "nxet fridey at 5pm",
+        &when::ParseConfig::default(),
+    );
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].as_ref().unwrap().distance(), 2);
+}
+
+#[test]
+fn test_parse_iter() {
+    let tz_aware = chrono_tz::Europe::Moscow.from_utc_datetime(&fixed_time());
+    let config = when::ParseConfig::default();
+
+    let first = when::en::parse_iter(tz_aware, "friday or monday", &config)
+        .next()
+        .unwrap()
+        .unwrap();
+    assert_eq!(first.get_matched_text(), "friday");
+    assert_eq!(
+        first,
+        when::en(tz_aware, "friday or monday", &config)[0]
+            .clone()
+            .unwrap()
+    );
+
+    for input in &[
+        "friday and monday",
+        "Today 21:50 and tomorrow 22:00 also yesterday   5a.m.",
+        "at 25:00 then at 10:00",
+        "réunion lundi prochain, café next monday",
+    ] {
+        let all: Vec<_> = when::en::parse_iter(tz_aware, input, &config).collect();
+        assert_eq!(all, when::en(tz_aware, input, &config));
+    }
+}
+
+#[test]
+fn test_preserve_case() {
+    let tz_aware = chrono_tz::Europe::Moscow.from_utc_datetime(&fixed_time());
+    let input = "İstanbul NEXT Friday";
+
+    // lowercased "İ" takes two characters, bounds still index the original input
+    let res = when::en(tz_aware, input, &when::ParseConfig::default());
+    let match_result = res[0].as_ref().unwrap();
+    assert_eq!(match_result.get_start_idx(), 9);
+    assert_eq!(match_result.get_end_idx(), 20);
+    assert_eq!(match_result.get_matched_text(), "NEXT Friday");
+    let original: String = input
+        .chars()
+        .skip(match_result.get_start_idx())
+        .take(match_result.get_end_idx() - match_result.get_start_idx())
+        .collect();
+    assert_eq!(original, "NEXT Friday");
+
+    // matched texts keep the case of the input
+    for text in &["5pm EST", "Q1"] {
+        let res = when::en(tz_aware, text, &when::ParseConfig::default());
+        assert_eq!(res[0].as_ref().unwrap().get_matched_text(), *text);
+    }
+
+    let res: Vec<_> =
+        when::en::parse_iter(tz_aware, input, &when::ParseConfig::default()).collect();
+    assert_eq!(
+        res,
+        when::en(tz_aware, input, &when::ParseConfig::default())
+    );
+
+    let config = when::ParseConfig {
+        preserve_case: true,
+        ..Default::default()
+    };
+    let res = when::en(tz_aware, input, &config);
+    let match_result = res[0].as_ref().unwrap();
+    assert_eq!(match_result.get_start_idx(), 9);
+    assert_eq!(match_result.get_end_idx(), 20);
+    assert_eq!(match_result.get_matched_text(), "NEXT Friday");
+    assert_eq!(
+        match_result.get_timeshift(),
+        when::en(tz_aware, "next friday", &when::ParseConfig::default())[0]
+            .as_ref()
+            .unwrap()
+            .get_timeshift()
+    );
+}
+
+#[test]
+fn test_resolve() {
+    let tz = chrono_tz::Europe::Moscow;
+    let tz_aware = tz.from_utc_datetime(&fixed_time());
+
+    let res = when::en(tz_aware, "in 30 days", &when::ParseConfig::default());
+    let naive = chrono::NaiveDateTime::from_str("2018-09-02T15:34:00").unwrap();
+    assert_eq!(
+        res[0].as_ref().unwrap().resolve(tz_aware),
+        Ok(tz.from_local_datetime(&naive).unwrap())
+    );
+
+    // month of the base date is shorter than the target day
+    let base = tz.ymd(2019, 1, 31).and_hms(10, 0, 0);
+    let res = when::en(base, "3rd of february", &when::ParseConfig::default());
+    assert_eq!(
+        res[0].as_ref().unwrap().resolve(base),
+        Ok(tz.ymd(2019, 2, 3).and_hms(10, 0, 0))
+    );
+
+    let res = when::en(tz_aware, "in 10 months", &when::ParseConfig::default());
+    let naive = chrono::NaiveDateTime::from_str("2019-06-03T15:34:00").unwrap();
+    assert_eq!(
+        res[0].as_ref().unwrap().resolve(tz_aware),
+        Ok(tz.from_local_datetime(&naive).unwrap())
+    );
+
+    // 17:00 at UTC-5 is 01:00 next day in Moscow
+    let res = when::en(tz_aware, "5pm est", &when::ParseConfig::default());
+    assert_eq!(
+        res[0].as_ref().unwrap().resolve(tz_aware),
+        Ok(tz.ymd(2018, 8, 4).and_hms(1, 0, 0))
+    );
+
+    let res = when::en(tz_aware, "in 300000 years", &when::ParseConfig::default());
+    assert_eq!(
+        res[0].as_ref().unwrap().resolve(tz_aware),
+        Err(when::DateTimeError::OutOfRange {
+            what: "year".to_owned(),
+            value: 302018,
+        })
+    );
+}
+
+#[test]
+#[cfg(feature = "std-time")]
+fn test_parse_all() {
+    let segments = when::en::parse_all(
+        chrono_tz::Europe::Moscow,
+        "call me next friday please",
+        false,
+    );
+    assert_eq!(segments.len(), 3);
+    assert_eq!(
+        segments[0],
+        when::Segment::Unmatched {
+            start: 0,
+            end: 8,
+            text: "call me ".to_owned()
+        }
+    );
+    match &segments[1] {
+        when::Segment::Matched(match_result) => {
+            assert_eq!(match_result.get_matched_text(), "next friday");
+            assert_eq!(match_result.get_start_idx(), 8);
+            assert_eq!(match_result.get_end_idx(), 19);
+        }
+        segment => panic!("unexpected segment {:?}", segment),
+    }
+    assert_eq!(
+        segments[2],
+        when::Segment::Unmatched {
+            start: 19,
+            end: 26,
+            text: " please".to_owned()
+        }
+    );
+
+    let segments = when::en::parse_all(chrono_tz::Europe::Moscow, "next friday", false);
+    assert_eq!(segments.len(), 1);
+}
+
+#[test]
+#[cfg(feature = "std-time")]
+fn test_parse_to_datetimes() {
+    // shifts by whole minutes drop the seconds, so the result is compared to the minute start
+    let before = chrono::Utc::now()
+        .with_second(0)
+        .unwrap()
+        .with_nanosecond(0)
+        .unwrap();
+    let res = when::en::parse_to_datetimes(chrono::Utc, "in 2 hours or at 25:00", false);
+    assert_eq!(res.len(), 2);
+
+    let shift = res[0].as_ref().unwrap().clone() - before;
+    assert!(shift.num_seconds() >= 2 * 3600 && shift.num_seconds() <= 2 * 3600 + 60);
+    assert!(res[1].is_err());
+}
+
+#[test]
+fn test_parse_at() {
+    let tz = chrono_tz::Europe::Moscow;
+    let now = tz.from_utc_datetime(&fixed_time());
+
+    let res = when::en::parse_at(tz, "tomorrow", false, now);
+    let naive = chrono::NaiveDateTime::from_str("2018-08-04T15:34:00").unwrap();
+    assert_eq!(res, vec![Ok(tz.from_local_datetime(&naive).unwrap())]);
+
+    let res = when::en::parse_at(tz, "yesterday", true, now);
+    let naive = chrono::NaiveDateTime::from_str("2018-08-02T15:34:00").unwrap();
+    assert_eq!(res, vec![Ok(tz.from_local_datetime(&naive).unwrap())]);
+}
+
+#[test]
+#[cfg(feature = "std-time")]
+fn test_parse_utc_and_offset() {
+    // the date/time is absolute, so it doesn't depend on the current time
+    let input = "march 5 2020 at 5pm";
+
+    assert_eq!(
+        when::en::parse_utc(input, false),
+        when::en::parse_to_datetimes(chrono::Utc, input, false)
+    );
+    assert_eq!(
+        when::en::parse_utc(input, false),
+        vec![Ok(chrono::Utc.ymd(2020, 3, 5).and_hms(17, 0, 0))]
+    );
+
+    let offset = chrono::FixedOffset::east_opt(3 * 3600).unwrap();
+    assert_eq!(
+        when::en::parse_with_offset(3 * 3600, input, true),
+        when::en::parse_to_datetimes(offset, input, true)
+    );
+    assert_eq!(
+        when::en::parse_with_offset(3 * 3600, input, true),
+        vec![Ok(offset.ymd(2020, 3, 5).and_hms(17, 0, 0))]
+    );
+
+    assert_eq!(
+        when::en::parse_with_offset(86400, input, false),
+        vec![Err(when::DateTimeError::OutOfRange {
+            what: "utc offset".to_owned(),
+            value: 86400,
+        })]
+    );
+}
+
+#[test]
+fn test_summary() {
+    let tz_aware = chrono_tz::Europe::Moscow.from_utc_datetime(&fixed_time());
+
+    let res = when::en(tz_aware, "see next friday", &when::ParseConfig::default());
+    assert_eq!(
+        res[0].as_ref().unwrap().summary(),
+        "[4..15] next friday -> +7 days"
+    );
+
+    let res = when::en(tz_aware, "in 90 minutes", &when::ParseConfig::default());
+    assert_eq!(
+        res[0].as_ref().unwrap().summary(),
+        "[0..13] in 90 minutes -> +1 hour 30 minutes"
+    );
+}
+
+#[test]
+fn test_try_merge() {
+    let tz = chrono_tz::Europe::Moscow;
+    let tz_aware = tz.from_utc_datetime(&fixed_time());
+
+    // a time before a date is not merged by the parser
+    let res = when::en(tz_aware, "5pm tomorrow", &when::ParseConfig::default());
+    assert_eq!(res.len(), 2);
+    let (time, date) = (res[0].as_ref().unwrap(), res[1].as_ref().unwrap());
+
+    let merged = time.try_merge(date).unwrap();
+    assert_eq!(merged.get_start_idx(), 0);
+    assert_eq!(merged.get_end_idx(), 12);
+    assert_eq!(merged.get_matched_text(), "5pm tomorrow");
+    assert_eq!(
+        merged.resolve(tz_aware),
+        Ok(tz.ymd(2018, 8, 4).and_hms(17, 0, 0))
+    );
+
+    // matches must follow each other
+    assert_eq!(date.try_merge(time), None);
+    assert_eq!(time.try_merge(time), None);
+
+    // two dates conflict
+    let res = when::en(
+        tz_aware,
+        "tomorrow, next friday",
+        &when::ParseConfig::default(),
+    );
+    assert_eq!(res.len(), 2);
+    let (first, second) = (res[0].as_ref().unwrap(), res[1].as_ref().unwrap());
+    assert_eq!(first.try_merge(second), None);
+}
+
+#[test]
+fn test_resolve_range() {
+    let tz = chrono_tz::Europe::Moscow;
+    let tz_aware = tz.from_utc_datetime(&fixed_time());
+
+    let res = when::en(tz_aware, "mon-fri", &when::ParseConfig::default());
+    let match_result = res[0].as_ref().unwrap();
+    assert!(match_result.is_range());
+
+    let naive = chrono::NaiveDateTime::from_str("2018-08-10T15:34:00").unwrap();
+    assert_eq!(
+        match_result.resolve_end(tz_aware),
+        Ok(Some(tz.from_local_datetime(&naive).unwrap()))
+    );
+
+    let res = when::en(tz_aware, "next friday", &when::ParseConfig::default());
+    assert!(!res[0].as_ref().unwrap().is_range());
+    assert_eq!(res[0].as_ref().unwrap().resolve_end(tz_aware), Ok(None));
+}
+
+#[test]
+fn test_matches_after_error() {
+    let tz_aware = chrono_tz::Europe::Moscow.from_utc_datetime(&fixed_time());
+
+    let res = when::en(
+        tz_aware,
+        "garbage then next friday",
+        &when::ParseConfig::default(),
+    );
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].as_ref().unwrap().get_start_idx(), 13);
+
+    // invalid time doesn't prevent the rule from matching the rest of the string
+    let res = when::en(
+        tz_aware,
+        "at 25:00 then at 10:00",
+        &when::ParseConfig::default(),
+    );
+    assert_eq!(res.len(), 2);
+    assert!(res[0].is_err());
+    let match_result = res[1].as_ref().unwrap();
+    assert_eq!(match_result.get_start_idx(), 17);
+    assert_eq!(match_result.get_end_idx(), 22);
+
+    let res = when::en(tz_aware, "32/13 and 20/12", &when::ParseConfig::default());
+    assert_eq!(res.len(), 2);
+    assert!(res[0].is_err());
+    assert_eq!(res[1].as_ref().unwrap().get_start_idx(), 10);
+
+    // symbols which are not a part of any word don't stop the rules either
+    let res = when::en(
+        tz_aware,
+        "not on 03/05/2019 but next friday / tomorrow",
+        &when::ParseConfig::default(),
+    );
+    assert_eq!(res.len(), 3);
+    assert_eq!(res[1].as_ref().unwrap().get_start_idx(), 22);
+    assert_eq!(res[2].as_ref().unwrap().get_start_idx(), 36);
+}
+
+#[test]
+fn test_weekday_list() {
+    let tz_aware = chrono_tz::Europe::Moscow.from_utc_datetime(&fixed_time());
+
+    let res = when::en(
+        tz_aware,
+        "classes on monday, wednesday and friday at 6pm",
+        &when::ParseConfig::default(),
+    );
+    assert_eq!(res.len(), 1);
+    let match_result = res[0].as_ref().unwrap();
+    assert!(match_result.is_list());
+    assert_eq!(
+        match_result.get_matched_text(),
+        "monday, wednesday and friday at 6pm"
+    );
+
+    let dates = match_result.resolve_list(tz_aware).unwrap();
+    assert_eq!(dates.len(), 3);
+    for date in &dates {
+        assert_eq!(date.hour(), 18);
+    }
+    assert_eq!(
+        dates.iter().map(|date| date.weekday()).collect::<Vec<_>>(),
+        vec![Weekday::Mon, Weekday::Wed, Weekday::Fri]
+    );
+}
+
+#[test]
+fn test_adjustment() {
+    let tz = chrono_tz::Europe::Moscow;
+    let tz_aware = tz.from_utc_datetime(&fixed_time());
+
+    let res = when::en(
+        tz_aware,
+        "next friday plus 2 days",
+        &when::ParseConfig::default(),
+    );
+    assert_eq!(res.len(), 1);
+    let match_result = res[0].as_ref().unwrap();
+    assert_eq!(match_result.get_matched_text(), "next friday plus 2 days");
+    assert_eq!(
+        match_result.resolve(tz_aware),
+        Ok(tz.ymd(2018, 8, 12).and_hms(15, 34, 0))
+    );
+
+    // the adjustment goes after the absolute values, today's noon has passed already
+    let res = when::en(
+        tz_aware,
+        "call me at noon minus 30 minutes.",
+        &when::ParseConfig::default(),
+    );
+    assert_eq!(res.len(), 1);
+    let match_result = res[0].as_ref().unwrap();
+    assert_eq!(match_result.get_matched_text(), "noon minus 30 minutes");
+    assert_eq!(
+        match_result.resolve(tz_aware),
+        Ok(tz.ymd(2018, 8, 4).and_hms(11, 30, 0))
+    );
+
+    // a dash is not a minus
+    let res = when::en(tz_aware, "noon - 30 minutes", &when::ParseConfig::default());
+    let match_result = res[0].as_ref().unwrap();
+    assert_eq!(match_result.get_matched_text(), "noon");
+    assert_eq!(
+        match_result.resolve(tz_aware),
+        Ok(tz.ymd(2018, 8, 4).and_hms(12, 0, 0))
+    );
+
+    // neither is it a range of hours before a unit
+    let res = when::en(tz_aware, "5pm - 5 minutes", &when::ParseConfig::default());
+    let match_result = res[0].as_ref().unwrap();
+    assert_eq!(match_result.get_matched_text(), "5pm");
+    assert!(!match_result.is_range());
+
+    // lazy parsing continues after the adjustment
+    let res = when::en::parse_iter(
+        tz_aware,
+        "tomorrow plus 1 week",
+        &when::ParseConfig::default(),
+    )
+    .next()
+    .unwrap()
+    .unwrap();
+    assert_eq!(res.get_matched_text(), "tomorrow plus 1 week");
+
+    // the adjustment is a part of the English rules only
+    let res = when::fr::parse(
+        tz_aware,
+        "lundi prochain plus 2 days",
+        &when::ParseConfig::default(),
+    );
+    assert_eq!(
+        res[0].as_ref().unwrap().get_matched_text(),
+        "lundi prochain"
+    );
+}
+
+#[test]
+fn test_char_bounds() {
+    let tz_aware = chrono_tz::Europe::Moscow.from_utc_datetime(&fixed_time());
+    let input = "café tomorrow, thé next friday, crème brûlée in 2 days";
+    let config = when::ParseConfig::default();
+
+    let bounds = |res: Vec<Result<when::MatchResult, when::DateTimeError>>| {
+        res.into_iter()
+            .map(|item| {
+                let item = item.unwrap();
+                (item.get_start_idx(), item.get_end_idx())
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let expected = vec![(5, 13), (19, 30), (45, 54)];
+    assert_eq!(bounds(when::en(tz_aware, input, &config)), expected);
+    assert_eq!(
+        bounds(when::en::parse_iter(tz_aware, input, &config).collect()),
+        expected
+    );
+}
+
+#[test]
+fn test_resolve_years() {
+    let tz = chrono_tz::Europe::Moscow;
+    // leap day
+    let base = tz.ymd(2020, 2, 29).and_hms(10, 0, 0);
+
+    let resolve = |input| {
+        let res = when::en(base, input, &when::ParseConfig::default());
+        res[0].as_ref().unwrap().resolve(base)
+    };
+
+    // february 29th doesn't exist in the target year, the last day of february is taken
+    assert_eq!(
+        resolve("in 1 year"),
+        Ok(tz.ymd(2021, 2, 28).and_hms(10, 0, 0))
+    );
+    assert_eq!(
+        resolve("3 years ago"),
+        Ok(tz.ymd(2017, 2, 28).and_hms(10, 0, 0))
+    );
+    assert_eq!(
+        resolve("4 years from now"),
+        Ok(tz.ymd(2024, 2, 29).and_hms(10, 0, 0))
+    );
+    // months before the beginning of the year go to the previous year
+    assert_eq!(
+        resolve("3 months ago"),
+        Ok(tz.ymd(2019, 11, 29).and_hms(10, 0, 0))
+    );
+}
+
+#[test]
+fn test_calendar_shift() {
+    let tz = chrono_tz::Europe::Moscow;
+    let resolve = |base: chrono::DateTime<chrono_tz::Tz>, input| {
+        let res = when::en(base, input, &when::ParseConfig::default());
+        res[0].as_ref().unwrap().resolve(base)
+    };
+
+    // months have different lengths, the last day of the target month is taken
+    let base = tz.ymd(2019, 1, 31).and_hms(10, 0, 0);
+    assert_eq!(
+        resolve(base, "in a month"),
+        Ok(tz.ymd(2019, 2, 28).and_hms(10, 0, 0))
+    );
+    let base = tz.ymd(2019, 3, 31).and_hms(10, 0, 0);
+    assert_eq!(
+        resolve(base, "next month"),
+        Ok(tz.ymd(2019, 4, 30).and_hms(10, 0, 0))
+    );
+    // february 29th exists in leap years only
+    let base = tz.ymd(2020, 1, 31).and_hms(10, 0, 0);
+    assert_eq!(
+        resolve(base, "in 1 month"),
+        Ok(tz.ymd(2020, 2, 29).and_hms(10, 0, 0))
+    );
+    let base = tz.ymd(2019, 12, 31).and_hms(10, 0, 0);
+    assert_eq!(
+        resolve(base, "in 14 months"),
+        Ok(tz.ymd(2021, 2, 28).and_hms(10, 0, 0))
+    );
+
+    // the shift is relative, so it follows the base it is resolved against
+    let parsed = when::en(base, "in 2 months", &when::ParseConfig::default());
+    let parsed = parsed[0].as_ref().unwrap();
+    assert_eq!(parsed.summary(), "[0..11] in 2 months -> +2 months");
+    let other = tz.ymd(2019, 7, 15).and_hms(9, 0, 0);
+    assert_eq!(
+        parsed.resolve(other),
+        Ok(tz.ymd(2019, 9, 15).and_hms(9, 0, 0))
+    );
+}
+
+#[test]
+fn test_holidays() {
+    let tz = chrono_tz::Europe::Moscow;
+    let base = tz.ymd(2024, 3, 1).and_hms(10, 0, 0);
+    let resolve = |input| {
+        let res = when::en(base, input, &when::ParseConfig::default());
+        res[0].as_ref().unwrap().resolve(base)
+    };
+
+    assert_eq!(
+        resolve("easter at 11am"),
+        Ok(tz.ymd(2024, 3, 31).and_hms(11, 0, 0))
+    );
+    assert_eq!(
+        resolve("on christmas at noon"),
+        Ok(tz.ymd(2024, 12, 25).and_hms(12, 0, 0))
+    );
+    assert_eq!(
+        resolve("new year's day"),
+        Ok(tz.ymd(2025, 1, 1).and_hms(10, 0, 0))
+    );
+}
+
+#[test]
+fn test_seasons() {
+    let parser = when::parser::Parser::new(chrono_tz::Europe::Moscow);
+    assert_date_time(parser, "see you in summer", &["2019-06-01T15:34:00"], 1);
+
+    let parser =
+        when::parser::Parser::new(chrono_tz::Europe::Moscow).hemisphere(when::Hemisphere::Southern);
+    assert_date_time(parser, "see you in summer", &["2018-12-01T15:34:00"], 1);
+
+    let parser = when::parser::Parser::new(chrono_tz::Europe::Moscow)
+        .hemisphere(when::Hemisphere::Southern)
+        .season_convention(when::SeasonConvention::Astronomical);
+    assert_date_time(parser, "see you in summer", &["2018-12-21T15:34:00"], 1);
+}
+
+#[test]
+fn test_custom_rules() {
+    let tz = chrono_tz::Europe::Moscow;
+    let base = tz.ymd(2019, 1, 10).and_hms(10, 0, 0);
+
+    let payday = |input: &str, _: &when::ParseConfig, _| {
+        let start = input.find("payday")?;
+        let mut ctx = when::Context::default();
+        ctx.day = Some(25);
+        Some(when::MatchResult::new(ctx, start, start + "payday".len()))
+    };
+
+    let registry = when::RuleRegistry::default_english().with_rule(payday);
+    let res = registry.parse(
+        base,
+        "Payday at 5pm, then payday next month",
+        &when::ParseConfig::default(),
+    );
+    assert_eq!(res.len(), 3);
+    let first = res[0].as_ref().unwrap();
+    assert_eq!(first.get_matched_text(), "Payday at 5pm");
+    assert_eq!(
+        first.resolve(base),
+        Ok(tz.ymd(2019, 1, 25).and_hms(17, 0, 0))
+    );
+    assert_eq!(res[1].as_ref().unwrap().get_start_idx(), 20);
+    assert_eq!(res[2].as_ref().unwrap().get_matched_text(), "next month");
+
+    // the config filters built-in rules only
+    let registry = when::RuleRegistry::empty().with_rule(payday);
+    let config = when::ParseConfig {
+        rules: when::RuleSet::only(&[]),
+        ..Default::default()
+    };
+    let res = registry.parse(base, "payday at 5pm", &config);
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].as_ref().unwrap().get_matched_text(), "payday");
+
+    // empty matches are ignored
+    let registry = when::RuleRegistry::empty().with_rule(|_: &str, _: &when::ParseConfig, _| {
+        Some(when::MatchResult::new(when::Context::default(), 0, 0))
+    });
+    assert!(registry
+        .parse(base, "anything", &when::ParseConfig::default())
+        .is_empty());
+}
+
+#[test]
+fn test_weekday_of_month() {
+    let tz = chrono_tz::Europe::Moscow;
+    let base = tz.ymd(2019, 1, 1).and_hms(10, 0, 0);
+    let parse = |input| when::en(base, input, &when::ParseConfig::default());
+
+    let res = parse("standup the first monday of the month at 11am");
+    assert_eq!(res.len(), 1);
+    assert_eq!(
+        res[0].as_ref().unwrap().resolve(base),
+        Ok(tz.ymd(2019, 1, 7).and_hms(11, 0, 0))
+    );
+
+    let res = parse("last friday of march");
+    assert_eq!(res.len(), 1);
+    assert_eq!(
+        res[0].as_ref().unwrap().resolve(base),
+        Ok(tz.ymd(2019, 3, 29).and_hms(10, 0, 0))
+    );
+
+    // february of 2019 has four mondays only
+    let res = parse("the fifth monday of february");
+    assert_eq!(
+        res,
+        vec![Err(when::DateTimeError::InvalidDate {
+            text: "the fifth monday of february".to_owned(),
+            what: "occurrence".to_owned(),
+            value: 5,
+        })]
+    );
+}
+
+#[test]
+fn test_prefer_future() {
+    let tz = chrono_tz::Europe::Moscow;
+    let base = tz.ymd(2019, 1, 10).and_hms(19, 0, 0);
+    let resolve = |input, config: &when::ParseConfig| {
+        let res = when::en(base, input, config);
+        assert_eq!(res.len(), 1);
+        res[0].as_ref().unwrap().resolve(base)
+    };
+
+    let future = when::ParseConfig::default();
+    assert_eq!(
+        resolve("at midnight", &future),
+        Ok(tz.ymd(2019, 1, 11).and_hms(0, 0, 0))
+    );
+    assert_eq!(
+        resolve("noon", &future),
+        Ok(tz.ymd(2019, 1, 11).and_hms(12, 0, 0))
+    );
+    assert_eq!(
+        resolve("at 5", &future),
+        Ok(tz.ymd(2019, 1, 11).and_hms(5, 0, 0))
+    );
+    // a date decides the day on its own
+    assert_eq!(
+        resolve("friday at midnight", &future),
+        Ok(tz.ymd(2019, 1, 11).and_hms(0, 0, 0))
+    );
+    assert_eq!(
+        resolve("tomorrow at 5", &future),
+        Ok(tz.ymd(2019, 1, 11).and_hms(5, 0, 0))
+    );
+
+    let today = when::ParseConfig {
+        prefer_future: false,
+        ..Default::default()
+    };
+    assert_eq!(
+        resolve("at midnight", &today),
+        Ok(tz.ymd(2019, 1, 10).and_hms(0, 0, 0))
+    );
+    assert_eq!(
+        resolve("noon", &today),
+        Ok(tz.ymd(2019, 1, 10).and_hms(12, 0, 0))
+    );
+    assert_eq!(
+        resolve("at 5", &today),
+        Ok(tz.ymd(2019, 1, 10).and_hms(17, 0, 0))
+    );
+}
+
+#[test]
+fn test_relative_days() {
+    let tz = chrono_tz::Europe::Moscow;
+    let resolve = |base: chrono::DateTime<chrono_tz::Tz>, input| {
+        let res = when::en(base, input, &when::ParseConfig::default());
+        assert_eq!(res.len(), 1);
+        res[0].as_ref().unwrap().resolve(base)
+    };
+
+    // the time of day of the reference is kept unless a time is given
+    let base = tz.ymd(2018, 12, 31).and_hms(19, 30, 0);
+    assert_eq!(resolve(base, "today"), Ok(base));
+    assert_eq!(
+        resolve(base, "tomorrow"),
+        Ok(tz.ymd(2019, 1, 1).and_hms(19, 30, 0))
+    );
+    assert_eq!(
+        resolve(base, "yesterday"),
+        Ok(tz.ymd(2018, 12, 30).and_hms(19, 30, 0))
+    );
+    assert_eq!(
+        resolve(base, "today at 9am"),
+        Ok(tz.ymd(2018, 12, 31).and_hms(9, 0, 0))
+    );
+    assert_eq!(
+        resolve(base, "tomorrow at 9am"),
+        Ok(tz.ymd(2019, 1, 1).and_hms(9, 0, 0))
+    );
+
+    let base = tz.ymd(2019, 3, 1).and_hms(8, 0, 0);
+    assert_eq!(
+        resolve(base, "yesterday 23:15"),
+        Ok(tz.ymd(2019, 2, 28).and_hms(23, 15, 0))
+    );
+    assert_eq!(
+        resolve(base, "the day before yesterday"),
+        Ok(tz.ymd(2019, 2, 27).and_hms(8, 0, 0))
+    );
+
+    // the days may start at midnight instead, a time of day still wins
+    let config = when::ParseConfig {
+        zero_time_of_day: true,
+        ..Default::default()
+    };
+    let midnight = |input| {
+        let res = when::en(base, input, &config);
+        assert_eq!(res.len(), 1);
+        res[0]
+            .as_ref()
+            .unwrap()
+            .resolve(base)
+            .unwrap()
+            .naive_local()
+    };
+    let naive = |s| NaiveDateTime::from_str(s).unwrap();
+    assert_eq!(midnight("today"), naive("2019-03-01T00:00:00"));
+    assert_eq!(midnight("tomorrow"), naive("2019-03-02T00:00:00"));
+    assert_eq!(midnight("yesterday"), naive("2019-02-28T00:00:00"));
+    assert_eq!(midnight("tomorrow at 5pm"), naive("2019-03-02T17:00:00"));
+    assert_eq!(midnight("yesterday evening"), naive("2019-02-28T19:00:00"));
+}
+
+#[test]
+fn test_minutes_to_hour() {
+    let tz = chrono_tz::Europe::Moscow;
+    let base = tz.ymd(2019, 1, 10).and_hms(3, 0, 0);
+    let parse = |input| when::en(base, input, &when::ParseConfig::default());
+
+    let res = parse("ten to six");
+    assert_eq!(res.len(), 1);
+    let match_result = res[0].as_ref().unwrap();
+    assert_eq!(match_result.get_matched_text(), "ten to six");
+    assert!(!match_result.is_range());
+    assert_eq!(
+        match_result.resolve(base),
+        Ok(tz.ymd(2019, 1, 10).and_hms(5, 50, 0))
+    );
+
+    // a range of numbers is not a time
+    assert!(parse("read pages 10 to 20").is_empty());
+}
+
+#[test]
+fn test_between() {
+    let tz = chrono_tz::Europe::Moscow;
+    // thursday
+    let base = tz.ymd(2019, 1, 10).and_hms(10, 0, 0);
+    let parse = |input| when::en(base, input, &when::ParseConfig::default());
+
+    let res = parse("tomorrow between 2 and 4pm");
+    assert_eq!(res.len(), 1);
+    let match_result = res[0].as_ref().unwrap();
+    assert_eq!(
+        match_result.resolve(base),
+        Ok(tz.ymd(2019, 1, 11).and_hms(14, 0, 0))
+    );
+    assert_eq!(
+        match_result.resolve_end(base),
+        Ok(Some(tz.ymd(2019, 1, 11).and_hms(16, 0, 0)))
+    );
+
+    // a range over midnight ends on the next day
+    for (input, end) in &[
+        ("from 9pm to 2am", "2019-01-11T02:00:00"),
+        ("10pm-1am", "2019-01-11T01:00:00"),
+        ("9 to 5", "2019-01-10T17:00:00"),
+    ] {
+        let res = parse(input);
+        assert_eq!(res.len(), 1, "{}", input);
+        let resolved = res[0].as_ref().unwrap().resolve_end(base).unwrap().unwrap();
+        assert_eq!(
+            resolved.naive_local(),
+            NaiveDateTime::from_str(end).unwrap(),
+            "{}",
+            input
+        );
+    }
+
+    // the range wins over the list of days inside of it
+    let res = parse("off between monday and thursday");
+    assert_eq!(res.len(), 1);
+    let match_result = res[0].as_ref().unwrap();
+    assert!(match_result.is_range());
+    assert!(!match_result.is_list());
+    assert_eq!(
+        match_result.get_matched_text(),
+        "between monday and thursday"
+    );
+    assert_eq!(
+        match_result.resolve(base),
+        Ok(tz.ymd(2019, 1, 14).and_hms(10, 0, 0))
+    );
+    assert_eq!(
+        match_result.resolve_end(base),
+        Ok(Some(tz.ymd(2019, 1, 17).and_hms(10, 0, 0)))
+    );
+}
+
+// the reference time is always given explicitly, so it works without the std-time feature
+#[test]
+fn test_explicit_time() {
+    let tz = chrono_tz::Europe::Moscow;
+    let now = tz.ymd(2019, 1, 10).and_hms(12, 0, 0);
+
+    assert_eq!(
+        when::en::parse_at(tz, "next friday at 5pm", false, now),
+        vec![Ok(tz.ymd(2019, 1, 11).and_hms(17, 0, 0))]
+    );
+
+    let segments = when::en::parse_all_at(tz, "see you tomorrow", false, now);
+    assert_eq!(segments.len(), 2);
+    match &segments[1] {
+        when::Segment::Matched(match_result) => {
+            assert_eq!(
+                match_result.resolve(now),
+                Ok(tz.ymd(2019, 1, 11).and_hms(12, 0, 0))
+            );
+        }
+        segment => panic!("unexpected segment {:?}", segment),
+    }
+
+    let parser = Parser::new(tz);
+    assert_eq!(
+        parser.parse_fixed_time(now.naive_utc(), "in 2 hours"),
+        vec![Ok(tz.ymd(2019, 1, 10).and_hms(14, 0, 0))]
+    );
+}
+
+#[test]
+fn test_part_of_day_meridiem() {
+    let tz = chrono_tz::Europe::Moscow;
+    let base = tz.ymd(2019, 1, 10).and_hms(10, 0, 0);
+    let parse = |input| when::en(base, input, &when::ParseConfig::default());
+
+    let res = parse("tomorrow at 5 in the afternoon");
+    assert_eq!(res.len(), 1);
+    assert_eq!(
+        res[0].as_ref().unwrap().resolve(base),
+        Ok(tz.ymd(2019, 1, 11).and_hms(17, 0, 0))
+    );
+
+    let res = parse("the flight is at 3 in the morning");
+    assert_eq!(res.len(), 1);
+    let match_result = res[0].as_ref().unwrap();
+    assert_eq!(match_result.get_matched_text(), "3 in the morning");
+    assert_eq!(
+        match_result.resolve(base),
+        Ok(tz.ymd(2019, 1, 10).and_hms(3, 0, 0))
+    );
+
+    // a part of the day alone is still a casual time
+    let res = parse("call me in the evening");
+    assert_eq!(res[0].as_ref().unwrap().get_matched_text(), "evening");
+}
+
+#[test]
+fn test_coming() {
+    let tz = chrono_tz::Europe::Moscow;
+    // thursday
+    let base = tz.ymd(2019, 1, 10).and_hms(10, 0, 0);
+    let resolve = |input| {
+        let res = when::en(base, input, &when::ParseConfig::default());
+        assert_eq!(res.len(), 1);
+        res[0].as_ref().unwrap().resolve(base)
+    };
+
+    let friday = Ok(tz.ymd(2019, 1, 11).and_hms(10, 0, 0));
+    assert_eq!(resolve("this coming friday"), friday);
+    assert_eq!(resolve("see you friday coming"), friday);
+    assert_eq!(resolve("next friday"), friday);
+    assert_eq!(
+        resolve("the coming month"),
+        Ok(tz.ymd(2019, 2, 10).and_hms(10, 0, 0))
+    );
+}
+
+#[test]
+fn test_relative_week_is_no_week_number() {
+    let tz = chrono_tz::Europe::Moscow;
+    let now = tz.from_utc_datetime(&fixed_time());
+
+    let res = when::en::parse(now, "next week 10:00", &Default::default());
+    assert_eq!(res.len(), 1);
+    let res = res[0].as_ref().unwrap();
+    assert_eq!(res.get_matched_text(), "next week 10:00");
+    let naive = chrono::NaiveDateTime::from_str("2018-08-10T10:00:00").unwrap();
+    assert_eq!(res.resolve(now).unwrap().naive_local(), naive);
+
+    let res = when::en::parse(now, "in 2 weeks 3 days", &Default::default());
+    assert_eq!(res.len(), 1);
+    let res = res[0].as_ref().unwrap();
+    assert_eq!(res.get_matched_text(), "in 2 weeks");
+    let naive = chrono::NaiveDateTime::from_str("2018-08-17T15:34:00").unwrap();
+    assert_eq!(res.resolve(now).unwrap().naive_local(), naive);
 }
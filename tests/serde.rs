@@ -0,0 +1,16 @@
+#![cfg(feature = "serde")]
+
+use chrono::{offset::TimeZone, NaiveDate};
+use when::{MatchResult, ParseConfig};
+
+#[test]
+fn test_match_result_round_trip() {
+    let tz_aware = chrono_tz::Europe::Moscow
+        .from_utc_datetime(&NaiveDate::from_ymd(2018, 8, 3).and_hms(12, 34, 56));
+    let res = when::en(tz_aware, "next friday", &ParseConfig::default());
+    let match_result = res[0].clone().unwrap();
+
+    let json = serde_json::to_string(&match_result).unwrap();
+    let restored: MatchResult = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, match_result);
+}